@@ -2,24 +2,37 @@
 //!
 //! A simple CLI tool for searching via DuckDuckGo and ArXiv.
 
+use chrono::{NaiveDate, Utc};
 use clap::{Parser, ValueEnum};
 use colored::*;
+use std::path::PathBuf;
 use websearch::{
-    providers::*,
-    types::{DebugOptions, SafeSearch, SearchOptions, SortBy, SortOrder},
-    web_search,
+    citation,
+    error::SearchError,
+    providers::{
+        arxiv::{ArxivDateRange, ArxivQuery},
+        *,
+    },
+    types::{DebugOptions, SafeSearch, SearchOptions, SearchResponse, SortBy, SortOrder},
+    web_search_response,
 };
 
 #[derive(Parser)]
 #[command(name = "websearch")]
-#[command(about = "Web search CLI (DuckDuckGo & ArXiv)")]
+#[command(about = "Web search CLI (DuckDuckGo, ArXiv & more)")]
 #[command(version)]
 struct Cli {
     /// Search query
     #[arg(value_name = "QUERY")]
     query: Option<String>,
 
-    /// Search provider (duckduckgo or arxiv)
+    /// Search provider
+    ///
+    /// Semantic Scholar reads an optional API key from SEMANTIC_SCHOLAR_API_KEY,
+    /// PubMed from NCBI_API_KEY. Crossref and OpenAlex join their polite pools
+    /// when WEBSEARCH_MAILTO is set. GitHub reads a token from GITHUB_TOKEN,
+    /// Stack Exchange an application key from STACKEXCHANGE_KEY. Elasticsearch
+    /// reads ELASTICSEARCH_URL, ELASTICSEARCH_INDEX and ELASTICSEARCH_API_KEY.
     #[arg(short, long, value_enum, default_value = "duckduckgo")]
     provider: Option<Provider>,
 
@@ -43,11 +56,47 @@ struct Cli {
     #[arg(long)]
     arxiv_ids: Option<String>,
 
-    /// Sort by field (for ArXiv)
+    /// PubMed IDs (comma-separated, for PubMed)
+    #[arg(long)]
+    pmids: Option<String>,
+
+    /// Site to search, e.g. "superuser" (for Stack Exchange)
+    #[arg(long, default_value = "stackoverflow")]
+    site: String,
+
+    /// Required tags, semicolon-separated, e.g. "rust;tokio" (for Stack Exchange)
+    #[arg(long)]
+    tagged: Option<String>,
+
+    /// Directory to index and search (required for Local)
+    #[arg(long, value_name = "DIR", required_if_eq("provider", "local"))]
+    index_dir: Option<PathBuf>,
+
+    /// Restrict to words in the title (for ArXiv)
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Restrict to an author, e.g. "hinton" or "del_maestro" (for ArXiv)
+    #[arg(long)]
+    author: Option<String>,
+
+    /// Restrict to a subject category, e.g. "cs.CL" (for ArXiv)
+    #[arg(long)]
+    category: Option<String>,
+
+    /// Only papers submitted on or after this date, YYYY-MM-DD (for ArXiv)
+    #[arg(long, value_name = "DATE")]
+    submitted_from: Option<NaiveDate>,
+
+    /// Only papers submitted on or before this date, YYYY-MM-DD (for ArXiv)
+    #[arg(long, value_name = "DATE")]
+    submitted_to: Option<NaiveDate>,
+
+    /// Sort by field (for ArXiv, GitHub, Stack Exchange and Hacker News)
     #[arg(long, value_enum)]
     sort_by: Option<SortByCli>,
 
-    /// Sort order (for ArXiv)
+    /// Sort order (for ArXiv, GitHub and Stack Exchange)
     #[arg(long, value_enum)]
     sort_order: Option<SortOrderCli>,
 
@@ -68,6 +117,32 @@ struct Cli {
 enum Provider {
     Duckduckgo,
     Arxiv,
+    /// Today's announcements; the query names categories, e.g. "cs.CL+cs.LG"
+    ArxivNew,
+    SemanticScholar,
+    Pubmed,
+    Crossref,
+    Openalex,
+    /// GitHub repositories; the query accepts qualifiers like "language:rust"
+    Github,
+    /// GitHub code (requires GITHUB_TOKEN)
+    GithubCode,
+    /// GitHub issues and pull requests
+    GithubIssues,
+    /// Stack Exchange questions; pick the site with --site
+    Stackexchange,
+    /// Hacker News stories; --sort-by submitted-date lists newest first
+    Hackernews,
+    /// crates.io
+    Crates,
+    /// npm registry
+    Npm,
+    /// PyPI; the query names one or more projects
+    Pypi,
+    /// Markdown, text and HTML files under --index-dir, searched offline
+    Local,
+    /// An Elasticsearch or OpenSearch index (set ELASTICSEARCH_INDEX)
+    Elasticsearch,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -94,12 +169,17 @@ enum SortOrderCli {
 enum OutputFormat {
     Table,
     Json,
+    JsonResponse,
     Simple,
+    Bibtex,
+    Ris,
+    CslJson,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
+    let arxiv_query = build_arxiv_query(&cli)?;
 
     if let Some(query) = cli.query {
         let provider = cli.provider.unwrap_or(Provider::Duckduckgo);
@@ -113,6 +193,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             cli.region,
             cli.safe_search,
             cli.arxiv_ids,
+            cli.pmids,
+            cli.site,
+            cli.tagged,
+            cli.index_dir,
+            arxiv_query,
             cli.sort_by,
             cli.sort_order,
             cli.debug,
@@ -130,6 +215,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_search(
     query: String,
     provider: Provider,
@@ -138,6 +224,11 @@ async fn handle_search(
     region: Option<String>,
     safe_search: Option<SafeSearchCli>,
     arxiv_ids: Option<String>,
+    pmids: Option<String>,
+    site: String,
+    tagged: Option<String>,
+    index_dir: Option<PathBuf>,
+    arxiv_query: Option<String>,
     sort_by: Option<SortByCli>,
     sort_order: Option<SortOrderCli>,
     debug: bool,
@@ -145,22 +236,29 @@ async fn handle_search(
     format: OutputFormat,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let provider_name = format!("{:?}", provider).to_lowercase();
-    let provider_box = create_provider(provider);
+    let provider_box = create_provider(provider, site, tagged, index_dir)?;
 
-    // For ArXiv, use either query or IDs
-    let (search_query, id_list) = if provider_name == "arxiv" {
-        if let Some(ids) = arxiv_ids {
-            ("".to_string(), Some(ids))
-        } else {
-            (query.clone(), None)
-        }
+    // For ArXiv and PubMed, use either query or IDs
+    let ids = match provider_name.as_str() {
+        "arxiv" => arxiv_ids,
+        "pubmed" => pmids,
+        _ => None,
+    };
+    let (search_query, id_list) = match ids {
+        Some(ids) => ("".to_string(), Some(ids)),
+        None => (query.clone(), None),
+    };
+
+    let arxiv_query = if provider_name == "arxiv" {
+        arxiv_query
     } else {
-        (query.clone(), None)
+        None
     };
 
     let options = SearchOptions {
         query: search_query,
         id_list,
+        native_query: arxiv_query,
         max_results: Some(max_results),
         language,
         region,
@@ -191,29 +289,153 @@ async fn handle_search(
         ..Default::default()
     };
 
-    let results = web_search(options).await?;
+    let response = web_search_response(options).await?;
 
-    display_results(&results, &format, raw, &provider_name);
+    display_results(&response, &format, raw, &provider_name);
     Ok(())
 }
 
-fn create_provider(provider: Provider) -> Box<dyn websearch::types::SearchProvider> {
-    match provider {
+/// Combine the free-text query with any fielded ArXiv flags
+///
+/// Returns `None` when no fielded flag is given, so the provider falls back to
+/// its default `all:` search.
+fn build_arxiv_query(cli: &Cli) -> Result<Option<String>, SearchError> {
+    let submitted = match (cli.submitted_from, cli.submitted_to) {
+        (None, None) => None,
+        (from, to) => {
+            // arXiv's first submissions date from August 1991
+            let from = from.unwrap_or(NaiveDate::from_ymd_opt(1991, 8, 1).unwrap_or_default());
+            let to = to.unwrap_or_else(|| Utc::now().date_naive());
+            Some(ArxivQuery::submitted(ArxivDateRange::days(from, to)?))
+        }
+    };
+
+    let fielded: Vec<ArxivQuery> = [
+        cli.title.clone().map(ArxivQuery::title),
+        cli.author.clone().map(ArxivQuery::author),
+        cli.category.clone().map(ArxivQuery::category),
+        submitted,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if fielded.is_empty() {
+        return Ok(None);
+    }
+
+    let query = cli.query.as_deref().unwrap_or_default().trim();
+    let base = (!query.is_empty()).then(|| ArxivQuery::all(query));
+    base.into_iter()
+        .chain(fielded)
+        .reduce(ArxivQuery::and)
+        .map(|query| query.render())
+        .transpose()
+}
+
+fn create_provider(
+    provider: Provider,
+    site: String,
+    tagged: Option<String>,
+    index_dir: Option<PathBuf>,
+) -> Result<Box<dyn websearch::types::SearchProvider>, SearchError> {
+    Ok(match provider {
         Provider::Duckduckgo => Box::new(DuckDuckGoProvider::new()),
         Provider::Arxiv => Box::new(ArxivProvider::new()),
-    }
+        Provider::ArxivNew => Box::new(ArxivFeedProvider::new()),
+        Provider::SemanticScholar => match std::env::var("SEMANTIC_SCHOLAR_API_KEY") {
+            Ok(api_key) if !api_key.is_empty() => {
+                Box::new(SemanticScholarProvider::with_api_key(api_key))
+            }
+            _ => Box::new(SemanticScholarProvider::new()),
+        },
+        Provider::Pubmed => match std::env::var("NCBI_API_KEY") {
+            Ok(api_key) if !api_key.is_empty() => Box::new(PubMedProvider::with_api_key(api_key)),
+            _ => Box::new(PubMedProvider::new()),
+        },
+        Provider::Crossref => match std::env::var("WEBSEARCH_MAILTO") {
+            Ok(mailto) if !mailto.is_empty() => Box::new(CrossrefProvider::with_mailto(mailto)),
+            _ => Box::new(CrossrefProvider::new()),
+        },
+        Provider::Openalex => match std::env::var("WEBSEARCH_MAILTO") {
+            Ok(mailto) if !mailto.is_empty() => Box::new(OpenAlexProvider::with_mailto(mailto)),
+            _ => Box::new(OpenAlexProvider::new()),
+        },
+        Provider::Github => Box::new(github_provider(github::GitHubSearchKind::Repositories)),
+        Provider::GithubCode => Box::new(github_provider(github::GitHubSearchKind::Code)),
+        Provider::GithubIssues => Box::new(github_provider(github::GitHubSearchKind::Issues)),
+        Provider::Stackexchange => Box::new(StackExchangeProvider::with_config(
+            stackexchange::StackExchangeConfig {
+                site,
+                key: std::env::var("STACKEXCHANGE_KEY")
+                    .ok()
+                    .filter(|key| !key.is_empty()),
+                tagged: tagged
+                    .iter()
+                    .flat_map(|tags| tags.split(';'))
+                    .map(|tag| tag.trim().to_string())
+                    .filter(|tag| !tag.is_empty())
+                    .collect(),
+                ..Default::default()
+            },
+        )),
+        Provider::Hackernews => Box::new(HackerNewsProvider::new()),
+        Provider::Crates => Box::new(CratesIoProvider::new()),
+        Provider::Npm => Box::new(NpmProvider::new()),
+        Provider::Pypi => Box::new(PyPiProvider::new()),
+        Provider::Local => Box::new(LocalIndexProvider::open(index_dir.ok_or_else(|| {
+            SearchError::ConfigError("--index-dir is required for the local provider".to_string())
+        })?)?),
+        Provider::Elasticsearch => Box::new(elasticsearch_provider()),
+    })
+}
+
+fn elasticsearch_provider() -> ElasticsearchProvider {
+    let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    let defaults = elasticsearch::ElasticsearchConfig::default();
+    ElasticsearchProvider::with_config(elasticsearch::ElasticsearchConfig {
+        base_url: env("ELASTICSEARCH_URL").unwrap_or(defaults.base_url.clone()),
+        index: env("ELASTICSEARCH_INDEX").unwrap_or_default(),
+        auth: env("ELASTICSEARCH_API_KEY").map(elasticsearch::ElasticsearchAuth::ApiKey),
+        ..defaults
+    })
+}
+
+fn github_provider(kind: github::GitHubSearchKind) -> GitHubProvider {
+    GitHubProvider::with_config(github::GitHubConfig {
+        token: std::env::var("GITHUB_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty()),
+        kind,
+        ..Default::default()
+    })
 }
 
 fn display_results(
-    results: &[websearch::types::SearchResult],
+    response: &SearchResponse,
     format: &OutputFormat,
     show_raw: bool,
     provider: &str,
 ) {
+    let results = &response.results;
+
     match format {
         OutputFormat::Json => {
             println!("{}", serde_json::to_string_pretty(results).unwrap());
         }
+        OutputFormat::JsonResponse => {
+            println!("{}", serde_json::to_string_pretty(response).unwrap());
+        }
+        OutputFormat::Bibtex => {
+            print!("{}", citation::to_bibtex(results));
+        }
+        OutputFormat::Ris => {
+            print!("{}", citation::to_ris(results));
+        }
+        OutputFormat::CslJson => {
+            let items = citation::to_csl_json(results);
+            println!("{}", serde_json::to_string_pretty(&items).unwrap());
+        }
         OutputFormat::Simple => {
             for (i, result) in results.iter().enumerate() {
                 println!("{}. {}", i + 1, result.title);
@@ -226,6 +448,12 @@ fn display_results(
         }
         OutputFormat::Table => {
             println!("{} {}", "Search Results from".bold(), provider.bold().blue());
+            if let Some(corrected) = &response.corrected_query {
+                println!("{} {}", "Including results for".dimmed(), corrected.bold());
+            }
+            if let Some(suggested) = &response.suggested_query {
+                println!("{} {}", "Did you mean".dimmed(), suggested.bold());
+            }
             println!("{}", "─".repeat(80).dimmed());
 
             for (i, result) in results.iter().enumerate() {
@@ -258,7 +486,19 @@ fn display_results(
                 println!();
             }
 
-            println!("{} {}", "Total results:".bold(), results.len().to_string().bold());
+            let total = match response.total_results {
+                Some(available) => format!("{} of {}", results.len(), available),
+                None => results.len().to_string(),
+            };
+            println!("{} {}", "Total results:".bold(), total.bold());
+
+            if !response.related_searches.is_empty() {
+                println!(
+                    "{} {}",
+                    "Related searches:".bold(),
+                    response.related_searches.join(", ")
+                );
+            }
         }
     }
 }
@@ -0,0 +1,658 @@
+//! Citation export (BibTeX, RIS and CSL-JSON) for search results
+//!
+//! ArXiv results carry full paper metadata and convert losslessly. Results
+//! from other providers are converted from the conventional `raw` keys
+//! `authors` (array or comma-separated string), `doi`, `venue` and `year`,
+//! falling back to the result's `published_date`.
+
+use crate::providers::arxiv::ArxivPaper;
+use crate::types::SearchResult;
+use chrono::{Datelike, NaiveDate};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Bibliographic record for a single work
+#[derive(Debug, Clone, PartialEq)]
+pub struct Citation {
+    /// Citation key, e.g. `lovelace2023electron`
+    pub key: String,
+    /// Title of the work
+    pub title: String,
+    /// Author names in listed order
+    pub authors: Vec<String>,
+    /// Publication date, with day/month precision where known
+    pub issued: Option<PartialDate>,
+    /// DOI without a resolver prefix
+    pub doi: Option<String>,
+    /// Landing page URL
+    pub url: String,
+    /// Journal, conference or other container
+    pub venue: Option<String>,
+    /// Abstract
+    pub abstract_text: Option<String>,
+    /// arXiv identifier, for preprints
+    pub arxiv_id: Option<String>,
+    /// arXiv primary category, for preprints
+    pub arxiv_category: Option<String>,
+}
+
+/// A date known to year, month or day precision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialDate {
+    pub year: i32,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+}
+
+impl PartialDate {
+    fn from_date(date: NaiveDate) -> Self {
+        Self {
+            year: date.year(),
+            month: Some(date.month()),
+            day: Some(date.day()),
+        }
+    }
+
+    /// Parse `YYYY`, `YYYY-MM`, `YYYY-MM-DD` or an RFC 3339 timestamp
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let date_part = value.get(..10).unwrap_or(value);
+        if let Ok(date) = NaiveDate::parse_from_str(date_part, "%Y-%m-%d") {
+            return Some(Self::from_date(date));
+        }
+
+        let mut parts = value.splitn(3, ['-', '/']);
+        let year = parts.next()?.get(..4)?.parse().ok()?;
+        let month = parts
+            .next()
+            .and_then(|month| month.parse().ok())
+            .filter(|month| (1..=12).contains(month));
+        Some(Self {
+            year,
+            month,
+            day: None,
+        })
+    }
+
+    fn parts(&self) -> Vec<i64> {
+        let mut parts = vec![i64::from(self.year)];
+        if let Some(month) = self.month {
+            parts.push(i64::from(month));
+            if let Some(day) = self.day {
+                parts.push(i64::from(day));
+            }
+        }
+        parts
+    }
+}
+
+impl Citation {
+    /// Build a citation from an arXiv paper
+    pub fn from_arxiv(paper: &ArxivPaper) -> Self {
+        let mut citation = Self {
+            key: String::new(),
+            title: paper.title.clone(),
+            authors: paper
+                .authors
+                .iter()
+                .map(|author| author.name.clone())
+                .collect(),
+            issued: paper
+                .published
+                .map(|date| PartialDate::from_date(date.date_naive())),
+            doi: paper.doi.clone(),
+            url: paper.id.unversioned().abs_url(),
+            venue: paper.journal_ref.clone(),
+            abstract_text: Some(paper.summary.clone()).filter(|text| !text.is_empty()),
+            arxiv_id: Some(paper.id.unversioned().to_string()),
+            arxiv_category: paper.primary_category.clone(),
+        };
+        citation.key = citation.default_key();
+        citation
+    }
+
+    /// Build a citation from any search result
+    ///
+    /// Returns `None` when the result has neither authors nor a date, since
+    /// such a record would not be citable.
+    pub fn from_search_result(result: &SearchResult) -> Option<Self> {
+        if let Some(paper) = ArxivPaper::from_search_result(result) {
+            return Some(Self::from_arxiv(&paper));
+        }
+
+        let raw = result.raw.as_ref();
+        let raw_str = |key: &str| {
+            raw.and_then(|raw| raw.get(key))
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let authors: Vec<String> = match raw.and_then(|raw| raw.get("authors")) {
+            Some(Value::Array(names)) => names
+                .iter()
+                .filter_map(|name| name.as_str().or_else(|| name.get("name")?.as_str()))
+                .map(str::to_string)
+                .collect(),
+            Some(Value::String(names)) => names
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        let issued = result
+            .published_date
+            .as_deref()
+            .and_then(PartialDate::parse)
+            .or_else(|| {
+                let year = raw?.get("year")?;
+                let year = year
+                    .as_i64()
+                    .map(|year| year.to_string())
+                    .or_else(|| year.as_str().map(str::to_string))?;
+                PartialDate::parse(&year)
+            });
+
+        if authors.is_empty() && issued.is_none() {
+            return None;
+        }
+
+        let mut citation = Self {
+            key: String::new(),
+            title: result.title.clone(),
+            authors,
+            issued,
+            doi: raw_str("doi").map(|doi| strip_doi_prefix(&doi).to_string()),
+            url: result.url.clone(),
+            venue: raw_str("venue").or_else(|| raw_str("journal")),
+            abstract_text: result.snippet.clone(),
+            arxiv_id: raw_str("arxiv_id"),
+            arxiv_category: None,
+        };
+        citation.key = citation.default_key();
+        Some(citation)
+    }
+
+    /// Deterministic key: first author's family name, year and first
+    /// significant title word, e.g. `lovelace2023electron`
+    pub fn default_key(&self) -> String {
+        let author = self
+            .authors
+            .first()
+            .map(|name| ascii_word(&split_name(name).0))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "anon".to_string());
+        let year = self
+            .issued
+            .map(|date| date.year.to_string())
+            .unwrap_or_else(|| "nd".to_string());
+        let word = self
+            .title
+            .split_whitespace()
+            .map(ascii_word)
+            .find(|word| word.len() > 2 && !STOP_WORDS.contains(&word.as_str()))
+            .unwrap_or_default();
+
+        format!("{author}{year}{word}")
+    }
+
+    /// Render as a BibTeX entry
+    pub fn to_bibtex(&self) -> String {
+        let entry_type = if self.venue.is_some() {
+            "article"
+        } else {
+            "misc"
+        };
+        let mut fields = vec![
+            ("title", format!("{{{}}}", escape_bibtex(&self.title))),
+            (
+                "author",
+                escape_bibtex(
+                    &self
+                        .authors
+                        .iter()
+                        .map(|name| bibtex_name(name))
+                        .collect::<Vec<_>>()
+                        .join(" and "),
+                ),
+            ),
+        ];
+        if let Some(venue) = &self.venue {
+            fields.push(("journal", escape_bibtex(venue)));
+        }
+        if let Some(issued) = self.issued {
+            fields.push(("year", issued.year.to_string()));
+            // PartialDate is public, so the month may be out of range
+            if let Some(month) = issued
+                .month
+                .and_then(|month| MONTHS.get((month as usize).wrapping_sub(1)))
+            {
+                fields.push(("month", month.to_string()));
+            }
+        }
+        if let Some(doi) = &self.doi {
+            fields.push(("doi", escape_bibtex(doi)));
+        }
+        if let Some(arxiv_id) = &self.arxiv_id {
+            fields.push(("eprint", arxiv_id.clone()));
+            fields.push(("archivePrefix", "arXiv".to_string()));
+            if let Some(category) = &self.arxiv_category {
+                fields.push(("primaryClass", category.clone()));
+            }
+        }
+        fields.push(("url", self.url.clone()));
+        if let Some(abstract_text) = &self.abstract_text {
+            fields.push(("abstract", escape_bibtex(abstract_text)));
+        }
+
+        let body: Vec<String> = fields
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(name, value)| format!("  {name} = {{{value}}}"))
+            .collect();
+        format!("@{entry_type}{{{},\n{}\n}}\n", self.key, body.join(",\n"))
+    }
+
+    /// Render as an RIS record
+    pub fn to_ris(&self) -> String {
+        let mut lines = vec![format!(
+            "TY  - {}",
+            if self.venue.is_some() { "JOUR" } else { "UNPB" }
+        )];
+        lines.push(format!("ID  - {}", self.key));
+        lines.push(format!("TI  - {}", self.title));
+        for author in &self.authors {
+            let (family, given) = split_name(author);
+            if given.is_empty() {
+                lines.push(format!("AU  - {family}"));
+            } else {
+                lines.push(format!("AU  - {family}, {given}"));
+            }
+        }
+        if let Some(issued) = self.issued {
+            lines.push(format!("PY  - {}", issued.year));
+            let month = issued.month.map(|m| format!("{m:02}")).unwrap_or_default();
+            let day = issued.day.map(|d| format!("{d:02}")).unwrap_or_default();
+            lines.push(format!("DA  - {}/{month}/{day}/", issued.year));
+        }
+        if let Some(venue) = &self.venue {
+            lines.push(format!("JO  - {venue}"));
+        }
+        if let Some(doi) = &self.doi {
+            lines.push(format!("DO  - {doi}"));
+        }
+        if let Some(arxiv_id) = &self.arxiv_id {
+            lines.push(format!("N1  - arXiv:{arxiv_id}"));
+        }
+        lines.push(format!("UR  - {}", self.url));
+        if let Some(abstract_text) = &self.abstract_text {
+            lines.push(format!("AB  - {abstract_text}"));
+        }
+        lines.push("ER  - ".to_string());
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Render as a CSL-JSON item
+    pub fn to_csl_json(&self) -> Value {
+        let mut item = json!({
+            "id": self.key,
+            "type": if self.venue.is_some() { "article-journal" } else { "article" },
+            "title": self.title,
+            "author": self
+                .authors
+                .iter()
+                .map(|name| {
+                    let (family, given) = split_name(name);
+                    if given.is_empty() {
+                        json!({ "literal": family })
+                    } else {
+                        json!({ "family": family, "given": given })
+                    }
+                })
+                .collect::<Vec<_>>(),
+            "URL": self.url,
+        });
+
+        let fields = item.as_object_mut().expect("CSL item is an object");
+        if let Some(issued) = self.issued {
+            fields.insert(
+                "issued".to_string(),
+                json!({ "date-parts": [issued.parts()] }),
+            );
+        }
+        if let Some(venue) = &self.venue {
+            fields.insert("container-title".to_string(), json!(venue));
+        }
+        if let Some(doi) = &self.doi {
+            fields.insert("DOI".to_string(), json!(doi));
+        }
+        if let Some(arxiv_id) = &self.arxiv_id {
+            fields.insert("number".to_string(), json!(format!("arXiv:{arxiv_id}")));
+            if self.venue.is_none() {
+                fields.insert("publisher".to_string(), json!("arXiv"));
+            }
+        }
+        if let Some(abstract_text) = &self.abstract_text {
+            fields.insert("abstract".to_string(), json!(abstract_text));
+        }
+
+        item
+    }
+}
+
+/// Build citations for every citable result, making keys unique
+///
+/// Colliding keys get `a`, `b`, ... `z`, `aa`, `ab`, ... suffixes in result
+/// order, so the same result list always produces the same keys.
+pub fn citations(results: &[SearchResult]) -> Vec<Citation> {
+    let mut citations: Vec<Citation> = results
+        .iter()
+        .filter_map(Citation::from_search_result)
+        .collect();
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for citation in &citations {
+        *counts.entry(citation.key.clone()).or_default() += 1;
+    }
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for citation in &mut citations {
+        if counts[&citation.key] > 1 {
+            let index = seen.entry(citation.key.clone()).or_default();
+            citation.key.push_str(&key_suffix(*index));
+            *index += 1;
+        }
+    }
+
+    citations
+}
+
+/// Letters for the `index`th colliding key: `a`..`z`, then `aa`, `ab`, ...
+fn key_suffix(mut index: usize) -> String {
+    let mut suffix = Vec::new();
+    loop {
+        suffix.push(b'a' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    suffix.reverse();
+    String::from_utf8(suffix).unwrap_or_default()
+}
+
+/// Render results as a BibTeX bibliography
+pub fn to_bibtex(results: &[SearchResult]) -> String {
+    citations(results)
+        .iter()
+        .map(Citation::to_bibtex)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render results as RIS records
+pub fn to_ris(results: &[SearchResult]) -> String {
+    citations(results)
+        .iter()
+        .map(Citation::to_ris)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render results as a CSL-JSON array
+pub fn to_csl_json(results: &[SearchResult]) -> Value {
+    Value::Array(
+        citations(results)
+            .iter()
+            .map(Citation::to_csl_json)
+            .collect(),
+    )
+}
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+const STOP_WORDS: [&str; 12] = [
+    "the", "and", "for", "from", "with", "into", "onto", "via", "its", "are", "how", "why",
+];
+
+/// Split a name into (family, given), accepting `Family, Given` and `Given Family`
+fn split_name(name: &str) -> (String, String) {
+    let name = name.trim();
+    if let Some((family, given)) = name.split_once(',') {
+        return (family.trim().to_string(), given.trim().to_string());
+    }
+    match name.rsplit_once(char::is_whitespace) {
+        Some((given, family)) => (family.to_string(), given.trim().to_string()),
+        None => (name.to_string(), String::new()),
+    }
+}
+
+fn bibtex_name(name: &str) -> String {
+    let (family, given) = split_name(name);
+    if given.is_empty() {
+        family
+    } else {
+        format!("{family}, {given}")
+    }
+}
+
+/// Lowercase ASCII letters and digits of a word, with common accents folded
+fn ascii_word(word: &str) -> String {
+    word.chars()
+        .filter_map(|c| {
+            let c = match c {
+                'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => {
+                    'a'
+                }
+                'ç' | 'Ç' => 'c',
+                'è' | 'é' | 'ê' | 'ë' | 'È' | 'É' | 'Ê' | 'Ë' => 'e',
+                'ì' | 'í' | 'î' | 'ï' | 'Ì' | 'Í' | 'Î' | 'Ï' => 'i',
+                'ñ' | 'Ñ' => 'n',
+                'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => {
+                    'o'
+                }
+                'ù' | 'ú' | 'û' | 'ü' | 'Ù' | 'Ú' | 'Û' | 'Ü' => 'u',
+                'ý' | 'ÿ' | 'Ý' => 'y',
+                c => c,
+            };
+            c.is_ascii_alphanumeric().then(|| c.to_ascii_lowercase())
+        })
+        .collect()
+}
+
+fn escape_bibtex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn strip_doi_prefix(doi: &str) -> &str {
+    for prefix in [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ] {
+        if let Some(stripped) = doi.strip_prefix(prefix) {
+            return stripped;
+        }
+    }
+    doi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::arxiv::{ArxivAuthor, ArxivId};
+    use chrono::{TimeZone, Utc};
+    use std::collections::HashSet;
+
+    fn paper() -> ArxivPaper {
+        ArxivPaper {
+            id: ArxivId::parse("2301.00001v2").unwrap(),
+            title: "The Électron Transport in Graphene".to_string(),
+            summary: "We study 100% of electrons.".to_string(),
+            authors: vec![
+                ArxivAuthor {
+                    name: "Ada Lovelace".to_string(),
+                    affiliations: vec![],
+                },
+                ArxivAuthor {
+                    name: "Alan Turing".to_string(),
+                    affiliations: vec![],
+                },
+            ],
+            published: Some(Utc.with_ymd_and_hms(2023, 1, 5, 9, 30, 0).unwrap()),
+            updated: None,
+            primary_category: Some("cond-mat.mes-hall".to_string()),
+            categories: vec!["cond-mat.mes-hall".to_string()],
+            doi: Some("10.1000/xyz_123".to_string()),
+            journal_ref: None,
+            comment: None,
+            abs_url: "http://arxiv.org/abs/2301.00001v2".to_string(),
+            pdf_url: "http://arxiv.org/pdf/2301.00001v2".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_arxiv_bibtex() {
+        let citation = Citation::from_arxiv(&paper());
+        assert_eq!(citation.key, "lovelace2023electron");
+
+        let bibtex = citation.to_bibtex();
+        assert!(bibtex.starts_with("@misc{lovelace2023electron,\n"));
+        assert!(bibtex.contains("  author = {Lovelace, Ada and Turing, Alan},\n"));
+        assert!(bibtex.contains("  month = {jan},\n"));
+        assert!(bibtex.contains("  doi = {10.1000/xyz\\_123},\n"));
+        assert!(bibtex.contains("  eprint = {2301.00001},\n"));
+        assert!(bibtex.contains("  primaryClass = {cond-mat.mes-hall},\n"));
+        assert!(bibtex.contains("  url = {https://arxiv.org/abs/2301.00001},\n"));
+        assert!(bibtex.contains("We study 100\\% of electrons."));
+        assert!(bibtex.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_arxiv_ris_and_csl() {
+        let citation = Citation::from_arxiv(&paper());
+
+        let ris = citation.to_ris();
+        assert!(ris.starts_with("TY  - UNPB\n"));
+        assert!(ris.contains("AU  - Lovelace, Ada\n"));
+        assert!(ris.contains("DA  - 2023/01/05/\n"));
+        assert!(ris.contains("N1  - arXiv:2301.00001\n"));
+        assert!(ris.ends_with("ER  - \n"));
+
+        let csl = citation.to_csl_json();
+        assert_eq!(csl["id"], "lovelace2023electron");
+        assert_eq!(csl["type"], "article");
+        assert_eq!(csl["author"][1]["family"], "Turing");
+        assert_eq!(csl["issued"]["date-parts"], json!([[2023, 1, 5]]));
+        assert_eq!(csl["DOI"], "10.1000/xyz_123");
+        assert_eq!(csl["publisher"], "arXiv");
+    }
+
+    #[test]
+    fn test_generic_result_and_unique_keys() {
+        let generic = SearchResult {
+            url: "https://example.org/paper".to_string(),
+            title: "On Graphs".to_string(),
+            snippet: None,
+            domain: None,
+            published_date: Some("2021".to_string()),
+            provider: Some("other".to_string()),
+            raw: Some(json!({
+                "authors": ["Leonhard Euler"],
+                "doi": "https://doi.org/10.5555/graphs",
+                "venue": "Journal of Graphs"
+            })),
+        };
+        let undated = SearchResult {
+            title: "Untitled".to_string(),
+            published_date: None,
+            raw: None,
+            ..generic.clone()
+        };
+
+        let citations = citations(&[generic.clone(), undated, generic]);
+        assert_eq!(citations.len(), 2);
+        assert_eq!(citations[0].key, "euler2021graphsa");
+        assert_eq!(citations[1].key, "euler2021graphsb");
+        assert_eq!(citations[0].doi.as_deref(), Some("10.5555/graphs"));
+        assert!(citations[0].to_bibtex().starts_with("@article{"));
+        assert!(citations[0].to_ris().contains("JO  - Journal of Graphs\n"));
+        assert_eq!(
+            citations[0].to_csl_json()["issued"]["date-parts"],
+            json!([[2021]])
+        );
+    }
+
+    #[test]
+    fn test_key_suffixes_stay_unique() {
+        assert_eq!(key_suffix(0), "a");
+        assert_eq!(key_suffix(25), "z");
+        assert_eq!(key_suffix(26), "aa");
+        assert_eq!(key_suffix(27), "ab");
+        assert_eq!(key_suffix(26 + 26 * 26), "aaa");
+
+        let result = SearchResult {
+            url: "https://example.org/paper".to_string(),
+            title: "On Graphs".to_string(),
+            snippet: None,
+            domain: None,
+            published_date: Some("2021".to_string()),
+            provider: Some("other".to_string()),
+            raw: Some(json!({"authors": ["Leonhard Euler"]})),
+        };
+        let keys: HashSet<String> = citations(&vec![result; 30])
+            .into_iter()
+            .map(|citation| citation.key)
+            .collect();
+        assert_eq!(keys.len(), 30);
+    }
+
+    #[test]
+    fn test_out_of_range_month_is_skipped() {
+        let mut citation = citations(&[SearchResult {
+            url: "https://example.org/paper".to_string(),
+            title: "On Graphs".to_string(),
+            snippet: None,
+            domain: None,
+            published_date: Some("2021".to_string()),
+            provider: None,
+            raw: Some(json!({"authors": ["Leonhard Euler"]})),
+        }])
+        .remove(0);
+
+        for month in [0, 13] {
+            citation.issued = Some(PartialDate {
+                year: 2021,
+                month: Some(month),
+                day: None,
+            });
+            assert!(!citation.to_bibtex().contains("month"));
+        }
+    }
+
+    #[test]
+    fn test_arxiv_search_result_round_trip() {
+        let result = paper().to_search_result();
+        let citation = Citation::from_search_result(&result).unwrap();
+
+        assert_eq!(citation, Citation::from_arxiv(&paper()));
+    }
+}
@@ -40,6 +40,10 @@ pub enum SearchError {
     #[error("Rate limit exceeded: {0}")]
     RateLimit(String),
 
+    /// The provider served a bot-detection/CAPTCHA page instead of results
+    #[error("Blocked by provider: {0}")]
+    Blocked(String),
+
     /// Authentication failed
     #[error("Authentication failed: {0}")]
     AuthenticationError(String),
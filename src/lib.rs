@@ -4,7 +4,7 @@
 //!
 //! ## Quick Start
 //!
-//! ```rust
+//! ```rust,no_run
 //! use websearch::{web_search, providers::DuckDuckGoProvider, SearchOptions};
 //!
 //! #[tokio::main]
@@ -26,14 +26,18 @@
 //! }
 //! ```
 
+pub mod citation;
 pub mod error;
 pub mod providers;
+pub mod resolver;
 pub mod types;
 pub mod utils;
 
 // Re-export common types
 pub use error::{SearchError, SearchResult as Result};
-pub use types::{DebugOptions, SearchOptions, SearchProvider, SearchResult};
+pub use types::{
+    DebugOptions, SearchOptions, SearchProvider, SearchResponse, SearchResult, SuggestProvider,
+};
 
 /// Main search function that queries a web search provider and returns standardized results
 ///
@@ -47,7 +51,7 @@ pub use types::{DebugOptions, SearchOptions, SearchProvider, SearchResult};
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,no_run
 /// use websearch::{web_search, providers::DuckDuckGoProvider, SearchOptions};
 ///
 /// # #[tokio::main]
@@ -62,11 +66,19 @@ pub use types::{DebugOptions, SearchOptions, SearchProvider, SearchResult};
 /// # }
 /// ```
 pub async fn web_search(options: SearchOptions) -> Result<Vec<SearchResult>> {
+    web_search_response(options)
+        .await
+        .map(|response| response.results)
+}
+
+/// Like [`web_search`], but returns the full [`SearchResponse`] including
+/// spelling corrections and related searches when the provider supplies them
+pub async fn web_search_response(options: SearchOptions) -> Result<SearchResponse> {
     use error::SearchError;
     use utils::debug;
 
     // Validate required options
-    if options.query.is_empty() && options.id_list.is_none() {
+    if options.query.is_empty() && options.id_list.is_none() && options.native_query.is_none() {
         return Err(SearchError::InvalidInput(
             "A search query or ID list (for Arxiv) is required".to_string(),
         ));
@@ -84,13 +96,24 @@ pub async fn web_search(options: SearchOptions) -> Result<Vec<SearchResult>> {
     );
 
     // Perform the search
-    match options.provider.search(&options).await {
-        Ok(results) => {
+    match options.provider.search_response(&options).await {
+        Ok(response) => {
             debug::log_response(
                 &options.debug,
-                &format!("Received {} results", results.len()),
+                &format!("Received {} results", response.results.len()),
             );
-            Ok(results)
+            Ok(response)
+        }
+        Err(error @ (SearchError::Blocked(_) | SearchError::RateLimit(_))) => {
+            // Surface blocking and rate limits as-is so callers can tell them
+            // apart from "no results" and back off
+            let troubleshooting = get_troubleshooting_info(options.provider.name(), &error);
+            debug::log(
+                &options.debug,
+                "Search blocked or rate limited",
+                &format!("{error}\n\nTroubleshooting: {troubleshooting}"),
+            );
+            Err(error)
         }
         Err(error) => {
             let troubleshooting = get_troubleshooting_info(options.provider.name(), &error);
@@ -135,11 +158,25 @@ fn get_troubleshooting_info(provider_name: &str, error: &SearchError) -> String
         } => {
             "The search provider is experiencing server issues. Try again later.".to_string()
         }
+        SearchError::Blocked(_) => {
+            "The provider flagged this client as automated traffic. Wait before retrying, lower your request rate, or switch to another provider.".to_string()
+        }
         _ => {
             // Provider-specific troubleshooting
             match provider_name {
                 "duckduckgo" => "You may be making too many requests to DuckDuckGo. Try adding a delay between requests or reduce your request frequency.".to_string(),
                 "arxiv" => "ArXiv may be temporarily unavailable. Try again later or reduce your request frequency.".to_string(),
+                "semanticscholar" => "Semantic Scholar's shared public rate limit is low. Set an API key or slow down.".to_string(),
+                "pubmed" => "NCBI allows 3 requests per second without an API key. Set an API key or slow down, and check the query's field tags.".to_string(),
+                "crossref" | "openalex" => format!("Set a contact address (mailto) to use the {provider_name} polite pool, and check any filter syntax."),
+                "github" => "GitHub allows 10 unauthenticated searches per minute and code search only with a token. Set a token, and check the query's qualifiers.".to_string(),
+                "stackexchange" => "Stack Exchange allows 300 requests a day without an application key. Set a key, wait out any backoff, and check the site name.".to_string(),
+                "hackernews" => "HN Search allows 10,000 requests per hour. Check the tag and numeric filter syntax.".to_string(),
+                "crates" | "npm" => format!("Check the query, and slow down if {provider_name} is rate limiting requests."),
+                "pypi" => "PyPI has no search API; give exact project names, separated by spaces or commas.".to_string(),
+                "local" => "Check that the index directory exists and is readable. Delete .websearch-index.json to force a rebuild.".to_string(),
+                "elasticsearch" => "Check the cluster URL, index name and credentials, and that the query template and field mapping match the index.".to_string(),
+                "arxiv-new" => "Check the category name (e.g. cs.CL). Announcement feeds are empty on days without a mailing.".to_string(),
                 _ => format!("Check your {provider_name} configuration and make sure your search request is valid."),
             }
         }
@@ -333,6 +370,34 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_web_search_blocked_error_is_preserved() {
+        let provider =
+            MockProvider::new("test").with_error(SearchError::Blocked("bot check".to_string()));
+        let options = SearchOptions {
+            query: "test query".to_string(),
+            provider: Box::new(provider),
+            ..Default::default()
+        };
+
+        let result = web_search(options).await;
+        assert!(matches!(result, Err(SearchError::Blocked(_))));
+    }
+
+    #[tokio::test]
+    async fn test_web_search_rate_limit_error_is_preserved() {
+        let provider = MockProvider::new("test")
+            .with_error(SearchError::RateLimit("slow down".to_string()));
+        let options = SearchOptions {
+            query: "test query".to_string(),
+            provider: Box::new(provider),
+            ..Default::default()
+        };
+
+        let result = web_search(options).await;
+        assert!(matches!(result, Err(SearchError::RateLimit(message)) if message == "slow down"));
+    }
+
     #[tokio::test]
     async fn test_web_search_with_arxiv_id_list() {
         let provider = MockProvider::new("arxiv");
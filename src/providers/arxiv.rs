@@ -2,82 +2,210 @@
 
 use crate::{
     error::{SearchError, SearchResult},
-    types::{SearchOptions, SearchProvider, SearchResult as SearchResultType},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType,
+    },
+    utils::http::HttpClient,
 };
-use serde::Deserialize;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 use url::Url;
 
-#[derive(Debug, Deserialize)]
-struct ArxivEntry {
-    id: String,
-    title: String,
-    summary: String,
-    published: String,
-    #[serde(default)]
-    authors: Vec<ArxivAuthor>,
-    #[serde(default)]
-    links: Vec<ArxivLink>,
+pub mod feed;
+pub mod fulltext;
+pub mod id;
+pub mod oai;
+pub mod paper;
+pub mod query;
+
+pub use feed::{AnnouncementType, ArxivAnnouncement, ArxivFeedConfig, ArxivFeedProvider};
+pub use fulltext::{FullTextConfig, FullTextFetcher, PaperText};
+pub use id::ArxivId;
+pub use oai::{HarvestOptions, MetadataFormat, OaiHarvester};
+pub use paper::{ArxivAuthor, ArxivPage, ArxivPaper};
+pub use query::{ArxivDateRange, ArxivField, ArxivQuery};
+
+/// Largest page the ArXiv API will return in a single request
+pub const MAX_PAGE_SIZE: u32 = 2000;
+
+/// Minimum spacing between requests asked for by the ArXiv API terms of use
+pub const POLITENESS_DELAY: Duration = Duration::from_secs(3);
+
+/// ArXiv API configuration
+#[derive(Debug, Clone)]
+pub struct ArxivConfig {
+    /// Query endpoint; point at a mirror or a local mock server as needed
+    pub base_url: String,
+    /// User agent sent with every request
+    pub user_agent: String,
+    /// Contact address added to the user agent, as arXiv asks of heavy users
+    pub contact_email: Option<String>,
+    /// Request timeout in milliseconds
+    pub timeout_ms: u64,
+    /// Largest page requested at once, at most [`MAX_PAGE_SIZE`]
+    pub max_page_size: u32,
+    /// Minimum spacing between requests
+    pub request_delay: Duration,
 }
 
-#[derive(Debug, Deserialize)]
-struct ArxivAuthor {
-    name: String,
+impl Default for ArxivConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://export.arxiv.org/api/query".to_string(),
+            user_agent: concat!("websearch/", env!("CARGO_PKG_VERSION")).to_string(),
+            contact_email: None,
+            timeout_ms: 15000,
+            max_page_size: MAX_PAGE_SIZE,
+            request_delay: POLITENESS_DELAY,
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct ArxivLink {
-    #[serde(rename = "href")]
-    href: String,
-    #[serde(rename = "type")]
-    link_type: Option<String>,
+impl ArxivConfig {
+    fn user_agent_header(&self) -> String {
+        match &self.contact_email {
+            Some(email) => format!("{} (mailto:{email})", self.user_agent),
+            None => self.user_agent.clone(),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct ArxivFeed {
-    #[serde(rename = "entry", default)]
-    entries: Vec<ArxivEntry>,
+impl ProviderConfig for ArxivConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        if !(1..=MAX_PAGE_SIZE).contains(&self.max_page_size) {
+            return Err(SearchError::ConfigError(format!(
+                "max_page_size must be between 1 and {MAX_PAGE_SIZE}"
+            )));
+        }
+        if self.timeout_ms == 0 {
+            return Err(SearchError::ConfigError(
+                "Timeout must be greater than zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
 }
 
 #[derive(Debug)]
 pub struct ArxivProvider {
-    base_url: String,
+    config: ArxivConfig,
+    http_client: HttpClient,
+    last_request: Mutex<Option<Instant>>,
 }
 
 impl ArxivProvider {
     pub fn new() -> Self {
+        Self::with_config(ArxivConfig::default())
+    }
+
+    /// Create an ArXiv provider with custom configuration
+    pub fn with_config(config: ArxivConfig) -> Self {
         Self {
-            base_url: "http://export.arxiv.org/api/query".to_string(),
+            http_client: HttpClient::with_timeout(config.timeout_ms),
+            config,
+            last_request: Mutex::new(None),
         }
     }
-}
 
-impl Default for ArxivProvider {
-    fn default() -> Self {
-        Self::new()
+    /// Search and return fully typed paper metadata
+    pub async fn search_papers(&self, options: &SearchOptions) -> SearchResult<Vec<ArxivPaper>> {
+        Ok(self.search_page(options).await?.papers)
     }
-}
 
-#[async_trait::async_trait]
-impl SearchProvider for ArxivProvider {
-    fn name(&self) -> &str {
-        "arxiv"
+    /// Search and return one page of papers with the feed's paging information
+    pub async fn search_page(&self, options: &SearchOptions) -> SearchResult<ArxivPage> {
+        let max_results = options
+            .max_results
+            .unwrap_or(10)
+            .min(self.config.max_page_size);
+        self.fetch_page(options, options.start, max_results).await
     }
 
-    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
-        let client = reqwest::Client::new();
-        let mut url = Url::parse(&self.base_url)?;
+    /// Retrieve every matching paper, up to `limit`, one page at a time
+    ///
+    /// Pages are `options.max_results` papers long (capped at the configured
+    /// maximum page size) starting from `options.start`, and consecutive
+    /// requests are spaced by the politeness delay.
+    pub async fn search_all_papers(
+        &self,
+        options: &SearchOptions,
+        limit: Option<usize>,
+    ) -> SearchResult<Vec<ArxivPaper>> {
+        let page_size = options
+            .max_results
+            .unwrap_or(100)
+            .clamp(1, self.config.max_page_size);
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut start = options.start.unwrap_or(0);
+        let mut papers = Vec::new();
+
+        while papers.len() < limit {
+            let remaining = u32::try_from(limit - papers.len()).unwrap_or(u32::MAX);
+            let page = self
+                .fetch_page(options, Some(start), page_size.min(remaining))
+                .await?;
+            let received = page.papers.len() as u32;
+            papers.extend(page.papers);
+
+            start += received;
+            let exhausted = page.total_results.is_some_and(|total| u64::from(start) >= total);
+            if received == 0 || exhausted {
+                break;
+            }
+        }
+
+        papers.truncate(limit);
+        Ok(papers)
+    }
+
+    /// Wait until the politeness delay since the previous request has passed
+    async fn wait_for_turn(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(previous) = *last_request {
+            let elapsed = previous.elapsed();
+            if elapsed < self.config.request_delay {
+                tokio::time::sleep(self.config.request_delay - elapsed).await;
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    async fn fetch_page(
+        &self,
+        options: &SearchOptions,
+        start: Option<u32>,
+        max_results: u32,
+    ) -> SearchResult<ArxivPage> {
+        self.config.validate()?;
+        let mut url = Url::parse(&self.config.base_url)?;
 
         // Build query parameters with proper lifetime management
+        let id_list;
         let search_query;
         let start_str;
-        let max_results_str;
 
         let mut query_params = Vec::new();
 
-        if let Some(id_list) = &options.id_list {
-            // Search by specific ArXiv IDs
+        if let Some(ids) = &options.id_list {
+            // Search by specific ArXiv IDs, validated before sending
+            id_list = id::parse_id_list(ids)?
+                .iter()
+                .map(ArxivId::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
             query_params.push(("id_list", id_list.as_str()));
+        } else if let Some(native_query) = &options.native_query {
+            // Search with a query already in arXiv syntax
+            query_params.push(("search_query", native_query.trim()));
         } else if !options.query.trim().is_empty() {
             // Search by query string
             search_query = format!("all:{}", options.query.trim());
@@ -89,13 +217,12 @@ impl SearchProvider for ArxivProvider {
         }
 
         // Add pagination parameters
-        if let Some(start) = options.start {
+        if let Some(start) = start {
             start_str = start.to_string();
             query_params.push(("start", start_str.as_str()));
         }
 
-        let max_results = options.max_results.unwrap_or(10).min(50); // ArXiv max is 50
-        max_results_str = max_results.to_string();
+        let max_results_str = max_results.to_string();
         query_params.push(("max_results", max_results_str.as_str()));
 
         // Add sort parameters
@@ -124,32 +251,14 @@ impl SearchProvider for ArxivProvider {
             }
         }
 
-        let response = client
-            .get(url.as_str())
-            .send()
-            .await
-            .map_err(|e| {
-                SearchError::HttpError {
-                    message: format!("ArXiv API request failed: {e}"),
-                    status_code: None,
-                    response_body: None,
-                }
-            })?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-
-            return Err(SearchError::HttpError {
-                message: format!("ArXiv API returned error: {}", status),
-                status_code: Some(status.as_u16()),
-                response_body: Some(error_text),
-            });
-        }
+        self.wait_for_turn().await;
 
-        let xml_text = response.text().await.map_err(|e| {
-            SearchError::ParseError(format!("Failed to read ArXiv response: {e}"))
-        })?;
+        let mut headers = HashMap::new();
+        headers.insert("User-Agent".to_string(), self.config.user_agent_header());
+        let xml_text = self
+            .http_client
+            .get_text_with_headers(url.as_str(), headers)
+            .await?;
 
         if let Some(debug) = &options.debug {
             if debug.enabled && debug.log_responses {
@@ -157,66 +266,44 @@ impl SearchProvider for ArxivProvider {
             }
         }
 
-        // Parse XML response
-        let feed: ArxivFeed = quick_xml::de::from_str(&xml_text).map_err(|e| {
-            SearchError::ParseError(format!("Failed to parse ArXiv XML: {e}"))
-        })?;
-
-        // Convert to standard format
-        let results: Vec<SearchResultType> = feed
-            .entries
-            .into_iter()
-            .map(|entry| {
-                // Extract ArXiv ID from the full ID URL
-                let arxiv_id = entry.id
-                    .split('/')
-                    .last()
-                    .unwrap_or(&entry.id)
-                    .to_string();
-
-                // Find the paper URL
-                let paper_url = entry.links
-                    .iter()
-                    .find(|link| link.link_type.as_deref() == Some("text/html"))
-                    .map(|link| link.href.clone())
-                    .unwrap_or_else(|| format!("https://arxiv.org/abs/{}", arxiv_id));
-
-                // Create author list
-                let authors: Vec<String> = entry.authors.iter().map(|a| a.name.clone()).collect();
-                let authors_string = if authors.is_empty() {
-                    None
-                } else {
-                    Some(authors.join(", "))
-                };
-
-                // Store raw data
-                let mut raw_data = HashMap::new();
-                raw_data.insert("arxiv_id".to_string(), serde_json::Value::String(arxiv_id.clone()));
-                raw_data.insert("published".to_string(), serde_json::Value::String(entry.published.clone()));
-                if let Some(authors_str) = &authors_string {
-                    raw_data.insert("authors".to_string(), serde_json::Value::String(authors_str.clone()));
-                }
-
-                SearchResultType {
-                    url: paper_url,
-                    title: entry.title.trim().to_string(),
-                    snippet: Some(entry.summary.trim().to_string()),
-                    domain: Some("arxiv.org".to_string()),
-                    published_date: Some(entry.published),
-                    provider: Some("arxiv".to_string()),
-                    raw: Some(serde_json::to_value(raw_data).unwrap_or_default()),
-                }
-            })
-            .collect();
-
-        Ok(results)
+        paper::parse_feed(&xml_text)
+    }
+}
+
+impl Default for ArxivProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for ArxivProvider {
+    fn name(&self) -> &str {
+        "arxiv"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        let papers = self.search_papers(options).await?;
+        Ok(papers.iter().map(ArxivPaper::to_search_result).collect())
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let page = self.search_page(options).await?;
+        Ok(SearchResponse {
+            results: page.papers.iter().map(ArxivPaper::to_search_result).collect(),
+            total_results: page.total_results,
+            ..Default::default()
+        })
     }
 
     fn config(&self) -> HashMap<String, String> {
         let mut config = HashMap::new();
         config.insert("provider".to_string(), "arxiv".to_string());
-        config.insert("base_url".to_string(), self.base_url.clone());
-        config.insert("max_results".to_string(), "50".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config.insert(
+            "max_results".to_string(),
+            self.config.max_page_size.to_string(),
+        );
         config
     }
 }
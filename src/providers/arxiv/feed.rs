@@ -0,0 +1,484 @@
+//! Daily announcement feeds (new submissions per category)
+//!
+//! arXiv publishes an RSS feed per category listing the papers announced in
+//! the latest mailing. See <https://info.arxiv.org/help/rss.html>.
+
+use super::id::ArxivId;
+use super::oai::split_raw_authors;
+use super::paper::{ArxivAuthor, ArxivPaper};
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{ProviderConfig, SearchOptions, SearchProvider, SearchResult as SearchResultType},
+    utils::http::{normalize_text, HttpClient},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Why a paper appears in a day's announcement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnnouncementType {
+    /// First announcement of a new submission in this category
+    New,
+    /// New submission whose primary category is elsewhere
+    CrossList,
+    /// New version of a paper in this category
+    Replace,
+    /// New version of a paper cross-listed to this category
+    ReplaceCross,
+    /// A type this version doesn't know, kept as the feed spelled it
+    Other(String),
+}
+
+impl AnnouncementType {
+    /// Parse a feed label (`cross`) or our own (`cross-list`)
+    fn parse(value: &str) -> Self {
+        match value.trim() {
+            "new" => Self::New,
+            "cross" | "cross-list" => Self::CrossList,
+            "replace" => Self::Replace,
+            "replace-cross" => Self::ReplaceCross,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// Label used in result metadata, e.g. `cross-list`
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::New => "new",
+            Self::CrossList => "cross-list",
+            Self::Replace => "replace",
+            Self::ReplaceCross => "replace-cross",
+            Self::Other(label) => label,
+        }
+    }
+}
+
+impl Serialize for AnnouncementType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for AnnouncementType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self::parse(&String::deserialize(deserializer)?))
+    }
+}
+
+impl fmt::Display for AnnouncementType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A paper from an announcement feed, with the reason it was announced
+///
+/// The feed carries no submission or version dates, so the paper's
+/// `published` and `updated` are `None`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArxivAnnouncement {
+    pub paper: ArxivPaper,
+    pub announce_type: AnnouncementType,
+    /// When the mailing was announced (the item's `pubDate`)
+    pub announced: Option<DateTime<Utc>>,
+}
+
+impl ArxivAnnouncement {
+    /// Convert to a `SearchResult`, marking the announcement type and date
+    /// in `raw`
+    pub fn to_search_result(&self) -> SearchResultType {
+        let mut result = self.paper.to_search_result();
+        result.provider = Some("arxiv-new".to_string());
+        if let Some(serde_json::Value::Object(raw)) = &mut result.raw {
+            raw.insert(
+                "announce_type".to_string(),
+                serde_json::Value::String(self.announce_type.to_string()),
+            );
+            if let Some(announced) = self.announced {
+                raw.insert(
+                    "announced".to_string(),
+                    serde_json::Value::String(announced.to_rfc3339()),
+                );
+            }
+        }
+        result
+    }
+}
+
+/// Announcement feed configuration
+#[derive(Debug, Clone)]
+pub struct ArxivFeedConfig {
+    /// Feed root; the category is appended as a path segment
+    pub base_url: String,
+    /// Announcement types to keep; empty keeps everything
+    pub announce_types: Vec<AnnouncementType>,
+}
+
+impl Default for ArxivFeedConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://rss.arxiv.org/rss".to_string(),
+            announce_types: Vec::new(),
+        }
+    }
+}
+
+impl ProviderConfig for ArxivFeedConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+/// Provider for the papers announced today in one or more categories
+///
+/// The search query names the categories, e.g. `cs.CL` or `cs.CL+cs.LG`.
+#[derive(Debug)]
+pub struct ArxivFeedProvider {
+    config: ArxivFeedConfig,
+    http_client: HttpClient,
+}
+
+impl ArxivFeedProvider {
+    pub fn new() -> Self {
+        Self::with_config(ArxivFeedConfig::default())
+    }
+
+    pub fn with_config(config: ArxivFeedConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// Fetch the latest announcements for `categories`
+    pub async fn announcements(&self, categories: &str) -> SearchResult<Vec<ArxivAnnouncement>> {
+        self.config.validate()?;
+        let categories = parse_categories(categories)?;
+
+        let url = format!(
+            "{}/{}",
+            self.config.base_url.trim_end_matches('/'),
+            categories.join("+")
+        );
+        let xml = self.http_client.get_text(&url).await?;
+
+        let announcements = parse_feed(&xml)?
+            .into_iter()
+            .filter(|announcement| {
+                self.config.announce_types.is_empty()
+                    || self
+                        .config
+                        .announce_types
+                        .contains(&announcement.announce_type)
+            })
+            .collect();
+        Ok(announcements)
+    }
+}
+
+impl Default for ArxivFeedProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for ArxivFeedProvider {
+    fn name(&self) -> &str {
+        "arxiv-new"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        let announcements = self.announcements(&options.query).await?;
+        let max_results = options.max_results.map_or(usize::MAX, |max| max as usize);
+
+        Ok(announcements
+            .iter()
+            .take(max_results)
+            .map(ArxivAnnouncement::to_search_result)
+            .collect())
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "arxiv-new".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config
+    }
+}
+
+/// Split and check a category list such as `cs.CL+cs.LG` or `cs.CL, math.AG`
+fn parse_categories(categories: &str) -> SearchResult<Vec<&str>> {
+    let categories: Vec<&str> = categories
+        .split(|c: char| c == '+' || c == ',' || c.is_whitespace())
+        .filter(|category| !category.is_empty())
+        .collect();
+
+    if categories.is_empty() {
+        return Err(SearchError::InvalidInput(
+            "ArXiv announcement feeds require a category, e.g. cs.CL".to_string(),
+        ));
+    }
+    if let Some(invalid) = categories.iter().find(|category| {
+        !category
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    }) {
+        return Err(SearchError::InvalidInput(format!(
+            "Invalid arXiv category: {invalid}"
+        )));
+    }
+    Ok(categories)
+}
+
+#[derive(Debug, Deserialize)]
+struct Rss {
+    channel: RssChannel,
+}
+
+#[derive(Debug, Deserialize)]
+struct RssChannel {
+    #[serde(rename = "item", default)]
+    items: Vec<RssItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RssItem {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: String,
+    guid: Option<String>,
+    link: Option<String>,
+    #[serde(rename = "category", default)]
+    categories: Vec<String>,
+    #[serde(rename = "pubDate")]
+    pub_date: Option<String>,
+    announce_type: Option<String>,
+    creator: Option<String>,
+}
+
+/// Parse an announcement RSS feed
+pub(crate) fn parse_feed(xml: &str) -> SearchResult<Vec<ArxivAnnouncement>> {
+    let rss: Rss = quick_xml::de::from_str(xml)
+        .map_err(|e| SearchError::ParseError(format!("Failed to parse arXiv RSS feed: {e}")))?;
+
+    // One malformed item shouldn't discard the rest of the day's announcements
+    let mut announcements = Vec::with_capacity(rss.channel.items.len());
+    for item in rss.channel.items {
+        match ArxivAnnouncement::try_from(item) {
+            Ok(announcement) => announcements.push(announcement),
+            Err(error) => log::warn!("Skipping arXiv feed item: {error}"),
+        }
+    }
+    Ok(announcements)
+}
+
+impl TryFrom<RssItem> for ArxivAnnouncement {
+    type Error = SearchError;
+
+    fn try_from(item: RssItem) -> SearchResult<Self> {
+        // The guid (`oai:arXiv.org:2403.01234v1`) carries the announced version
+        let raw_id = item
+            .guid
+            .as_deref()
+            .or(item.link.as_deref())
+            .ok_or_else(|| SearchError::ParseError("RSS item has no identifier".to_string()))?;
+        let id = ArxivId::parse(raw_id.trim().trim_start_matches("oai:arXiv.org:"))?;
+
+        // The description reads `arXiv:<id> Announce Type: <type> Abstract: <text>`
+        let description = normalize_text(&item.description);
+        let summary = description
+            .split_once("Abstract:")
+            .map_or(description.as_str(), |(_, summary)| summary)
+            .trim()
+            .to_string();
+        let announce_type = item
+            .announce_type
+            .as_deref()
+            .or_else(|| {
+                description
+                    .split_once("Announce Type:")
+                    .and_then(|(_, rest)| rest.split_whitespace().next())
+            })
+            .map(AnnouncementType::parse)
+            .unwrap_or_else(|| {
+                log::warn!("arXiv feed item {id} has no announce type");
+                AnnouncementType::Other(String::new())
+            });
+        if let AnnouncementType::Other(label) = &announce_type {
+            log::debug!("Unknown arXiv announce type '{label}' for {id}");
+        }
+
+        let categories: Vec<String> = item
+            .categories
+            .iter()
+            .map(|category| category.trim().to_string())
+            .filter(|category| !category.is_empty())
+            .collect();
+        let announced = item
+            .pub_date
+            .as_deref()
+            .and_then(|date| DateTime::parse_from_rfc2822(date.trim()).ok())
+            .map(|date| date.with_timezone(&Utc));
+
+        let paper = ArxivPaper {
+            abs_url: id.abs_url(),
+            pdf_url: id.pdf_url(),
+            id,
+            title: normalize_text(&item.title),
+            summary,
+            authors: split_raw_authors(item.creator.as_deref().unwrap_or_default())
+                .into_iter()
+                .map(|name| ArxivAuthor {
+                    name,
+                    affiliations: Vec::new(),
+                })
+                .collect(),
+            published: None,
+            updated: None,
+            primary_category: categories.first().cloned(),
+            categories,
+            doi: None,
+            journal_ref: None,
+            comment: None,
+        };
+
+        Ok(Self {
+            paper,
+            announce_type,
+            announced,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS_XML: &str = r#"<?xml version='1.0' encoding='UTF-8'?>
+<rss xmlns:arxiv="http://arxiv.org/schemas/atom" xmlns:dc="http://purl.org/dc/elements/1.1/" version="2.0">
+  <channel>
+    <title>cs.CL updates on arXiv.org</title>
+    <link>http://rss.arxiv.org/rss/cs.CL</link>
+    <description>cs.CL updates on the arXiv.org e-print archive.</description>
+    <item>
+      <title>Talking Engines</title>
+      <link>https://arxiv.org/abs/2403.01234</link>
+      <description>arXiv:2403.01234v1 Announce Type: new
+Abstract: We study engines
+that talk.</description>
+      <guid isPermaLink="false">oai:arXiv.org:2403.01234v1</guid>
+      <category>cs.CL</category>
+      <category>cs.AI</category>
+      <pubDate>Mon, 11 Mar 2024 00:00:00 -0400</pubDate>
+      <arxiv:announce_type>new</arxiv:announce_type>
+      <dc:rights>http://creativecommons.org/licenses/by/4.0/</dc:rights>
+      <dc:creator>Ada Lovelace, Charles Babbage</dc:creator>
+    </item>
+    <item>
+      <title>Revised Engines</title>
+      <link>https://arxiv.org/abs/2401.00002</link>
+      <description>arXiv:2401.00002v3 Announce Type: replace-cross
+Abstract: Revised.</description>
+      <guid isPermaLink="false">oai:arXiv.org:2401.00002v3</guid>
+      <category>cs.LG</category>
+      <category>cs.CL</category>
+      <pubDate>Mon, 11 Mar 2024 00:00:00 -0400</pubDate>
+      <arxiv:announce_type>replace-cross</arxiv:announce_type>
+      <dc:creator>Grace Hopper</dc:creator>
+    </item>
+  </channel>
+</rss>"#;
+
+    #[test]
+    fn test_parse_feed() {
+        let announcements = parse_feed(RSS_XML).unwrap();
+        assert_eq!(announcements.len(), 2);
+
+        let first = &announcements[0];
+        assert_eq!(first.announce_type, AnnouncementType::New);
+        assert_eq!(first.paper.id.to_string(), "2403.01234v1");
+        assert_eq!(first.paper.summary, "We study engines that talk.");
+        assert_eq!(first.paper.author_names(), "Ada Lovelace, Charles Babbage");
+        assert_eq!(first.paper.primary_category.as_deref(), Some("cs.CL"));
+        assert_eq!(
+            first.announced.unwrap().to_rfc3339(),
+            "2024-03-11T04:00:00+00:00"
+        );
+        // The announcement date is not the submission date
+        assert_eq!(first.paper.published, None);
+        let raw = first.to_search_result().raw.unwrap();
+        assert_eq!(raw["announced"], "2024-03-11T04:00:00+00:00");
+
+        let second = &announcements[1];
+        assert_eq!(second.announce_type, AnnouncementType::ReplaceCross);
+        let result = second.to_search_result();
+        assert_eq!(result.provider.as_deref(), Some("arxiv-new"));
+        assert_eq!(result.raw.unwrap()["announce_type"], "replace-cross");
+    }
+
+    #[test]
+    fn test_parse_empty_feed() {
+        let xml = r#"<rss version="2.0"><channel><title>cs.CL</title><skipDays><day>Saturday</day></skipDays></channel></rss>"#;
+        assert!(parse_feed(xml).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_malformed_item_is_skipped() {
+        let xml = RSS_XML
+            .replace("oai:arXiv.org:2403.01234v1", "oai:arXiv.org:bogus")
+            .replace("https://arxiv.org/abs/2403.01234", "bogus");
+        let announcements = parse_feed(&xml).unwrap();
+
+        assert_eq!(announcements.len(), 1);
+        assert_eq!(announcements[0].paper.id.to_string(), "2401.00002v3");
+    }
+
+    #[test]
+    fn test_unknown_announce_type_is_kept() {
+        let xml = RSS_XML.replace(
+            "<arxiv:announce_type>new</arxiv:announce_type>",
+            "<arxiv:announce_type>withdrawn</arxiv:announce_type>",
+        );
+        let announcements = parse_feed(&xml).unwrap();
+        assert_eq!(
+            announcements[0].announce_type,
+            AnnouncementType::Other("withdrawn".to_string())
+        );
+
+        let labels = serde_json::json!(["cross-list", "withdrawn"]);
+        let parsed: Vec<AnnouncementType> = serde_json::from_value(labels.clone()).unwrap();
+        assert_eq!(
+            parsed,
+            [
+                AnnouncementType::CrossList,
+                AnnouncementType::Other("withdrawn".to_string())
+            ]
+        );
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), labels);
+    }
+
+    #[test]
+    fn test_parse_categories() {
+        assert_eq!(
+            parse_categories("cs.CL+cs.LG").unwrap(),
+            vec!["cs.CL", "cs.LG"]
+        );
+        assert_eq!(
+            parse_categories("cs.CL, math.AG").unwrap(),
+            vec!["cs.CL", "math.AG"]
+        );
+        assert!(parse_categories("  ").is_err());
+        assert!(parse_categories("cs.CL/../x").is_err());
+    }
+}
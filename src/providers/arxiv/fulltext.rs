@@ -0,0 +1,447 @@
+//! Full-text retrieval: PDF and e-print downloads with text extraction
+//!
+//! Downloads go through the crate's [`HttpClient`] and are cached on disk under
+//! the versioned identifier, so each version is fetched at most once.
+//! Unversioned identifiers are first resolved to the latest version through the
+//! arXiv API.
+//!
+//! Text extraction is PDF-only: it runs poppler's `pdftotext`, an external
+//! program that must be installed separately. E-print sources are downloaded
+//! as-is and never converted to text.
+
+use super::id::ArxivId;
+use super::paper::ArxivPaper;
+use super::{ArxivConfig, ArxivProvider, POLITENESS_DELAY};
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{ProviderConfig, SearchOptions, SearchResult as SearchResultType},
+    utils::http::HttpClient,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Full-text retrieval configuration
+#[derive(Debug, Clone)]
+pub struct FullTextConfig {
+    /// Directory holding downloaded PDFs, e-prints and extracted text
+    pub cache_dir: PathBuf,
+    /// PDF root; the identifier is appended as a path segment
+    pub pdf_base_url: String,
+    /// E-print (source) root; the identifier is appended as a path segment
+    pub source_base_url: String,
+    /// `pdftotext` executable
+    pub pdftotext: PathBuf,
+    /// Download timeout in milliseconds
+    pub timeout_ms: u64,
+    /// Minimum spacing between downloads
+    pub request_delay: Duration,
+    /// API used to find the latest version of unversioned identifiers
+    pub arxiv: ArxivConfig,
+}
+
+impl Default for FullTextConfig {
+    fn default() -> Self {
+        Self {
+            cache_dir: default_cache_dir(),
+            pdf_base_url: "https://arxiv.org/pdf".to_string(),
+            source_base_url: "https://arxiv.org/e-print".to_string(),
+            pdftotext: PathBuf::from("pdftotext"),
+            timeout_ms: 60_000,
+            request_delay: POLITENESS_DELAY,
+            arxiv: ArxivConfig::default(),
+        }
+    }
+}
+
+/// The per-user cache directory, falling back to the system temp directory
+fn default_cache_dir() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("websearch")
+        .join("arxiv")
+}
+
+impl ProviderConfig for FullTextConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.pdf_base_url.is_empty() || self.source_base_url.is_empty() {
+            return Err(SearchError::ConfigError(
+                "PDF and e-print base URLs are required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.pdf_base_url
+    }
+}
+
+/// Plain text of a paper, one entry per PDF page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaperText {
+    /// The versioned identifier the text was extracted from
+    pub id: ArxivId,
+    pub pages: Vec<String>,
+}
+
+impl PaperText {
+    /// Whole document, pages separated by blank lines
+    pub fn text(&self) -> String {
+        self.pages.join("\n\n")
+    }
+}
+
+/// Downloads arXiv PDFs and e-prints and extracts text from the PDFs
+#[derive(Debug)]
+pub struct FullTextFetcher {
+    config: FullTextConfig,
+    http_client: HttpClient,
+    arxiv: ArxivProvider,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl FullTextFetcher {
+    pub fn new() -> Self {
+        Self::with_config(FullTextConfig::default())
+    }
+
+    pub fn with_config(config: FullTextConfig) -> Self {
+        Self {
+            http_client: HttpClient::with_timeout(config.timeout_ms),
+            arxiv: ArxivProvider::with_config(config.arxiv.clone()),
+            config,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Download a paper's PDF, returning the cached file
+    pub async fn download_pdf(&self, id: &ArxivId) -> SearchResult<PathBuf> {
+        let id = self.pin_version(id).await?;
+        self.download_pinned_pdf(&id).await
+    }
+
+    async fn download_pinned_pdf(&self, id: &ArxivId) -> SearchResult<PathBuf> {
+        let url = format!("{}/{id}", self.config.pdf_base_url.trim_end_matches('/'));
+        self.download(&url, &self.cache_path(id, "pdf"), Download::Pdf)
+            .await
+    }
+
+    /// Download a paper's e-print source, returning the cached file
+    ///
+    /// This is usually a gzipped tarball of the LaTeX sources, but single-file
+    /// submissions are served as a gzipped `.tex` or as a PDF.
+    pub async fn download_source(&self, id: &ArxivId) -> SearchResult<PathBuf> {
+        let id = self.pin_version(id).await?;
+        let url = format!("{}/{id}", self.config.source_base_url.trim_end_matches('/'));
+        self.download(&url, &self.cache_path(&id, "src"), Download::Source)
+            .await
+    }
+
+    /// Download a paper's PDF and extract its text page by page
+    ///
+    /// Returns a `ConfigError` before downloading anything when `pdftotext`
+    /// can't be run.
+    pub async fn extract_text(&self, id: &ArxivId) -> SearchResult<PaperText> {
+        let id = self.pin_version(id).await?;
+        let text_path = self.cache_path(&id, "txt");
+        let text = match tokio::fs::read_to_string(&text_path).await {
+            Ok(text) => text,
+            Err(_) => {
+                self.check_pdftotext().await?;
+                let pdf_path = self.download_pinned_pdf(&id).await?;
+                let text = self.run_pdftotext(&pdf_path).await?;
+                write_atomically(&text_path, text.as_bytes()).await?;
+                text
+            }
+        };
+
+        Ok(PaperText {
+            pages: split_pages(&text),
+            id,
+        })
+    }
+
+    /// Extract the text of an arXiv search result
+    pub async fn extract_result_text(&self, result: &SearchResultType) -> SearchResult<PaperText> {
+        let id = match ArxivPaper::from_search_result(result) {
+            Some(paper) => paper.id,
+            None => {
+                let raw_id = result
+                    .raw
+                    .as_ref()
+                    .and_then(|raw| raw.get("arxiv_id"))
+                    .and_then(|id| id.as_str())
+                    .unwrap_or(&result.url);
+                ArxivId::parse(raw_id)?
+            }
+        };
+        self.extract_text(&id).await
+    }
+
+    /// The identifier pinned to a version, asking the API for the latest one
+    /// when `id` has none
+    ///
+    /// Caching under an unversioned id would serve the first download forever,
+    /// even after the paper is revised.
+    async fn pin_version(&self, id: &ArxivId) -> SearchResult<ArxivId> {
+        if id.version().is_some() {
+            return Ok(id.clone());
+        }
+
+        let options = SearchOptions {
+            id_list: Some(id.to_string()),
+            max_results: Some(1),
+            ..Default::default()
+        };
+        self.arxiv
+            .search_papers(&options)
+            .await?
+            .into_iter()
+            .map(|paper| paper.id)
+            .find(|found| found.base() == id.base() && found.version().is_some())
+            .ok_or_else(|| SearchError::InvalidInput(format!("No arXiv paper found for {id}")))
+    }
+
+    /// Cache location for an identifier; legacy `archive/number` ids are flattened
+    fn cache_path(&self, id: &ArxivId, extension: &str) -> PathBuf {
+        let name = id.to_string().replace('/', "_");
+        self.config.cache_dir.join(format!("{name}.{extension}"))
+    }
+
+    async fn download(&self, url: &str, path: &Path, kind: Download) -> SearchResult<PathBuf> {
+        self.config.validate()?;
+        if tokio::fs::try_exists(path).await.unwrap_or(false) {
+            return Ok(path.to_path_buf());
+        }
+
+        self.wait_for_turn().await;
+        let response = self
+            .http_client
+            .get_response_with_headers(url, HashMap::new())
+            .await?;
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        if !status.is_success() {
+            return Err(SearchError::HttpError {
+                message: format!("Request failed with status: {status}"),
+                status_code: Some(status.as_u16()),
+                response_body: response.text().await.ok(),
+            });
+        }
+
+        let bytes = response.bytes().await?;
+        // Files in the cache are trusted forever, so never cache an error page
+        if !kind.accepts(&content_type, &bytes) {
+            return Err(SearchError::ProviderError(format!(
+                "{url} returned {} instead of {}; not caching it",
+                if content_type.is_empty() {
+                    "an unrecognized body"
+                } else {
+                    content_type.as_str()
+                },
+                kind.description()
+            )));
+        }
+        write_atomically(path, &bytes).await?;
+        Ok(path.to_path_buf())
+    }
+
+    /// Wait until the politeness delay since the previous download has passed
+    async fn wait_for_turn(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(previous) = *last_request {
+            let elapsed = previous.elapsed();
+            if elapsed < self.config.request_delay {
+                tokio::time::sleep(self.config.request_delay - elapsed).await;
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+
+    /// Make sure `pdftotext` can be run, so a missing install doesn't cost a download
+    async fn check_pdftotext(&self) -> SearchResult<()> {
+        tokio::process::Command::new(&self.config.pdftotext)
+            .arg("-v")
+            .output()
+            .await
+            .map(|_| ())
+            .map_err(|e| self.pdftotext_error(e))
+    }
+
+    async fn run_pdftotext(&self, pdf_path: &Path) -> SearchResult<String> {
+        let output = tokio::process::Command::new(&self.config.pdftotext)
+            .args(["-enc", "UTF-8"])
+            .arg(pdf_path)
+            .arg("-")
+            .output()
+            .await
+            .map_err(|e| self.pdftotext_error(e))?;
+
+        if !output.status.success() {
+            return Err(SearchError::ParseError(format!(
+                "pdftotext failed on {}: {}",
+                pdf_path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn pdftotext_error(&self, error: std::io::Error) -> SearchError {
+        let program = self.config.pdftotext.display();
+        SearchError::ConfigError(match error.kind() {
+            std::io::ErrorKind::NotFound => format!(
+                "Text extraction needs poppler's pdftotext, but '{program}' was not found. \
+                 Install poppler-utils or set FullTextConfig::pdftotext"
+            ),
+            _ => format!("Failed to run {program}: {error}"),
+        })
+    }
+}
+
+impl Default for FullTextFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What a download is expected to contain
+#[derive(Debug, Clone, Copy)]
+enum Download {
+    Pdf,
+    /// Gzipped tarball or `.tex`, or a PDF for PDF-only submissions
+    Source,
+}
+
+impl Download {
+    fn description(self) -> &'static str {
+        match self {
+            Download::Pdf => "a PDF",
+            Download::Source => "an e-print",
+        }
+    }
+
+    /// Whether a response body is the expected file rather than an HTML
+    /// error, captcha or "PDF being generated" page
+    fn accepts(self, content_type: &str, bytes: &[u8]) -> bool {
+        let is_pdf = bytes.starts_with(b"%PDF");
+        match self {
+            Download::Pdf => is_pdf,
+            Download::Source => {
+                let is_html = content_type.starts_with("text/html")
+                    || bytes
+                        .iter()
+                        .find(|byte| !byte.is_ascii_whitespace())
+                        .is_some_and(|&byte| byte == b'<');
+                let is_gzip = bytes.starts_with(&[0x1f, 0x8b]);
+                !bytes.is_empty() && (is_pdf || is_gzip || !is_html)
+            }
+        }
+    }
+}
+
+/// Write through a temporary file so an interrupted download never looks cached
+///
+/// Each write gets its own temporary name, so concurrent writes of the same or
+/// sibling files (`X.pdf`, `X.txt`) can't clobber each other's partial file.
+async fn write_atomically(path: &Path, contents: &[u8]) -> SearchResult<()> {
+    static NEXT_WRITE: AtomicU64 = AtomicU64::new(0);
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let partial = PathBuf::from(format!(
+        "{}.{}-{}.part",
+        path.display(),
+        std::process::id(),
+        NEXT_WRITE.fetch_add(1, Ordering::Relaxed)
+    ));
+    if let Err(error) = tokio::fs::write(&partial, contents).await {
+        let _ = tokio::fs::remove_file(&partial).await;
+        return Err(error.into());
+    }
+    tokio::fs::rename(&partial, path).await?;
+    Ok(())
+}
+
+/// Split `pdftotext` output into pages at its form-feed separators
+fn split_pages(text: &str) -> Vec<String> {
+    let mut pages: Vec<String> = text
+        .split('\u{c}')
+        .map(|page| page.trim_end().to_string())
+        .collect();
+    // pdftotext terminates every page, including the last, with a form feed
+    if pages.last().is_some_and(|page| page.is_empty()) {
+        pages.pop();
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_pages() {
+        let pages = split_pages("Title\nAbstract\n\u{c}Body\n\u{c}\u{c}");
+        assert_eq!(pages, vec!["Title\nAbstract", "Body", ""]);
+        assert!(split_pages("").is_empty());
+    }
+
+    #[test]
+    fn test_download_accepts_only_expected_files() {
+        let html = b"<!DOCTYPE html><title>PDF being generated</title>";
+
+        assert!(Download::Pdf.accepts("application/pdf", b"%PDF-1.7"));
+        assert!(!Download::Pdf.accepts("application/pdf", html));
+        assert!(!Download::Pdf.accepts("text/html", b"captcha"));
+
+        assert!(Download::Source.accepts("application/x-eprint-tar", &[0x1f, 0x8b, 8]));
+        assert!(Download::Source.accepts("application/pdf", b"%PDF-1.7"));
+        assert!(!Download::Source.accepts("text/html; charset=utf-8", b"Not found"));
+        assert!(!Download::Source.accepts("", html));
+        assert!(!Download::Source.accepts("application/octet-stream", b""));
+    }
+
+    #[tokio::test]
+    async fn test_write_atomically_uses_distinct_partial_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let pdf = dir.path().join("2403.00001.pdf");
+        let txt = dir.path().join("2403.00001.txt");
+
+        let (first, second) = tokio::join!(
+            write_atomically(&pdf, b"%PDF"),
+            write_atomically(&txt, b"text")
+        );
+        first.unwrap();
+        second.unwrap();
+
+        assert_eq!(std::fs::read(&pdf).unwrap(), b"%PDF");
+        assert_eq!(std::fs::read(&txt).unwrap(), b"text");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_cache_path_flattens_legacy_ids() {
+        let fetcher = FullTextFetcher::with_config(FullTextConfig {
+            cache_dir: PathBuf::from("/cache"),
+            ..Default::default()
+        });
+        let id = ArxivId::parse("hep-th/9901001v2").unwrap();
+        assert_eq!(
+            fetcher.cache_path(&id, "pdf"),
+            PathBuf::from("/cache/hep-th_9901001v2.pdf")
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! arXiv identifier parsing
+//!
+//! Handles both identifier schemes described at
+//! <https://info.arxiv.org/help/arxiv_identifier.html>:
+//! new-style `YYMM.NNNN(N)` identifiers and legacy `archive(.SC)/YYMMNNN`
+//! identifiers, each with an optional `vN` version suffix.
+
+use crate::error::{SearchError, SearchResult};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A validated arXiv identifier, optionally pinned to a version
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArxivId {
+    base: String,
+    version: Option<u32>,
+}
+
+impl ArxivId {
+    /// Parse an identifier
+    ///
+    /// Accepts bare identifiers (`2301.00001v2`, `hep-th/9901001`), the
+    /// `arXiv:` prefix, and abs/pdf URLs.
+    pub fn parse(input: &str) -> SearchResult<Self> {
+        let invalid = || SearchError::InvalidInput(format!("Invalid arXiv identifier: '{input}'"));
+
+        let id = strip_decorations(input.trim());
+        let (base, version) = split_version(id).ok_or_else(invalid)?;
+
+        if !is_new_style(base) && !is_legacy(base) {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            base: base.to_string(),
+            version,
+        })
+    }
+
+    /// Identifier without the version suffix, e.g. `hep-th/9901001`
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Version number, if the identifier is pinned to one
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    /// Whether this is a pre-2007 `archive/YYMMNNN` identifier
+    pub fn is_legacy(&self) -> bool {
+        self.base.contains('/')
+    }
+
+    /// The same paper without a pinned version (i.e. the latest version)
+    pub fn unversioned(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            version: None,
+        }
+    }
+
+    /// Abstract page URL
+    pub fn abs_url(&self) -> String {
+        format!("https://arxiv.org/abs/{self}")
+    }
+
+    /// PDF URL
+    pub fn pdf_url(&self) -> String {
+        format!("https://arxiv.org/pdf/{self}")
+    }
+}
+
+impl fmt::Display for ArxivId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.version {
+            Some(version) => write!(f, "{}v{version}", self.base),
+            None => write!(f, "{}", self.base),
+        }
+    }
+}
+
+impl FromStr for ArxivId {
+    type Err = SearchError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Serialize for ArxivId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ArxivId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parse a comma-separated `id_list`, reporting every invalid entry
+pub fn parse_id_list(id_list: &str) -> SearchResult<Vec<ArxivId>> {
+    let mut ids = Vec::new();
+    let mut invalid = Vec::new();
+
+    for entry in id_list
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        match ArxivId::parse(entry) {
+            Ok(id) => ids.push(id),
+            Err(_) => invalid.push(entry),
+        }
+    }
+
+    if !invalid.is_empty() {
+        return Err(SearchError::InvalidInput(format!(
+            "Invalid arXiv identifier(s): {}",
+            invalid.join(", ")
+        )));
+    }
+    if ids.is_empty() {
+        return Err(SearchError::InvalidInput(
+            "ArXiv ID list is empty".to_string(),
+        ));
+    }
+
+    Ok(ids)
+}
+
+/// Remove URL, `arXiv:` and `.pdf` decorations around an identifier
+fn strip_decorations(input: &str) -> &str {
+    let mut id = input;
+
+    for marker in ["/abs/", "/pdf/"] {
+        if let Some(position) = id.find(marker) {
+            id = &id[position + marker.len()..];
+            break;
+        }
+    }
+
+    // `get` rather than indexing, so non-ASCII input can't split a character
+    if id.len() > 6
+        && id
+            .get(..6)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("arxiv:"))
+    {
+        id = &id[6..];
+    }
+
+    id.strip_suffix(".pdf").unwrap_or(id)
+}
+
+/// Split a trailing `vN` suffix from an identifier
+fn split_version(id: &str) -> Option<(&str, Option<u32>)> {
+    match id.rfind('v') {
+        Some(position)
+            if id[..position]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_digit()) =>
+        {
+            let digits = &id[position + 1..];
+            if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let version = digits.parse().ok().filter(|version| *version > 0)?;
+            Some((&id[..position], Some(version)))
+        }
+        _ => Some((id, None)),
+    }
+}
+
+/// `YYMM.NNNN` (2007-2014) or `YYMM.NNNNN` (2015 onwards)
+fn is_new_style(id: &str) -> bool {
+    let Some((prefix, number)) = id.split_once('.') else {
+        return false;
+    };
+    if prefix.len() != 4 || !prefix.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+    if !number.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let year: u32 = prefix[..2].parse().unwrap_or(0);
+    let month: u32 = prefix[2..].parse().unwrap_or(0);
+    if !(1..=12).contains(&month) || year < 7 {
+        return false;
+    }
+
+    match number.len() {
+        4 => year < 15,
+        5 => year >= 15,
+        _ => false,
+    }
+}
+
+/// `archive/YYMMNNN` or `archive.SC/YYMMNNN`, e.g. `math.GT/0309136`
+fn is_legacy(id: &str) -> bool {
+    let Some((archive, number)) = id.split_once('/') else {
+        return false;
+    };
+
+    let (name, subject) = match archive.split_once('.') {
+        Some((name, subject)) => (name, Some(subject)),
+        None => (archive, None),
+    };
+    let valid_name = !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '-');
+    let valid_subject = subject.is_none_or(|subject| {
+        subject.len() == 2 && subject.chars().all(|c| c.is_ascii_uppercase())
+    });
+
+    let month: u32 = number.get(2..4).and_then(|m| m.parse().ok()).unwrap_or(0);
+    let valid_number = number.len() == 7
+        && number.chars().all(|c| c.is_ascii_digit())
+        && (1..=12).contains(&month);
+
+    valid_name && valid_subject && valid_number
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_new_style() {
+        let id = ArxivId::parse("2301.00001v2").unwrap();
+        assert_eq!(id.base(), "2301.00001");
+        assert_eq!(id.version(), Some(2));
+        assert!(!id.is_legacy());
+        assert_eq!(id.to_string(), "2301.00001v2");
+
+        let id = ArxivId::parse("arXiv:0704.0001").unwrap();
+        assert_eq!(id.base(), "0704.0001");
+        assert_eq!(id.version(), None);
+    }
+
+    #[test]
+    fn test_parse_legacy() {
+        let id = ArxivId::parse("http://arxiv.org/abs/hep-th/9901001v2").unwrap();
+        assert_eq!(id.base(), "hep-th/9901001");
+        assert_eq!(id.version(), Some(2));
+        assert!(id.is_legacy());
+        assert_eq!(id.abs_url(), "https://arxiv.org/abs/hep-th/9901001v2");
+        assert_eq!(
+            id.unversioned().pdf_url(),
+            "https://arxiv.org/pdf/hep-th/9901001"
+        );
+
+        let id = ArxivId::parse("math.GT/0309136").unwrap();
+        assert_eq!(id.base(), "math.GT/0309136");
+    }
+
+    #[test]
+    fn test_parse_urls() {
+        let id = ArxivId::parse("https://arxiv.org/pdf/2301.00001v1.pdf").unwrap();
+        assert_eq!(id.to_string(), "2301.00001v1");
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        for input in [
+            "",
+            "1234.5678",   // year before new-style scheme
+            "2301.0001",   // four digits after 2015
+            "1301.00001",  // five digits before 2015
+            "2313.00001",  // month 13
+            "2301.00001v", // empty version
+            "2301.00001v0",
+            "hep-th/990100",
+            "HEP-TH/9901001",
+            "not an id",
+        ] {
+            assert!(ArxivId::parse(input).is_err(), "{input} should be invalid");
+        }
+    }
+
+    #[test]
+    fn test_rejects_non_ascii() {
+        for input in [
+            "aaaaa€",
+            "év1",
+            "x€v2",
+            "arXiv€:2301.00001",
+            "2301.00001€v1",
+        ] {
+            assert!(
+                matches!(ArxivId::parse(input), Err(SearchError::InvalidInput(_))),
+                "{input} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_id_list() {
+        let ids = parse_id_list("2301.00001, hep-th/9901001v1,").unwrap();
+        assert_eq!(ids.len(), 2);
+
+        match parse_id_list("2301.00001,bogus,1234.5678") {
+            Err(SearchError::InvalidInput(message)) => {
+                assert!(message.contains("bogus"));
+                assert!(message.contains("1234.5678"));
+            }
+            other => panic!("Expected InvalidInput, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,700 @@
+//! Bulk metadata harvesting from arXiv's OAI-PMH interface
+//!
+//! The search API is meant for interactive queries; building a local corpus
+//! should go through `export.arxiv.org/oai2` instead. See
+//! <https://info.arxiv.org/help/oa/index.html>.
+
+use super::id::ArxivId;
+use super::paper::{non_empty, ArxivAuthor, ArxivPaper};
+use super::POLITENESS_DELAY;
+use crate::{
+    error::{SearchError, SearchResult},
+    types::ProviderConfig,
+    utils::http::{normalize_text, HttpClient},
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use url::Url;
+
+/// OAI-PMH metadata formats offered by arXiv
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MetadataFormat {
+    /// `arXiv`: structured authors with affiliations, created/updated dates
+    #[default]
+    ArXiv,
+    /// `arXivRaw`: author string as submitted, plus the full version history
+    ArXivRaw,
+}
+
+impl MetadataFormat {
+    /// `metadataPrefix` value for this format
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            MetadataFormat::ArXiv => "arXiv",
+            MetadataFormat::ArXivRaw => "arXivRaw",
+        }
+    }
+}
+
+/// Selective-harvesting parameters for `ListRecords`
+#[derive(Debug, Clone, Default)]
+pub struct HarvestOptions {
+    /// OAI set, e.g. `cs` or `physics:hep-th`
+    pub set: Option<String>,
+    /// Only records changed on or after this date
+    pub from: Option<NaiveDate>,
+    /// Only records changed on or before this date
+    pub until: Option<NaiveDate>,
+    /// Metadata format to request
+    pub format: MetadataFormat,
+}
+
+/// OAI-PMH harvester configuration
+#[derive(Debug, Clone)]
+pub struct OaiConfig {
+    /// OAI-PMH endpoint
+    pub base_url: String,
+    /// Minimum spacing between requests
+    pub request_delay: Duration,
+    /// How long to wait before retrying a 503 that has no `Retry-After` header
+    pub retry_delay: Duration,
+    /// Maximum number of retries per request after a 503
+    pub max_retries: u32,
+}
+
+impl Default for OaiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://export.arxiv.org/oai2".to_string(),
+            request_delay: POLITENESS_DELAY,
+            retry_delay: Duration::from_secs(10),
+            max_retries: 3,
+        }
+    }
+}
+
+impl ProviderConfig for OaiConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+/// One `ListRecords` response
+#[derive(Debug, Clone, Default)]
+pub struct HarvestPage {
+    /// Papers on this page; deleted records are skipped
+    pub papers: Vec<ArxivPaper>,
+    /// Token for the next page, `None` on the last page
+    pub resumption_token: Option<String>,
+    /// Size of the complete list, when the server reports it
+    pub complete_list_size: Option<u64>,
+}
+
+/// Harvester for arXiv's OAI-PMH `ListRecords` verb
+#[derive(Debug)]
+pub struct OaiHarvester {
+    config: OaiConfig,
+    http_client: HttpClient,
+}
+
+impl OaiHarvester {
+    /// Create a harvester for the public arXiv endpoint
+    pub fn new() -> Self {
+        Self::with_config(OaiConfig::default())
+    }
+
+    /// Create a harvester with custom configuration
+    pub fn with_config(config: OaiConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// Fetch the first page of records matching `options`
+    pub async fn list_records(&self, options: &HarvestOptions) -> SearchResult<HarvestPage> {
+        self.config.validate()?;
+        if let (Some(from), Some(until)) = (options.from, options.until) {
+            if from > until {
+                return Err(SearchError::InvalidInput(format!(
+                    "Harvest range starts after it ends ({from} > {until})"
+                )));
+            }
+        }
+
+        let mut url = Url::parse(&self.config.base_url)?;
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("verb", "ListRecords")
+                .append_pair("metadataPrefix", options.format.prefix());
+            if let Some(set) = options.set.as_deref().filter(|set| !set.is_empty()) {
+                query.append_pair("set", set);
+            }
+            if let Some(from) = options.from {
+                query.append_pair("from", &from.format("%Y-%m-%d").to_string());
+            }
+            if let Some(until) = options.until {
+                query.append_pair("until", &until.format("%Y-%m-%d").to_string());
+            }
+        }
+
+        self.fetch(url.as_str()).await
+    }
+
+    /// Fetch the page following a previous response's resumption token
+    pub async fn resume(&self, resumption_token: &str) -> SearchResult<HarvestPage> {
+        let mut url = Url::parse(&self.config.base_url)?;
+        url.query_pairs_mut()
+            .append_pair("verb", "ListRecords")
+            .append_pair("resumptionToken", resumption_token);
+
+        // Resumption requests share the politeness delay with the first page
+        tokio::time::sleep(self.config.request_delay).await;
+        self.fetch(url.as_str()).await
+    }
+
+    /// Harvest every record matching `options`, up to `limit` papers,
+    /// following resumption tokens
+    pub async fn harvest(
+        &self,
+        options: &HarvestOptions,
+        limit: Option<usize>,
+    ) -> SearchResult<Vec<ArxivPaper>> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut page = self.list_records(options).await?;
+        let mut papers = Vec::new();
+
+        loop {
+            papers.extend(page.papers);
+            if papers.len() >= limit {
+                break;
+            }
+            match page.resumption_token {
+                Some(token) => page = self.resume(&token).await?,
+                None => break,
+            }
+        }
+
+        papers.truncate(limit);
+        Ok(papers)
+    }
+
+    async fn fetch(&self, url: &str) -> SearchResult<HarvestPage> {
+        let mut attempt = 0;
+        loop {
+            let response = self
+                .http_client
+                .get_response_with_headers(url, HashMap::new())
+                .await?;
+            let status = response.status();
+
+            if status.as_u16() == 503 && attempt < self.config.max_retries {
+                attempt += 1;
+                let delay = retry_after(response.headers()).unwrap_or(self.config.retry_delay);
+                log::debug!("OAI-PMH server asked to retry in {delay:?}");
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+            if !status.is_success() {
+                return Err(SearchError::HttpError {
+                    message: format!("Request failed with status: {status}"),
+                    status_code: Some(status.as_u16()),
+                    response_body: response.text().await.ok(),
+                });
+            }
+            return parse_list_records(&response.text().await?);
+        }
+    }
+}
+
+/// Delay requested by a `Retry-After` header, in seconds or as an HTTP date
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+impl Default for OaiHarvester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OaiResponse {
+    error: Option<OaiError>,
+    #[serde(rename = "ListRecords")]
+    list_records: Option<ListRecords>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OaiError {
+    #[serde(rename = "@code")]
+    code: String,
+    #[serde(rename = "$text", default)]
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListRecords {
+    #[serde(rename = "record", default)]
+    records: Vec<OaiRecord>,
+    #[serde(rename = "resumptionToken")]
+    resumption_token: Option<ResumptionToken>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResumptionToken {
+    #[serde(rename = "@completeListSize")]
+    complete_list_size: Option<u64>,
+    #[serde(rename = "$text")]
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OaiRecord {
+    header: OaiHeader,
+    metadata: Option<OaiMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OaiHeader {
+    identifier: Option<String>,
+    #[serde(rename = "@status")]
+    status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OaiMetadata {
+    #[serde(rename = "arXiv")]
+    arxiv: Option<ArxivFormat>,
+    #[serde(rename = "arXivRaw")]
+    arxiv_raw: Option<ArxivRawFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArxivFormat {
+    id: String,
+    created: Option<String>,
+    updated: Option<String>,
+    authors: Option<ArxivFormatAuthors>,
+    #[serde(default)]
+    title: String,
+    categories: Option<String>,
+    comments: Option<String>,
+    #[serde(rename = "journal-ref")]
+    journal_ref: Option<String>,
+    doi: Option<String>,
+    #[serde(rename = "abstract", default)]
+    summary: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArxivFormatAuthors {
+    #[serde(rename = "author", default)]
+    authors: Vec<ArxivFormatAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArxivFormatAuthor {
+    #[serde(default)]
+    keyname: String,
+    forenames: Option<String>,
+    suffix: Option<String>,
+    #[serde(rename = "affiliation", default)]
+    affiliations: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArxivRawFormat {
+    id: String,
+    #[serde(rename = "version", default)]
+    versions: Vec<ArxivRawVersion>,
+    #[serde(default)]
+    title: String,
+    authors: Option<String>,
+    categories: Option<String>,
+    comments: Option<String>,
+    #[serde(rename = "journal-ref")]
+    journal_ref: Option<String>,
+    doi: Option<String>,
+    #[serde(rename = "abstract", default)]
+    summary: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArxivRawVersion {
+    #[serde(rename = "@version")]
+    version: String,
+    date: Option<String>,
+}
+
+/// Parse a `ListRecords` response
+pub(crate) fn parse_list_records(xml: &str) -> SearchResult<HarvestPage> {
+    let response: OaiResponse = quick_xml::de::from_str(xml)
+        .map_err(|e| SearchError::ParseError(format!("Failed to parse OAI-PMH XML: {e}")))?;
+
+    if let Some(error) = response.error {
+        return match error.code.as_str() {
+            // An empty selection is a normal outcome, not a failure
+            "noRecordsMatch" => Ok(HarvestPage::default()),
+            "badArgument" | "badResumptionToken" | "cannotDisseminateFormat" | "noSetHierarchy" => {
+                Err(SearchError::InvalidInput(format!(
+                    "OAI-PMH {}: {}",
+                    error.code,
+                    normalize_text(&error.message)
+                )))
+            }
+            _ => Err(SearchError::ProviderError(format!(
+                "OAI-PMH {}: {}",
+                error.code,
+                normalize_text(&error.message)
+            ))),
+        };
+    }
+
+    let Some(list_records) = response.list_records else {
+        return Ok(HarvestPage::default());
+    };
+
+    let mut papers = Vec::new();
+    for record in list_records.records {
+        if record.header.status.as_deref() == Some("deleted") {
+            continue;
+        }
+        let paper = match record.metadata {
+            Some(OaiMetadata {
+                arxiv: Some(metadata),
+                ..
+            }) => ArxivPaper::try_from(metadata),
+            Some(OaiMetadata {
+                arxiv_raw: Some(metadata),
+                ..
+            }) => ArxivPaper::try_from(metadata),
+            _ => continue,
+        };
+        // One malformed record shouldn't abort a long harvest
+        match paper {
+            Ok(paper) => papers.push(paper),
+            Err(error) => log::warn!(
+                "Skipping OAI-PMH record {}: {error}",
+                record
+                    .header
+                    .identifier
+                    .as_deref()
+                    .unwrap_or("without identifier")
+            ),
+        }
+    }
+
+    let (resumption_token, complete_list_size) = match list_records.resumption_token {
+        Some(token) => (
+            token
+                .token
+                .map(|token| token.trim().to_string())
+                .filter(|token| !token.is_empty()),
+            token.complete_list_size,
+        ),
+        None => (None, None),
+    };
+
+    Ok(HarvestPage {
+        papers,
+        resumption_token,
+        complete_list_size,
+    })
+}
+
+impl TryFrom<ArxivFormat> for ArxivPaper {
+    type Error = SearchError;
+
+    fn try_from(metadata: ArxivFormat) -> SearchResult<Self> {
+        let id = ArxivId::parse(&metadata.id)?;
+        let authors = metadata
+            .authors
+            .map(|authors| authors.authors)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|author| {
+                let name = [author.forenames, Some(author.keyname), author.suffix]
+                    .into_iter()
+                    .flatten()
+                    .map(|part| normalize_text(&part))
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                ArxivAuthor {
+                    name,
+                    affiliations: author
+                        .affiliations
+                        .iter()
+                        .map(|affiliation| normalize_text(affiliation))
+                        .collect(),
+                }
+            })
+            .collect();
+        let categories = split_categories(metadata.categories.as_deref());
+
+        Ok(Self {
+            abs_url: id.abs_url(),
+            pdf_url: id.pdf_url(),
+            id,
+            title: normalize_text(&metadata.title),
+            summary: normalize_text(&metadata.summary),
+            authors,
+            published: metadata.created.as_deref().and_then(parse_day),
+            updated: metadata.updated.as_deref().and_then(parse_day),
+            primary_category: categories.first().cloned(),
+            categories,
+            doi: non_empty(metadata.doi),
+            journal_ref: non_empty(metadata.journal_ref),
+            comment: non_empty(metadata.comments),
+        })
+    }
+}
+
+impl TryFrom<ArxivRawFormat> for ArxivPaper {
+    type Error = SearchError;
+
+    fn try_from(metadata: ArxivRawFormat) -> SearchResult<Self> {
+        let latest = metadata
+            .versions
+            .last()
+            .map(|version| version.version.as_str());
+        let id = match latest {
+            Some(version) => ArxivId::parse(&format!("{}{version}", metadata.id.trim()))?,
+            None => ArxivId::parse(&metadata.id)?,
+        };
+        let categories = split_categories(metadata.categories.as_deref());
+        let version_date = |version: Option<&ArxivRawVersion>| {
+            version
+                .and_then(|version| version.date.as_deref())
+                .and_then(|date| DateTime::parse_from_rfc2822(date.trim()).ok())
+                .map(|date| date.with_timezone(&Utc))
+        };
+
+        Ok(Self {
+            abs_url: id.abs_url(),
+            pdf_url: id.pdf_url(),
+            id,
+            title: normalize_text(&metadata.title),
+            summary: normalize_text(&metadata.summary),
+            authors: split_raw_authors(metadata.authors.as_deref().unwrap_or_default())
+                .into_iter()
+                .map(|name| ArxivAuthor {
+                    name,
+                    affiliations: Vec::new(),
+                })
+                .collect(),
+            published: version_date(metadata.versions.first()),
+            updated: version_date(metadata.versions.last()),
+            primary_category: categories.first().cloned(),
+            categories,
+            doi: non_empty(metadata.doi),
+            journal_ref: non_empty(metadata.journal_ref),
+            comment: non_empty(metadata.comments),
+        })
+    }
+}
+
+fn split_categories(categories: Option<&str>) -> Vec<String> {
+    categories
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Split an `arXivRaw` author string such as `A. One, B. Two and C. Three`
+pub(super) fn split_raw_authors(authors: &str) -> Vec<String> {
+    normalize_text(authors)
+        .split(", ")
+        .flat_map(|part| part.split(" and "))
+        .map(|name| name.trim().trim_start_matches("and ").trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn parse_day(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .ok()
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARXIV_FORMAT_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<OAI-PMH xmlns="http://www.openarchives.org/OAI/2.0/">
+  <responseDate>2024-03-11T10:00:00Z</responseDate>
+  <request verb="ListRecords" metadataPrefix="arXiv" set="cs">http://export.arxiv.org/oai2</request>
+  <ListRecords>
+    <record>
+      <header>
+        <identifier>oai:arXiv.org:2403.01234</identifier>
+        <datestamp>2024-03-05</datestamp>
+        <setSpec>cs</setSpec>
+      </header>
+      <metadata>
+        <arXiv xmlns="http://arxiv.org/OAI/arXiv/">
+          <id>2403.01234</id>
+          <created>2024-03-02</created>
+          <updated>2024-03-04</updated>
+          <authors>
+            <author><keyname>Lovelace</keyname><forenames>Ada</forenames><affiliation>London</affiliation></author>
+            <author><keyname>Babbage</keyname><forenames>Charles</forenames><suffix>Jr</suffix></author>
+          </authors>
+          <title>Analytical
+            Engines</title>
+          <categories>cs.CL cs.LG</categories>
+          <comments>10 pages</comments>
+          <doi>10.1000/engines</doi>
+          <abstract>  Notes on engines. </abstract>
+        </arXiv>
+      </metadata>
+    </record>
+    <record>
+      <header status="deleted">
+        <identifier>oai:arXiv.org:2403.09999</identifier>
+        <datestamp>2024-03-05</datestamp>
+      </header>
+    </record>
+    <resumptionToken cursor="0" completeListSize="2400">6960524|1001</resumptionToken>
+  </ListRecords>
+</OAI-PMH>"#;
+
+    #[test]
+    fn test_parse_arxiv_format() {
+        let page = parse_list_records(ARXIV_FORMAT_XML).unwrap();
+
+        assert_eq!(page.resumption_token.as_deref(), Some("6960524|1001"));
+        assert_eq!(page.complete_list_size, Some(2400));
+        assert_eq!(page.papers.len(), 1);
+
+        let paper = &page.papers[0];
+        assert_eq!(paper.id.to_string(), "2403.01234");
+        assert_eq!(paper.title, "Analytical Engines");
+        assert_eq!(paper.summary, "Notes on engines.");
+        assert_eq!(paper.authors[0].name, "Ada Lovelace");
+        assert_eq!(paper.authors[0].affiliations, vec!["London"]);
+        assert_eq!(paper.authors[1].name, "Charles Babbage Jr");
+        assert_eq!(paper.primary_category.as_deref(), Some("cs.CL"));
+        assert_eq!(paper.categories, vec!["cs.CL", "cs.LG"]);
+        assert_eq!(
+            paper.published.unwrap().date_naive().to_string(),
+            "2024-03-02"
+        );
+        assert_eq!(paper.pdf_url, "https://arxiv.org/pdf/2403.01234");
+    }
+
+    #[test]
+    fn test_parse_arxiv_raw_format() {
+        let xml = r#"<OAI-PMH>
+  <ListRecords>
+    <record>
+      <header><identifier>oai:arXiv.org:hep-th/9901001</identifier></header>
+      <metadata>
+        <arXivRaw>
+          <id>hep-th/9901001</id>
+          <submitter>Someone</submitter>
+          <version version="v1"><date>Fri, 1 Jan 1999 10:00:00 GMT</date><size>20kb</size></version>
+          <version version="v2"><date>Mon, 1 Feb 1999 12:30:00 GMT</date><size>21kb</size></version>
+          <title>Strings</title>
+          <authors>E. Witten, J. Maldacena and A. Strominger</authors>
+          <categories>hep-th</categories>
+          <abstract>Strings.</abstract>
+        </arXivRaw>
+      </metadata>
+    </record>
+    <resumptionToken cursor="1000" completeListSize="1001"/>
+  </ListRecords>
+</OAI-PMH>"#;
+        let page = parse_list_records(xml).unwrap();
+
+        assert_eq!(page.resumption_token, None);
+        let paper = &page.papers[0];
+        assert_eq!(paper.id.base(), "hep-th/9901001");
+        assert_eq!(paper.id.version(), Some(2));
+        assert_eq!(
+            paper
+                .authors
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["E. Witten", "J. Maldacena", "A. Strominger"]
+        );
+        assert_eq!(
+            paper.published.unwrap().to_rfc3339(),
+            "1999-01-01T10:00:00+00:00"
+        );
+        assert_eq!(
+            paper.updated.unwrap().to_rfc3339(),
+            "1999-02-01T12:30:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_malformed_record_is_skipped() {
+        let xml = r#"<OAI-PMH><ListRecords>
+            <record>
+              <header><identifier>oai:arXiv.org:broken</identifier></header>
+              <metadata><arXiv><id>not an id</id><title>Broken</title></arXiv></metadata>
+            </record>
+            <record>
+              <header><identifier>oai:arXiv.org:2403.00001</identifier></header>
+              <metadata><arXiv><id>2403.00001</id><title>Fine</title></arXiv></metadata>
+            </record>
+        </ListRecords></OAI-PMH>"#;
+
+        let page = parse_list_records(xml).unwrap();
+        assert_eq!(page.papers.len(), 1);
+        assert_eq!(page.papers[0].id.to_string(), "2403.00001");
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(reqwest::header::RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_parse_oai_errors() {
+        let no_records = r#"<OAI-PMH><error code="noRecordsMatch">No records</error></OAI-PMH>"#;
+        assert!(parse_list_records(no_records).unwrap().papers.is_empty());
+
+        let bad_token = r#"<OAI-PMH><error code="badResumptionToken">expired</error></OAI-PMH>"#;
+        assert!(matches!(
+            parse_list_records(bad_token),
+            Err(SearchError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,430 @@
+//! Typed arXiv paper metadata parsed from the Atom feed
+
+use super::id::ArxivId;
+use crate::{
+    error::{SearchError, SearchResult},
+    types::SearchResult as SearchResultType,
+    utils::http::normalize_text,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Full metadata for a single arXiv paper
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArxivPaper {
+    /// arXiv identifier, e.g. `2301.00001v1`; unversioned when the source
+    /// only reports the base identifier
+    pub id: ArxivId,
+    /// Paper title
+    pub title: String,
+    /// Abstract
+    pub summary: String,
+    /// Authors in listed order
+    pub authors: Vec<ArxivAuthor>,
+    /// When version 1 was submitted
+    pub published: Option<DateTime<Utc>>,
+    /// When the retrieved version was submitted
+    pub updated: Option<DateTime<Utc>>,
+    /// Primary subject category, e.g. `cs.CL`
+    pub primary_category: Option<String>,
+    /// All subject categories, primary first
+    pub categories: Vec<String>,
+    /// DOI of the published version
+    pub doi: Option<String>,
+    /// Journal reference of the published version
+    pub journal_ref: Option<String>,
+    /// Author comments (page counts, conference, ...)
+    pub comment: Option<String>,
+    /// Abstract page URL
+    pub abs_url: String,
+    /// PDF URL
+    pub pdf_url: String,
+}
+
+/// An arXiv author with optional affiliations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArxivAuthor {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub affiliations: Vec<String>,
+}
+
+impl ArxivPaper {
+    /// Author names joined with commas
+    pub fn author_names(&self) -> String {
+        self.authors
+            .iter()
+            .map(|author| author.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Recover the paper from a result returned by the ArXiv provider
+    pub fn from_search_result(result: &SearchResultType) -> Option<Self> {
+        let paper = result.raw.as_ref()?.get("paper")?;
+        serde_json::from_value(paper.clone()).ok()
+    }
+
+    /// Convert to the provider-independent result format
+    ///
+    /// The full paper is kept under `raw.paper` alongside the flat
+    /// `arxiv_id`, `published` and `authors` keys.
+    pub fn to_search_result(&self) -> SearchResultType {
+        let published = self
+            .published
+            .map(|date| date.to_rfc3339_opts(SecondsFormat::Secs, true));
+
+        let mut raw_data = serde_json::Map::new();
+        raw_data.insert(
+            "arxiv_id".to_string(),
+            serde_json::Value::String(self.id.to_string()),
+        );
+        if let Some(published) = &published {
+            raw_data.insert(
+                "published".to_string(),
+                serde_json::Value::String(published.clone()),
+            );
+        }
+        if !self.authors.is_empty() {
+            raw_data.insert(
+                "authors".to_string(),
+                serde_json::Value::String(self.author_names()),
+            );
+        }
+        raw_data.insert(
+            "paper".to_string(),
+            serde_json::to_value(self).unwrap_or_default(),
+        );
+
+        SearchResultType {
+            url: self.abs_url.clone(),
+            title: self.title.clone(),
+            snippet: Some(self.summary.clone()),
+            domain: Some("arxiv.org".to_string()),
+            published_date: published,
+            provider: Some("arxiv".to_string()),
+            raw: Some(serde_json::Value::Object(raw_data)),
+        }
+    }
+}
+
+/// One page of an arXiv API result set
+#[derive(Debug, Clone, Default)]
+pub struct ArxivPage {
+    /// Papers on this page
+    pub papers: Vec<ArxivPaper>,
+    /// Total number of papers matching the query (`opensearch:totalResults`)
+    pub total_results: Option<u64>,
+    /// Offset of the first paper on this page (`opensearch:startIndex`)
+    pub start_index: Option<u64>,
+    /// Requested page size (`opensearch:itemsPerPage`)
+    pub items_per_page: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AtomFeed {
+    #[serde(rename = "totalResults")]
+    total_results: Option<u64>,
+    #[serde(rename = "startIndex")]
+    start_index: Option<u64>,
+    #[serde(rename = "itemsPerPage")]
+    items_per_page: Option<u64>,
+    #[serde(rename = "entry", default)]
+    entries: Vec<AtomEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AtomEntry {
+    id: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    summary: String,
+    published: Option<String>,
+    updated: Option<String>,
+    #[serde(rename = "author", default)]
+    authors: Vec<AtomAuthor>,
+    #[serde(rename = "link", default)]
+    links: Vec<AtomLink>,
+    #[serde(rename = "category", default)]
+    categories: Vec<AtomCategory>,
+    primary_category: Option<AtomCategory>,
+    doi: Option<String>,
+    journal_ref: Option<String>,
+    comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AtomAuthor {
+    name: String,
+    #[serde(rename = "affiliation", default)]
+    affiliations: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AtomLink {
+    #[serde(rename = "@href")]
+    href: String,
+    #[serde(rename = "@type")]
+    link_type: Option<String>,
+    #[serde(rename = "@title")]
+    title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AtomCategory {
+    #[serde(rename = "@term")]
+    term: String,
+}
+
+/// Parse an arXiv API Atom feed into a page of papers
+pub(crate) fn parse_feed(xml: &str) -> SearchResult<ArxivPage> {
+    let feed: AtomFeed = quick_xml::de::from_str(xml)
+        .map_err(|e| SearchError::ParseError(format!("Failed to parse ArXiv XML: {e}")))?;
+
+    let mut papers = Vec::with_capacity(feed.entries.len());
+    for entry in feed.entries {
+        // One malformed entry shouldn't discard the rest of the page, but an
+        // API error entry describes the whole request
+        match ArxivPaper::try_from(entry) {
+            Ok(paper) => papers.push(paper),
+            Err(error @ SearchError::InvalidInput(_)) => return Err(error),
+            Err(error) => log::warn!("Skipping arXiv entry: {error}"),
+        }
+    }
+
+    Ok(ArxivPage {
+        papers,
+        total_results: feed.total_results,
+        start_index: feed.start_index,
+        items_per_page: feed.items_per_page,
+    })
+}
+
+impl TryFrom<AtomEntry> for ArxivPaper {
+    type Error = SearchError;
+
+    fn try_from(entry: AtomEntry) -> SearchResult<Self> {
+        // The API reports problems such as malformed IDs as a single entry
+        // whose id points at its error documentation
+        if entry.id.contains("/api/errors") {
+            return Err(SearchError::InvalidInput(format!(
+                "ArXiv API error: {}",
+                normalize_text(&entry.summary)
+            )));
+        }
+
+        let id = ArxivId::parse(&entry.id).map_err(|_| {
+            SearchError::ParseError(format!("Unrecognized arXiv entry id: {}", entry.id))
+        })?;
+
+        let abs_url = entry
+            .links
+            .iter()
+            .find(|link| link.link_type.as_deref() == Some("text/html"))
+            .map(|link| link.href.clone())
+            .unwrap_or_else(|| id.abs_url());
+        let pdf_url = entry
+            .links
+            .iter()
+            .find(|link| {
+                link.title.as_deref() == Some("pdf")
+                    || link.link_type.as_deref() == Some("application/pdf")
+            })
+            .map(|link| link.href.clone())
+            .unwrap_or_else(|| id.pdf_url());
+
+        let primary_category = entry.primary_category.map(|category| category.term);
+        let mut categories: Vec<String> = primary_category.iter().cloned().collect();
+        for category in entry.categories {
+            if !categories.contains(&category.term) {
+                categories.push(category.term);
+            }
+        }
+
+        Ok(Self {
+            id,
+            title: normalize_text(&entry.title),
+            summary: normalize_text(&entry.summary),
+            authors: entry
+                .authors
+                .into_iter()
+                .map(|author| ArxivAuthor {
+                    name: normalize_text(&author.name),
+                    affiliations: author
+                        .affiliations
+                        .iter()
+                        .map(|affiliation| normalize_text(affiliation))
+                        .collect(),
+                })
+                .collect(),
+            published: entry.published.as_deref().and_then(parse_date),
+            updated: entry.updated.as_deref().and_then(parse_date),
+            primary_category,
+            categories,
+            doi: non_empty(entry.doi),
+            journal_ref: non_empty(entry.journal_ref),
+            comment: non_empty(entry.comment),
+            abs_url,
+            pdf_url,
+        })
+    }
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+pub(super) fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| normalize_text(&value))
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEED_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:opensearch="http://a9.com/-/spec/opensearch/1.1/" xmlns:arxiv="http://arxiv.org/schemas/atom">
+  <title type="html">ArXiv Query: search_query=all:electron</title>
+  <opensearch:totalResults>2</opensearch:totalResults>
+  <opensearch:startIndex>0</opensearch:startIndex>
+  <opensearch:itemsPerPage>2</opensearch:itemsPerPage>
+  <entry>
+    <id>http://arxiv.org/abs/2301.00001v2</id>
+    <updated>2023-02-10T12:00:00Z</updated>
+    <published>2023-01-01T09:30:00Z</published>
+    <title>Electron Transport
+      in Graphene</title>
+    <summary>  We study electrons.
+    </summary>
+    <author>
+      <name>Ada Lovelace</name>
+      <arxiv:affiliation>University of London</arxiv:affiliation>
+    </author>
+    <author>
+      <name>Alan Turing</name>
+    </author>
+    <arxiv:doi>10.1000/xyz123</arxiv:doi>
+    <link title="doi" href="http://dx.doi.org/10.1000/xyz123" rel="related"/>
+    <arxiv:comment>12 pages, 3 figures</arxiv:comment>
+    <arxiv:journal_ref>Phys. Rev. B 1, 2 (2023)</arxiv:journal_ref>
+    <link href="http://arxiv.org/abs/2301.00001v2" rel="alternate" type="text/html"/>
+    <link title="pdf" href="http://arxiv.org/pdf/2301.00001v2" rel="related" type="application/pdf"/>
+    <arxiv:primary_category term="cond-mat.mes-hall" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="cond-mat.mes-hall" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="physics.app-ph" scheme="http://arxiv.org/schemas/atom"/>
+  </entry>
+  <entry>
+    <id>http://arxiv.org/abs/2301.00002v1</id>
+    <updated>2023-01-02T00:00:00Z</updated>
+    <published>2023-01-02T00:00:00Z</published>
+    <title>A Second Paper</title>
+    <summary>Short.</summary>
+    <author><name>Grace Hopper</name></author>
+    <link href="http://arxiv.org/abs/2301.00002v1" rel="alternate" type="text/html"/>
+    <arxiv:primary_category term="cs.PL" scheme="http://arxiv.org/schemas/atom"/>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn test_parse_feed_full_metadata() {
+        let page = parse_feed(FEED_XML).unwrap();
+        assert_eq!(page.total_results, Some(2));
+        assert_eq!(page.start_index, Some(0));
+        assert_eq!(page.items_per_page, Some(2));
+
+        let papers = page.papers;
+        assert_eq!(papers.len(), 2);
+
+        let paper = &papers[0];
+        assert_eq!(paper.id.base(), "2301.00001");
+        assert_eq!(paper.id.version(), Some(2));
+        assert_eq!(paper.title, "Electron Transport in Graphene");
+        assert_eq!(paper.summary, "We study electrons.");
+        assert_eq!(paper.authors.len(), 2);
+        assert_eq!(paper.authors[0].affiliations, vec!["University of London"]);
+        assert!(paper.authors[1].affiliations.is_empty());
+        assert_eq!(paper.author_names(), "Ada Lovelace, Alan Turing");
+        assert_eq!(
+            paper.published.unwrap().to_rfc3339(),
+            "2023-01-01T09:30:00+00:00"
+        );
+        assert!(paper.updated > paper.published);
+        assert_eq!(paper.primary_category.as_deref(), Some("cond-mat.mes-hall"));
+        assert_eq!(
+            paper.categories,
+            vec!["cond-mat.mes-hall", "physics.app-ph"]
+        );
+        assert_eq!(paper.doi.as_deref(), Some("10.1000/xyz123"));
+        assert_eq!(
+            paper.journal_ref.as_deref(),
+            Some("Phys. Rev. B 1, 2 (2023)")
+        );
+        assert_eq!(paper.comment.as_deref(), Some("12 pages, 3 figures"));
+        assert_eq!(paper.abs_url, "http://arxiv.org/abs/2301.00001v2");
+        assert_eq!(paper.pdf_url, "http://arxiv.org/pdf/2301.00001v2");
+
+        assert_eq!(papers[1].categories, vec!["cs.PL"]);
+        assert_eq!(papers[1].pdf_url, "https://arxiv.org/pdf/2301.00002v1");
+        assert_eq!(papers[1].doi, None);
+    }
+
+    #[test]
+    fn test_parse_feed_legacy_id() {
+        let xml = FEED_XML.replace("2301.00002v1", "hep-th/9901001v3");
+        let papers = parse_feed(&xml).unwrap().papers;
+
+        assert_eq!(papers[1].id.base(), "hep-th/9901001");
+        assert_eq!(papers[1].id.version(), Some(3));
+        assert_eq!(papers[1].pdf_url, "https://arxiv.org/pdf/hep-th/9901001v3");
+    }
+
+    #[test]
+    fn test_malformed_entry_is_skipped() {
+        let xml = FEED_XML.replace("2301.00001v2</id>", "not-an-arxiv-id</id>");
+        let papers = parse_feed(&xml).unwrap().papers;
+
+        assert_eq!(papers.len(), 1);
+        assert_eq!(papers[0].id.to_string(), "2301.00002v1");
+    }
+
+    #[test]
+    fn test_parse_feed_api_error() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <entry>
+    <id>http://arxiv.org/api/errors#incorrect_id_format_for_1234</id>
+    <title>Error</title>
+    <summary>incorrect id format for 1234</summary>
+  </entry>
+</feed>"#;
+
+        match parse_feed(xml) {
+            Err(SearchError::InvalidInput(message)) => {
+                assert!(message.contains("incorrect id format"))
+            }
+            other => panic!("Expected InvalidInput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_search_result_round_trip() {
+        let paper = parse_feed(FEED_XML).unwrap().papers.remove(0);
+        let result = paper.to_search_result();
+
+        assert_eq!(result.url, paper.abs_url);
+        assert_eq!(result.provider.as_deref(), Some("arxiv"));
+        let raw = result.raw.as_ref().unwrap();
+        assert_eq!(raw["arxiv_id"], "2301.00001v2");
+        assert_eq!(raw["authors"], "Ada Lovelace, Alan Turing");
+        assert_eq!(
+            result.published_date.as_deref(),
+            Some("2023-01-01T09:30:00Z")
+        );
+
+        assert_eq!(ArxivPaper::from_search_result(&result), Some(paper));
+    }
+}
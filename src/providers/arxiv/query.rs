@@ -0,0 +1,430 @@
+//! Typed builder for arXiv `search_query` expressions
+//!
+//! See <https://info.arxiv.org/help/api/user-manual.html#query_details> for the
+//! underlying syntax.
+
+use crate::error::{SearchError, SearchResult};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use std::fmt;
+
+/// Searchable arXiv metadata fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArxivField {
+    /// All fields (`all:`)
+    All,
+    /// Title (`ti:`)
+    Title,
+    /// Author (`au:`)
+    Author,
+    /// Abstract (`abs:`)
+    Abstract,
+    /// Comment (`co:`)
+    Comment,
+    /// Journal reference (`jr:`)
+    JournalRef,
+    /// Subject category, e.g. `cs.CL` (`cat:`)
+    Category,
+    /// Report number (`rn:`)
+    ReportNumber,
+}
+
+impl ArxivField {
+    /// Query prefix used by the arXiv API
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            ArxivField::All => "all",
+            ArxivField::Title => "ti",
+            ArxivField::Author => "au",
+            ArxivField::Abstract => "abs",
+            ArxivField::Comment => "co",
+            ArxivField::JournalRef => "jr",
+            ArxivField::Category => "cat",
+            ArxivField::ReportNumber => "rn",
+        }
+    }
+}
+
+impl fmt::Display for ArxivField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.prefix())
+    }
+}
+
+/// Inclusive submission date range, rendered as
+/// `submittedDate:[YYYYMMDDHHMM TO YYYYMMDDHHMM]`
+///
+/// arXiv matches at minute granularity in GMT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArxivDateRange {
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+}
+
+impl ArxivDateRange {
+    /// Create a range between two instants
+    pub fn new(from: DateTime<Utc>, to: DateTime<Utc>) -> SearchResult<Self> {
+        if from > to {
+            return Err(SearchError::InvalidInput(format!(
+                "ArXiv date range starts after it ends ({} > {})",
+                from.format("%Y-%m-%d %H:%M"),
+                to.format("%Y-%m-%d %H:%M")
+            )));
+        }
+        Ok(Self { from, to })
+    }
+
+    /// Create a range covering whole days, from the start of `from` to the end of `to`
+    pub fn days(from: NaiveDate, to: NaiveDate) -> SearchResult<Self> {
+        let end_of_day = NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN);
+        Self::new(
+            from.and_time(NaiveTime::MIN).and_utc(),
+            to.and_time(end_of_day).and_utc(),
+        )
+    }
+
+    /// Create a range covering the last `days` days up to now
+    pub fn last_days(days: u32) -> SearchResult<Self> {
+        let to = Utc::now();
+        let from = Duration::try_days(i64::from(days))
+            .and_then(|span| to.checked_sub_signed(span))
+            .ok_or_else(|| {
+                SearchError::InvalidInput(format!("ArXiv date range of {days} days is too long"))
+            })?;
+        Self::new(from, to)
+    }
+
+    /// Start of the range
+    pub fn from(&self) -> DateTime<Utc> {
+        self.from
+    }
+
+    /// End of the range
+    pub fn to(&self) -> DateTime<Utc> {
+        self.to
+    }
+}
+
+impl fmt::Display for ArxivDateRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "submittedDate:[{} TO {}]",
+            self.from.format("%Y%m%d%H%M"),
+            self.to.format("%Y%m%d%H%M")
+        )
+    }
+}
+
+/// A structured arXiv search query
+///
+/// Terms are combined with [`and`](Self::and), [`or`](Self::or) and
+/// [`and_not`](Self::and_not); nested combinations are parenthesized when
+/// rendered. A multi-word term matches every word in the field, while
+/// [`phrase`](Self::phrase) matches the words in order.
+///
+/// Pass [`render`](Self::render)'s output as `SearchOptions::native_query`.
+///
+/// ```
+/// use websearch::providers::arxiv::ArxivQuery;
+///
+/// let query = ArxivQuery::author("del_maestro")
+///     .and(ArxivQuery::phrase_in_title("quantum criticality"))
+///     .and_not(ArxivQuery::category("cond-mat.str-el"));
+///
+/// assert_eq!(
+///     query.render().unwrap(),
+///     r#"(au:del_maestro AND ti:"quantum criticality") ANDNOT cat:cond-mat.str-el"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArxivQuery {
+    /// Words that must all appear in a field
+    Term { field: ArxivField, value: String },
+    /// An exact phrase in a field
+    Phrase { field: ArxivField, value: String },
+    /// Papers submitted within a date range
+    Submitted(ArxivDateRange),
+    /// Both sub-queries must match
+    And(Box<ArxivQuery>, Box<ArxivQuery>),
+    /// Either sub-query may match
+    Or(Box<ArxivQuery>, Box<ArxivQuery>),
+    /// The first sub-query must match and the second must not
+    AndNot(Box<ArxivQuery>, Box<ArxivQuery>),
+}
+
+impl ArxivQuery {
+    /// Match words in the given field
+    pub fn term(field: ArxivField, value: impl Into<String>) -> Self {
+        ArxivQuery::Term {
+            field,
+            value: value.into(),
+        }
+    }
+
+    /// Match an exact phrase in the given field
+    pub fn phrase(field: ArxivField, value: impl Into<String>) -> Self {
+        ArxivQuery::Phrase {
+            field,
+            value: value.into(),
+        }
+    }
+
+    /// Match words in any field (`all:`)
+    pub fn all(value: impl Into<String>) -> Self {
+        Self::term(ArxivField::All, value)
+    }
+
+    /// Match words in the title (`ti:`)
+    pub fn title(value: impl Into<String>) -> Self {
+        Self::term(ArxivField::Title, value)
+    }
+
+    /// Match an exact phrase in the title (`ti:"..."`)
+    pub fn phrase_in_title(value: impl Into<String>) -> Self {
+        Self::phrase(ArxivField::Title, value)
+    }
+
+    /// Match an author name (`au:`); arXiv expects `surname` or `surname_initial`
+    pub fn author(value: impl Into<String>) -> Self {
+        Self::term(ArxivField::Author, value)
+    }
+
+    /// Match words in the abstract (`abs:`)
+    pub fn abstract_text(value: impl Into<String>) -> Self {
+        Self::term(ArxivField::Abstract, value)
+    }
+
+    /// Match an exact phrase in the abstract (`abs:"..."`)
+    pub fn phrase_in_abstract(value: impl Into<String>) -> Self {
+        Self::phrase(ArxivField::Abstract, value)
+    }
+
+    /// Match words in the comments (`co:`)
+    pub fn comment(value: impl Into<String>) -> Self {
+        Self::term(ArxivField::Comment, value)
+    }
+
+    /// Match words in the journal reference (`jr:`)
+    pub fn journal_ref(value: impl Into<String>) -> Self {
+        Self::term(ArxivField::JournalRef, value)
+    }
+
+    /// Match a subject category such as `cs.CL` (`cat:`)
+    pub fn category(value: impl Into<String>) -> Self {
+        Self::term(ArxivField::Category, value)
+    }
+
+    /// Match a report number (`rn:`)
+    pub fn report_number(value: impl Into<String>) -> Self {
+        Self::term(ArxivField::ReportNumber, value)
+    }
+
+    /// Match papers submitted within `range`
+    pub fn submitted(range: ArxivDateRange) -> Self {
+        ArxivQuery::Submitted(range)
+    }
+
+    /// Require both this query and `other`
+    pub fn and(self, other: ArxivQuery) -> Self {
+        ArxivQuery::And(Box::new(self), Box::new(other))
+    }
+
+    /// Accept either this query or `other`
+    pub fn or(self, other: ArxivQuery) -> Self {
+        ArxivQuery::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Require this query but exclude anything matching `other`
+    pub fn and_not(self, other: ArxivQuery) -> Self {
+        ArxivQuery::AndNot(Box::new(self), Box::new(other))
+    }
+
+    /// Render the query in arXiv syntax, rejecting terms with no words
+    ///
+    /// An empty term would render as a dangling operator or `ti:""`, which
+    /// arXiv either rejects or silently matches against nothing.
+    pub fn render(&self) -> SearchResult<String> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+
+    fn validate(&self) -> SearchResult<()> {
+        match self {
+            ArxivQuery::Term { field, value } | ArxivQuery::Phrase { field, value } => {
+                if sanitize(value).split_whitespace().next().is_none() {
+                    return Err(SearchError::InvalidInput(format!(
+                        "ArXiv {field}: query term is empty"
+                    )));
+                }
+                Ok(())
+            }
+            ArxivQuery::Submitted(_) => Ok(()),
+            ArxivQuery::And(left, right)
+            | ArxivQuery::Or(left, right)
+            | ArxivQuery::AndNot(left, right) => {
+                left.validate()?;
+                right.validate()
+            }
+        }
+    }
+
+    fn is_compound(&self) -> bool {
+        match self {
+            ArxivQuery::Term { value, .. } => sanitize(value).split_whitespace().count() > 1,
+            ArxivQuery::Phrase { .. } | ArxivQuery::Submitted(_) => false,
+            _ => true,
+        }
+    }
+
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_compound() {
+            write!(f, "({self})")
+        } else {
+            write!(f, "{self}")
+        }
+    }
+
+    fn fmt_binary(
+        f: &mut fmt::Formatter<'_>,
+        left: &ArxivQuery,
+        operator: &str,
+        right: &ArxivQuery,
+    ) -> fmt::Result {
+        left.fmt_operand(f)?;
+        write!(f, " {operator} ")?;
+        right.fmt_operand(f)
+    }
+}
+
+impl fmt::Display for ArxivQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArxivQuery::Term { field, value } => {
+                let value = sanitize(value);
+                let words: Vec<&str> = value.split_whitespace().collect();
+                let terms: Vec<String> =
+                    words.iter().map(|word| format!("{field}:{word}")).collect();
+                write!(f, "{}", terms.join(" AND "))
+            }
+            ArxivQuery::Phrase { field, value } => {
+                let value = sanitize(value);
+                let words: Vec<&str> = value.split_whitespace().collect();
+                write!(f, "{field}:\"{}\"", words.join(" "))
+            }
+            ArxivQuery::Submitted(range) => write!(f, "{range}"),
+            ArxivQuery::And(left, right) => Self::fmt_binary(f, left, "AND", right),
+            ArxivQuery::Or(left, right) => Self::fmt_binary(f, left, "OR", right),
+            ArxivQuery::AndNot(left, right) => Self::fmt_binary(f, left, "ANDNOT", right),
+        }
+    }
+}
+
+/// Remove characters that would break out of a term (quotes, grouping and
+/// field separators)
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '"' | '(' | ')' | ':' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_terms() {
+        assert_eq!(ArxivQuery::all("electron").to_string(), "all:electron");
+        assert_eq!(ArxivQuery::category("cs.CL").to_string(), "cat:cs.CL");
+        assert_eq!(
+            ArxivQuery::title("quantum computing").to_string(),
+            "ti:quantum AND ti:computing"
+        );
+        assert_eq!(
+            ArxivQuery::phrase_in_abstract("  dark   matter ").to_string(),
+            "abs:\"dark matter\""
+        );
+    }
+
+    #[test]
+    fn test_boolean_grouping() {
+        let query = ArxivQuery::category("cs.CL")
+            .or(ArxivQuery::category("cs.LG"))
+            .and(ArxivQuery::title("transformer"));
+        assert_eq!(
+            query.to_string(),
+            "(cat:cs.CL OR cat:cs.LG) AND ti:transformer"
+        );
+
+        let query =
+            ArxivQuery::abstract_text("neural network").and_not(ArxivQuery::comment("withdrawn"));
+        assert_eq!(
+            query.to_string(),
+            "(abs:neural AND abs:network) ANDNOT co:withdrawn"
+        );
+    }
+
+    #[test]
+    fn test_submitted_date_range() {
+        let from = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let query = ArxivQuery::category("cs.CL").and(ArxivQuery::submitted(
+            ArxivDateRange::days(from, to).unwrap(),
+        ));
+
+        assert_eq!(
+            query.to_string(),
+            "cat:cs.CL AND submittedDate:[202403040000 TO 202403102359]"
+        );
+    }
+
+    #[test]
+    fn test_date_range_validation() {
+        let from = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+
+        assert!(matches!(
+            ArxivDateRange::days(from, to),
+            Err(SearchError::InvalidInput(_))
+        ));
+        assert!(ArxivDateRange::days(to, to).is_ok());
+
+        let week = ArxivDateRange::last_days(7).unwrap();
+        assert_eq!((week.to() - week.from()).num_days(), 7);
+        assert!(matches!(
+            ArxivDateRange::last_days(u32::MAX),
+            Err(SearchError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_values_cannot_break_syntax() {
+        let query = ArxivQuery::title("a\") OR (all:b");
+        assert_eq!(query.to_string(), "ti:a AND ti:OR AND ti:all AND ti:b");
+        assert!(!query.to_string().contains('"'));
+    }
+
+    #[test]
+    fn test_empty_terms_are_rejected() {
+        for query in [
+            ArxivQuery::title(""),
+            ArxivQuery::phrase_in_title("  \"() "),
+            ArxivQuery::category("cs.CL").and(ArxivQuery::author(":")),
+            ArxivQuery::all("x").and_not(ArxivQuery::comment("")),
+        ] {
+            assert!(
+                matches!(query.render(), Err(SearchError::InvalidInput(_))),
+                "{query:?}"
+            );
+        }
+        assert_eq!(
+            ArxivQuery::title("Attention: all you need")
+                .render()
+                .unwrap(),
+            "ti:Attention AND ti:all AND ti:you AND ti:need"
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Crossref REST API provider
+//!
+//! Searches Crossref's `works` endpoint. Setting a `mailto` address routes
+//! requests to the faster "polite" pool. See <https://api.crossref.org/>.
+
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SortBy, SortOrder,
+    },
+    utils::http::{html_to_text, normalize_text, HttpClient},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// Largest page Crossref returns
+pub const MAX_ROWS: u32 = 1000;
+
+/// Crossref configuration
+#[derive(Debug, Clone)]
+pub struct CrossrefConfig {
+    /// API root
+    pub base_url: String,
+    /// Contact address; identifies the client and selects the polite pool
+    pub mailto: Option<String>,
+    /// Filters applied to every search, e.g. `("type", "journal-article")` or
+    /// `("from-pub-date", "2020-01-01")`
+    pub filters: Vec<(String, String)>,
+}
+
+impl Default for CrossrefConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.crossref.org".to_string(),
+            mailto: None,
+            filters: Vec::new(),
+        }
+    }
+}
+
+impl ProviderConfig for CrossrefConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        if let Some((name, _)) = self
+            .filters
+            .iter()
+            .find(|(name, value)| name.is_empty() || value.is_empty() || name.contains(','))
+        {
+            return Err(SearchError::ConfigError(format!(
+                "Invalid Crossref filter '{name}'"
+            )));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+/// A work record from Crossref
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CrossrefWork {
+    #[serde(rename = "DOI")]
+    pub doi: String,
+    #[serde(default)]
+    pub title: Vec<String>,
+    #[serde(default)]
+    pub author: Vec<CrossrefAuthor>,
+    #[serde(default)]
+    pub container_title: Vec<String>,
+    pub publisher: Option<String>,
+    #[serde(rename = "type")]
+    pub work_type: Option<String>,
+    pub issued: Option<CrossrefDate>,
+    #[serde(rename = "URL")]
+    pub url: Option<String>,
+    /// JATS-formatted abstract, when the publisher deposited one
+    #[serde(rename = "abstract")]
+    pub abstract_text: Option<String>,
+    #[serde(default)]
+    pub link: Vec<CrossrefLink>,
+    #[serde(default)]
+    pub license: Vec<CrossrefLicense>,
+    pub is_referenced_by_count: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrossrefAuthor {
+    pub given: Option<String>,
+    pub family: Option<String>,
+    /// Organisational authors only have a name
+    pub name: Option<String>,
+}
+
+impl CrossrefAuthor {
+    /// `Family, Given`, or the organisation name
+    pub fn display_name(&self) -> Option<String> {
+        match (&self.family, &self.given) {
+            (Some(family), Some(given)) => Some(format!("{family}, {given}")),
+            (Some(family), None) => Some(family.clone()),
+            _ => self.name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CrossrefDate {
+    /// `[[year, month, day]]` with month and day optional
+    #[serde(default)]
+    pub date_parts: Vec<Vec<Option<i32>>>,
+}
+
+impl CrossrefDate {
+    pub fn year(&self) -> Option<i32> {
+        self.date_parts.first()?.first().copied().flatten()
+    }
+
+    /// `YYYY`, `YYYY-MM` or `YYYY-MM-DD`
+    pub fn to_iso(&self) -> Option<String> {
+        let parts: Vec<i32> = self
+            .date_parts
+            .first()?
+            .iter()
+            .map_while(|part| *part)
+            .collect();
+        match parts.as_slice() {
+            [year] => Some(format!("{year:04}")),
+            [year, month] => Some(format!("{year:04}-{month:02}")),
+            [year, month, day, ..] => Some(format!("{year:04}-{month:02}-{day:02}")),
+            [] => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CrossrefLink {
+    #[serde(rename = "URL")]
+    pub url: String,
+    pub content_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossrefLicense {
+    #[serde(rename = "URL")]
+    pub url: String,
+}
+
+impl CrossrefWork {
+    /// Full-text PDF link deposited by the publisher, if any
+    pub fn pdf_url(&self) -> Option<&str> {
+        self.link
+            .iter()
+            .find(|link| link.content_type.as_deref() == Some("application/pdf"))
+            .map(|link| link.url.as_str())
+    }
+
+    /// Whether the work carries a Creative Commons license
+    pub fn is_open_access(&self) -> bool {
+        self.license
+            .iter()
+            .any(|license| license.url.contains("creativecommons.org"))
+    }
+
+    /// Convert to the provider-independent result format
+    pub fn to_search_result(&self) -> SearchResultType {
+        let url = self
+            .url
+            .clone()
+            .unwrap_or_else(|| format!("https://doi.org/{}", self.doi));
+        let title = self
+            .title
+            .first()
+            .map(|title| normalize_text(title))
+            .unwrap_or_default();
+        let venue = self.container_title.first().cloned();
+        let year = self.issued.as_ref().and_then(CrossrefDate::year);
+
+        let mut raw = serde_json::json!({
+            "doi": self.doi,
+            "authors": self.author.iter().filter_map(CrossrefAuthor::display_name).collect::<Vec<_>>(),
+            "is_open_access": self.is_open_access(),
+            "cited_by_count": self.is_referenced_by_count,
+            "work": self,
+        });
+        if let Some(venue) = &venue {
+            raw["venue"] = venue.clone().into();
+        }
+        if let Some(year) = year {
+            raw["year"] = year.into();
+        }
+        if let Some(pdf_url) = self.pdf_url() {
+            raw["pdf_url"] = pdf_url.into();
+        }
+
+        SearchResultType {
+            domain: crate::utils::http::extract_domain(&url),
+            url,
+            title,
+            snippet: self
+                .abstract_text
+                .as_deref()
+                .map(abstract_text)
+                .filter(|text| !text.is_empty())
+                .or(venue),
+            published_date: self.issued.as_ref().and_then(CrossrefDate::to_iso),
+            provider: Some("crossref".to_string()),
+            raw: Some(raw),
+        }
+    }
+}
+
+/// Plain text of a JATS/HTML abstract, without its leading "Abstract" heading
+pub(crate) fn abstract_text(text: &str) -> String {
+    let plain = html_to_text(text);
+    plain
+        .strip_prefix("Abstract ")
+        .map(str::to_string)
+        .unwrap_or(plain)
+}
+
+#[derive(Debug, Deserialize)]
+struct WorksResponse {
+    status: String,
+    message: WorksMessage,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct WorksMessage {
+    total_results: Option<u64>,
+    #[serde(default)]
+    items: Vec<CrossrefWork>,
+}
+
+/// Crossref search provider
+#[derive(Debug)]
+pub struct CrossrefProvider {
+    config: CrossrefConfig,
+    http_client: HttpClient,
+}
+
+impl CrossrefProvider {
+    pub fn new() -> Self {
+        Self::with_config(CrossrefConfig::default())
+    }
+
+    /// Create a provider in the polite pool
+    pub fn with_mailto(mailto: impl Into<String>) -> Self {
+        Self::with_config(CrossrefConfig {
+            mailto: Some(mailto.into()),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_config(config: CrossrefConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// Search works and return typed records with the total match count
+    pub async fn search_works(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<(Vec<CrossrefWork>, Option<u64>)> {
+        self.config.validate()?;
+        let query = options.query.trim();
+        if query.is_empty() {
+            return Err(SearchError::InvalidInput(
+                "Crossref search requires a query".to_string(),
+            ));
+        }
+
+        let rows = options.max_results.unwrap_or(10).clamp(1, MAX_ROWS);
+        let offset = match options.start {
+            Some(start) => start,
+            None => options
+                .page
+                .unwrap_or(1)
+                .saturating_sub(1)
+                .checked_mul(rows)
+                .ok_or_else(|| {
+                    SearchError::InvalidInput("Crossref page is out of range".to_string())
+                })?,
+        };
+
+        let mut url = Url::parse(&format!(
+            "{}/works",
+            self.config.base_url.trim_end_matches('/')
+        ))?;
+        {
+            let mut params = url.query_pairs_mut();
+            params
+                .append_pair("query", query)
+                .append_pair("rows", &rows.to_string())
+                .append_pair("offset", &offset.to_string());
+            if !self.config.filters.is_empty() {
+                let filter = self
+                    .config
+                    .filters
+                    .iter()
+                    .map(|(name, value)| format!("{name}:{value}"))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                params.append_pair("filter", &filter);
+            }
+            if let Some(sort_by) = &options.sort_by {
+                let sort = match sort_by {
+                    SortBy::Relevance => "relevance",
+                    SortBy::SubmittedDate => "published",
+                    SortBy::LastUpdatedDate => "updated",
+                };
+                params.append_pair("sort", sort);
+            }
+            if let Some(sort_order) = &options.sort_order {
+                let order = match sort_order {
+                    SortOrder::Ascending => "asc",
+                    SortOrder::Descending => "desc",
+                };
+                params.append_pair("order", order);
+            }
+            if let Some(mailto) = &self.config.mailto {
+                params.append_pair("mailto", mailto);
+            }
+        }
+
+        let response: WorksResponse =
+            self.http_client
+                .get_json(url.as_str())
+                .await
+                .map_err(|error| match error {
+                    SearchError::HttpError {
+                        status_code: Some(429),
+                        ..
+                    } => SearchError::RateLimit(
+                        "Crossref rate limit reached; set mailto to use the polite pool"
+                            .to_string(),
+                    ),
+                    other => other,
+                })?;
+        if response.status != "ok" {
+            return Err(SearchError::ProviderError(format!(
+                "Crossref returned status '{}'",
+                response.status
+            )));
+        }
+
+        Ok((response.message.items, response.message.total_results))
+    }
+}
+
+impl Default for CrossrefProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for CrossrefProvider {
+    fn name(&self) -> &str {
+        "crossref"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let (works, total_results) = self.search_works(options).await?;
+        Ok(SearchResponse {
+            results: works.iter().map(CrossrefWork::to_search_result).collect(),
+            total_results,
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "crossref".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config.insert(
+            "polite_pool".to_string(),
+            self.config.mailto.is_some().to_string(),
+        );
+        config.insert("max_results".to_string(), MAX_ROWS.to_string());
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORK_JSON: &str = r#"{
+        "DOI": "10.1038/nature14539",
+        "title": ["Deep   learning"],
+        "author": [
+            {"given": "Yann", "family": "LeCun", "sequence": "first"},
+            {"given": "Yoshua", "family": "Bengio", "sequence": "additional"},
+            {"name": "Deep Learning Consortium", "sequence": "additional"}
+        ],
+        "container-title": ["Nature"],
+        "publisher": "Springer Science and Business Media LLC",
+        "type": "journal-article",
+        "issued": {"date-parts": [[2015, 5]]},
+        "URL": "https://doi.org/10.1038/nature14539",
+        "abstract": "<jats:title>Abstract</jats:title><jats:p>Deep learning allows <jats:italic>computational</jats:italic> models.</jats:p><jats:p>Layers &amp; representations.</jats:p>",
+        "link": [
+            {"URL": "https://www.nature.com/articles/nature14539.pdf", "content-type": "application/pdf"},
+            {"URL": "https://www.nature.com/articles/nature14539", "content-type": "text/html"}
+        ],
+        "license": [{"URL": "https://creativecommons.org/licenses/by/4.0/"}],
+        "is-referenced-by-count": 50000
+    }"#;
+
+    #[test]
+    fn test_work_to_search_result() {
+        let work: CrossrefWork = serde_json::from_str(WORK_JSON).unwrap();
+        let result = work.to_search_result();
+
+        assert_eq!(result.title, "Deep learning");
+        assert_eq!(result.published_date.as_deref(), Some("2015-05"));
+        assert_eq!(
+            result.snippet.as_deref(),
+            Some("Deep learning allows computational models. Layers & representations.")
+        );
+
+        let raw = result.raw.as_ref().unwrap();
+        assert_eq!(raw["doi"], "10.1038/nature14539");
+        assert_eq!(raw["venue"], "Nature");
+        assert_eq!(raw["year"], 2015);
+        assert_eq!(raw["is_open_access"], true);
+        assert_eq!(
+            raw["pdf_url"],
+            "https://www.nature.com/articles/nature14539.pdf"
+        );
+        assert_eq!(raw["authors"][2], "Deep Learning Consortium");
+
+        let citation = crate::citation::Citation::from_search_result(&result).unwrap();
+        assert_eq!(citation.key, "lecun2015deep");
+    }
+
+    #[test]
+    fn test_filter_validation() {
+        let config = CrossrefConfig {
+            filters: vec![("type,has-abstract".to_string(), "x".to_string())],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -2,11 +2,20 @@
 
 use crate::{
     error::{SearchError, SearchResult},
-    types::{ProviderConfig, SearchOptions, SearchProvider, SearchResult as SearchResultType},
-    utils::{debug, http::HttpClient},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SuggestProvider,
+    },
+    utils::{
+        debug,
+        http::{self, HttpClient},
+    },
 };
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use url::Url;
 
 /// DuckDuckGo search types
 #[derive(Debug, Clone)]
@@ -16,12 +25,12 @@ pub enum SearchType {
     News,
 }
 
-impl ToString for SearchType {
-    fn to_string(&self) -> String {
+impl fmt::Display for SearchType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            SearchType::Text => "text".to_string(),
-            SearchType::Images => "images".to_string(),
-            SearchType::News => "news".to_string(),
+            SearchType::Text => write!(f, "text"),
+            SearchType::Images => write!(f, "images"),
+            SearchType::News => write!(f, "news"),
         }
     }
 }
@@ -37,6 +46,12 @@ pub struct DuckDuckGoConfig {
     pub use_lite: bool,
     /// User agent for requests
     pub user_agent: String,
+    /// Whether to keep sponsored results (`.result--ad`) in the output
+    pub include_ads: bool,
+    /// Base URL for the Instant Answer (zero-click info) API
+    pub instant_answer_url: String,
+    /// Base URL for the autocomplete (`ac/`) endpoint
+    pub suggest_url: String,
 }
 
 impl Default for DuckDuckGoConfig {
@@ -46,6 +61,9 @@ impl Default for DuckDuckGoConfig {
             search_type: SearchType::Text,
             use_lite: false,
             user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36".to_string(),
+            include_ads: false,
+            instant_answer_url: "https://api.duckduckgo.com/".to_string(),
+            suggest_url: "https://duckduckgo.com/ac/".to_string(),
         }
     }
 }
@@ -87,22 +105,55 @@ impl DuckDuckGoProvider {
 
     /// Create a DuckDuckGo provider for image search
     pub fn for_images() -> Self {
-        let mut config = DuckDuckGoConfig::default();
-        config.search_type = SearchType::Images;
-        config.base_url = "https://duckduckgo.com/i.js".to_string();
-        Self::with_config(config)
+        Self::with_config(DuckDuckGoConfig {
+            base_url: "https://duckduckgo.com/i.js".to_string(),
+            search_type: SearchType::Images,
+            ..Default::default()
+        })
     }
 
     /// Create a DuckDuckGo provider for news search
     pub fn for_news() -> Self {
-        let mut config = DuckDuckGoConfig::default();
-        config.search_type = SearchType::News;
-        config.base_url = "https://duckduckgo.com/news.js".to_string();
-        Self::with_config(config)
+        Self::with_config(DuckDuckGoConfig {
+            base_url: "https://duckduckgo.com/news.js".to_string(),
+            search_type: SearchType::News,
+            ..Default::default()
+        })
+    }
+
+    /// Fetch DuckDuckGo's Instant Answer (zero-click info) for a query
+    ///
+    /// This is a quick factual summary (abstract, direct answer, definition,
+    /// infobox and related topics) and complements the organic results
+    /// returned by [`SearchProvider::search`].
+    pub async fn instant_answer(&self, query: &str) -> SearchResult<InstantAnswer> {
+        if query.trim().is_empty() {
+            return Err(SearchError::InvalidInput(
+                "Instant Answer lookup requires a query".to_string(),
+            ));
+        }
+
+        let mut url = Url::parse(&self.config.instant_answer_url)?;
+        url.query_pairs_mut()
+            .append_pair("q", query.trim())
+            .append_pair("format", "json")
+            .append_pair("no_html", "1")
+            .append_pair("no_redirect", "1")
+            .append_pair("skip_disambig", "1");
+
+        let mut headers = HashMap::new();
+        headers.insert("User-Agent".to_string(), self.config.user_agent.clone());
+
+        let response: ApiInstantAnswer = self
+            .http_client
+            .get_json_with_headers(url.as_str(), headers)
+            .await?;
+
+        Ok(response.into())
     }
 
     /// Perform text search using HTML scraping
-    async fn search_text(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+    async fn search_text(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
         let mut headers = HashMap::new();
         headers.insert("User-Agent".to_string(), self.config.user_agent.clone());
         headers.insert(
@@ -137,61 +188,46 @@ impl DuckDuckGoProvider {
             &format!("DuckDuckGo HTML response received (length: {})", html.len()),
         );
 
+        let document = Html::parse_document(&html);
+        if is_anomaly_page(&document)? {
+            return Err(SearchError::Blocked(
+                "DuckDuckGo returned an anomaly (bot-check) page instead of results. \
+                 Slow down requests, vary the user agent, or retry later."
+                    .to_string(),
+            ));
+        }
+
         // Parse HTML and extract search results
-        self.parse_text_results(&html, options.max_results.unwrap_or(10))
+        let results = self.parse_text_results(&document, options.max_results.unwrap_or(10))?;
+        let mut response = parse_page_extras(&document)?;
+        response.results = results;
+        Ok(response)
     }
 
     /// Parse HTML search results from DuckDuckGo
+    ///
+    /// Each `.result` container is parsed as a unit so that a result missing a
+    /// snippet (or a skipped ad) cannot shift fields onto its neighbours.
     fn parse_text_results(
         &self,
-        html: &str,
+        document: &Html,
         max_results: u32,
     ) -> SearchResult<Vec<SearchResultType>> {
-        let document = Html::parse_document(html);
+        let selectors = ResultSelectors::new()?;
         let mut results = Vec::new();
 
-        // Selector for search result links
-        let result_selector = Selector::parse("h2.result__title a")
-            .map_err(|_| SearchError::ParseError("Invalid CSS selector for results".to_string()))?;
-
-        // Selector for result snippets
-        let snippet_selector = Selector::parse(".result__snippet").map_err(|_| {
-            SearchError::ParseError("Invalid CSS selector for snippets".to_string())
-        })?;
-
-        let result_links: Vec<_> = document.select(&result_selector).collect();
-        let result_snippets: Vec<_> = document.select(&snippet_selector).collect();
-
-        for (i, link_element) in result_links.iter().enumerate() {
+        for block in document.select(&selectors.result) {
             if results.len() >= max_results as usize {
                 break;
             }
 
-            if let Some(href) = link_element.value().attr("href") {
-                // Skip DuckDuckGo internal links
-                if href.contains("duckduckgo.com") || href.contains("google.com/search") {
-                    continue;
-                }
-
-                let url = crate::utils::http::normalize_url(href);
-                let title = crate::utils::http::normalize_text(&link_element.inner_html());
-
-                // Get corresponding snippet
-                let snippet = result_snippets.get(i).map(|snippet_elem| {
-                    crate::utils::http::normalize_text(&snippet_elem.inner_html())
-                });
-
-                let domain = crate::utils::http::extract_domain(&url);
-
-                results.push(SearchResultType {
-                    url,
-                    title,
-                    snippet,
-                    domain,
-                    published_date: None,
-                    provider: Some("duckduckgo".to_string()),
-                    raw: None,
-                });
+            let sponsored = block.value().classes().any(|class| class == "result--ad");
+            if sponsored && !self.config.include_ads {
+                continue;
+            }
+
+            if let Some(result) = parse_result_block(block, &selectors, sponsored) {
+                results.push(result);
             }
         }
 
@@ -199,6 +235,179 @@ impl DuckDuckGoProvider {
     }
 }
 
+/// CSS selectors used to pick apart a DuckDuckGo HTML result block
+struct ResultSelectors {
+    result: Selector,
+    title: Selector,
+    snippet: Selector,
+    displayed_url: Selector,
+    favicon: Selector,
+}
+
+impl ResultSelectors {
+    fn new() -> SearchResult<Self> {
+        let parse = |css: &str| {
+            Selector::parse(css).map_err(|_| {
+                SearchError::ParseError(format!("Invalid CSS selector for results: {css}"))
+            })
+        };
+
+        Ok(Self {
+            result: parse("div.result")?,
+            title: parse("h2.result__title a")?,
+            snippet: parse(".result__snippet")?,
+            displayed_url: parse(".result__url")?,
+            favicon: parse(".result__icon__img")?,
+        })
+    }
+}
+
+/// Extract a single search result from a `.result` container
+fn parse_result_block(
+    block: ElementRef<'_>,
+    selectors: &ResultSelectors,
+    sponsored: bool,
+) -> Option<SearchResultType> {
+    let link_element = block.select(&selectors.title).next()?;
+    let href = link_element.value().attr("href")?;
+    let url = resolve_result_url(href, sponsored)?;
+
+    let title = element_text(link_element);
+    if title.is_empty() {
+        return None;
+    }
+
+    let snippet = block
+        .select(&selectors.snippet)
+        .next()
+        .map(element_text)
+        .filter(|snippet| !snippet.is_empty());
+    let displayed_url = block
+        .select(&selectors.displayed_url)
+        .next()
+        .map(element_text)
+        .filter(|displayed| !displayed.is_empty());
+    let favicon = block
+        .select(&selectors.favicon)
+        .next()
+        .and_then(|img| img.value().attr("src"))
+        .map(http::normalize_url);
+
+    let domain = http::extract_domain(&url);
+
+    let mut raw_data = serde_json::Map::new();
+    raw_data.insert("sponsored".to_string(), serde_json::Value::Bool(sponsored));
+    if let Some(displayed_url) = displayed_url {
+        raw_data.insert(
+            "displayed_url".to_string(),
+            serde_json::Value::String(displayed_url),
+        );
+    }
+    if let Some(favicon) = favicon {
+        raw_data.insert("favicon".to_string(), serde_json::Value::String(favicon));
+    }
+
+    Some(SearchResultType {
+        url,
+        title,
+        snippet,
+        domain,
+        published_date: None,
+        provider: Some("duckduckgo".to_string()),
+        raw: Some(serde_json::Value::Object(raw_data)),
+    })
+}
+
+/// Resolve a result link to its target URL
+///
+/// DuckDuckGo wraps organic links in a `/l/?uddg=<target>` redirect; those are
+/// unwrapped. Any other DuckDuckGo-internal link is dropped unless it belongs to
+/// a sponsored result, whose click-through URL is kept as-is.
+fn resolve_result_url(href: &str, sponsored: bool) -> Option<String> {
+    let url = http::normalize_url(href);
+    let parsed = Url::parse(&url).ok()?;
+    let host = parsed.host_str().unwrap_or_default();
+
+    if host == "duckduckgo.com" || host.ends_with(".duckduckgo.com") {
+        if parsed.path().starts_with("/l/") {
+            return parsed
+                .query_pairs()
+                .find(|(key, _)| key == "uddg")
+                .map(|(_, target)| target.into_owned());
+        }
+        return sponsored.then_some(url);
+    }
+
+    if host.ends_with("google.com") && parsed.path().starts_with("/search") {
+        return None;
+    }
+
+    Some(url)
+}
+
+/// Extract spelling corrections and related searches from a results page
+///
+/// The returned response has no results; the caller fills them in.
+fn parse_page_extras(document: &Html) -> SearchResult<SearchResponse> {
+    let parse = |css: &str| {
+        Selector::parse(css)
+            .map_err(|_| SearchError::ParseError(format!("Invalid CSS selector: {css}")))
+    };
+    let spelling_selector = parse("#did_you_mean, .msg--spelling")?;
+    let link_selector = parse("a")?;
+    let related_selector = parse(".related-searches a, .result--related a")?;
+
+    let mut response = SearchResponse::default();
+
+    for message in document.select(&spelling_selector) {
+        let text = element_text(message).to_lowercase();
+        let Some(query) = message
+            .select(&link_selector)
+            .next()
+            .map(element_text)
+            .filter(|query| !query.is_empty())
+        else {
+            continue;
+        };
+
+        if text.contains("including results for") || text.contains("showing results for") {
+            response.corrected_query.get_or_insert(query);
+        } else if text.contains("did you mean") {
+            response.suggested_query.get_or_insert(query);
+        }
+    }
+
+    for link in document.select(&related_selector) {
+        let related = element_text(link);
+        if !related.is_empty() && !response.related_searches.contains(&related) {
+            response.related_searches.push(related);
+        }
+    }
+
+    Ok(response)
+}
+
+/// Detect DuckDuckGo's anomaly/CAPTCHA page, which is served with HTTP 200
+///
+/// Matches the challenge DOM rather than its wording, and only when the page
+/// has no result blocks, so a result that quotes the page isn't mistaken
+/// for it.
+fn is_anomaly_page(document: &Html) -> SearchResult<bool> {
+    let parse = |css: &str| {
+        Selector::parse(css)
+            .map_err(|_| SearchError::ParseError(format!("Invalid CSS selector: {css}")))
+    };
+    let challenge = parse("#anomaly-modal, .anomaly-modal__modal, form#challenge-form")?;
+    let result = ResultSelectors::new()?.result;
+
+    Ok(document.select(&challenge).next().is_some() && document.select(&result).next().is_none())
+}
+
+/// Collect the text content of an element with normalized whitespace
+fn element_text(element: ElementRef<'_>) -> String {
+    http::normalize_text(&element.text().collect::<String>())
+}
+
 impl Default for DuckDuckGoProvider {
     fn default() -> Self {
         Self::new()
@@ -212,6 +421,12 @@ impl SearchProvider for DuckDuckGoProvider {
     }
 
     async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        self.search_response(options)
+            .await
+            .map(|response| response.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
         match self.config.search_type {
             SearchType::Text => self.search_text(options).await,
             SearchType::Images => {
@@ -235,6 +450,510 @@ impl SearchProvider for DuckDuckGoProvider {
             self.config.search_type.to_string(),
         );
         config.insert("use_lite".to_string(), self.config.use_lite.to_string());
+        config.insert(
+            "include_ads".to_string(),
+            self.config.include_ads.to_string(),
+        );
         config
     }
 }
+
+#[async_trait::async_trait]
+impl SuggestProvider for DuckDuckGoProvider {
+    async fn suggest(&self, query: &str) -> SearchResult<Vec<String>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut url = Url::parse(&self.config.suggest_url)?;
+        url.query_pairs_mut()
+            .append_pair("q", query)
+            .append_pair("type", "list");
+
+        let mut headers = HashMap::new();
+        headers.insert("User-Agent".to_string(), self.config.user_agent.clone());
+
+        let response: serde_json::Value = self
+            .http_client
+            .get_json_with_headers(url.as_str(), headers)
+            .await?;
+
+        Ok(parse_suggestions(&response))
+    }
+}
+
+/// Extract suggestions from an `ac/` response
+///
+/// With `type=list` the endpoint answers in OpenSearch format
+/// (`["query", ["s1", "s2"]]`); without it, a list of `{"phrase": ...}`
+/// objects is returned. Both are accepted, preserving the ranked order.
+fn parse_suggestions(response: &serde_json::Value) -> Vec<String> {
+    let items = match response {
+        serde_json::Value::Array(items) => items,
+        _ => return Vec::new(),
+    };
+
+    let phrases: Vec<&serde_json::Value> = match items.get(1) {
+        Some(serde_json::Value::Array(list)) if items[0].is_string() => list.iter().collect(),
+        _ => items.iter().filter_map(|item| item.get("phrase")).collect(),
+    };
+
+    let mut suggestions: Vec<String> = Vec::new();
+    for phrase in phrases.into_iter().filter_map(|phrase| phrase.as_str()) {
+        let phrase = http::normalize_text(phrase);
+        if !phrase.is_empty() && !suggestions.contains(&phrase) {
+            suggestions.push(phrase);
+        }
+    }
+    suggestions
+}
+
+/// DuckDuckGo Instant Answer ("zero-click info") for a query
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstantAnswer {
+    /// Name of the topic the answer is about
+    pub heading: Option<String>,
+    /// Topic summary, usually taken from Wikipedia
+    pub abstract_text: Option<String>,
+    /// Name of the source of the abstract
+    pub abstract_source: Option<String>,
+    /// URL of the source of the abstract
+    pub abstract_url: Option<String>,
+    /// Direct answer (calculations, conversions, etc.)
+    pub answer: Option<String>,
+    /// Kind of direct answer (e.g. `calc`, `ip`)
+    pub answer_type: Option<String>,
+    /// Dictionary definition
+    pub definition: Option<String>,
+    /// Name of the source of the definition
+    pub definition_source: Option<String>,
+    /// URL of the source of the definition
+    pub definition_url: Option<String>,
+    /// Image associated with the topic
+    pub image: Option<String>,
+    /// Response category (`A` article, `D` disambiguation, `E` exclusive, ...)
+    pub answer_category: Option<String>,
+    /// Structured facts about the topic
+    pub infobox: Vec<InfoboxEntry>,
+    /// Related topics, with grouped topics flattened
+    pub related_topics: Vec<RelatedTopic>,
+}
+
+impl InstantAnswer {
+    /// Whether DuckDuckGo returned nothing useful for the query
+    pub fn is_empty(&self) -> bool {
+        self.abstract_text.is_none()
+            && self.answer.is_none()
+            && self.definition.is_none()
+            && self.infobox.is_empty()
+            && self.related_topics.is_empty()
+    }
+}
+
+/// A single label/value fact from an Instant Answer infobox
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InfoboxEntry {
+    pub label: String,
+    pub value: String,
+}
+
+/// A topic related to an Instant Answer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedTopic {
+    /// Topic description
+    pub text: String,
+    /// DuckDuckGo URL for the topic
+    pub url: Option<String>,
+    /// Topic icon
+    pub icon: Option<String>,
+    /// Name of the group the topic was listed under, if any
+    pub group: Option<String>,
+}
+
+/// Raw Instant Answer API response
+///
+/// Several fields change type depending on the answer (e.g. `Infobox` is an
+/// empty string when absent, `Answer` is an object for some answer types), so
+/// those are kept as JSON values and normalized in the conversion.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ApiInstantAnswer {
+    #[serde(default)]
+    heading: String,
+    #[serde(default)]
+    abstract_text: String,
+    #[serde(default)]
+    abstract_source: String,
+    #[serde(default, rename = "AbstractURL")]
+    abstract_url: String,
+    #[serde(default)]
+    answer: serde_json::Value,
+    #[serde(default)]
+    answer_type: String,
+    #[serde(default)]
+    definition: String,
+    #[serde(default)]
+    definition_source: String,
+    #[serde(default, rename = "DefinitionURL")]
+    definition_url: String,
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    r#type: String,
+    #[serde(default)]
+    infobox: serde_json::Value,
+    #[serde(default)]
+    related_topics: Vec<ApiRelatedTopic>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ApiRelatedTopic {
+    Group {
+        #[serde(rename = "Name")]
+        name: String,
+        #[serde(rename = "Topics", default)]
+        topics: Vec<ApiTopic>,
+    },
+    Topic(ApiTopic),
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiTopic {
+    #[serde(rename = "Text", default)]
+    text: String,
+    #[serde(rename = "FirstURL")]
+    first_url: Option<String>,
+    #[serde(rename = "Icon")]
+    icon: Option<ApiIcon>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiIcon {
+    #[serde(rename = "URL", default)]
+    url: String,
+}
+
+impl From<ApiInstantAnswer> for InstantAnswer {
+    fn from(api: ApiInstantAnswer) -> Self {
+        let answer = match api.answer {
+            serde_json::Value::String(answer) => non_empty(answer),
+            serde_json::Value::Object(object) => object
+                .get("result")
+                .and_then(|result| result.as_str())
+                .map(http::normalize_text)
+                .filter(|answer| !answer.is_empty()),
+            _ => None,
+        };
+
+        let infobox = api
+            .infobox
+            .get("content")
+            .and_then(|content| content.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| {
+                        let label = entry.get("label")?.as_str()?;
+                        let value = match entry.get("value")? {
+                            serde_json::Value::String(value) => value.clone(),
+                            serde_json::Value::Number(value) => value.to_string(),
+                            _ => return None,
+                        };
+                        Some(InfoboxEntry {
+                            label: label.to_string(),
+                            value,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut related_topics = Vec::new();
+        for topic in api.related_topics {
+            match topic {
+                ApiRelatedTopic::Topic(topic) => related_topics.extend(topic.into_related(None)),
+                ApiRelatedTopic::Group { name, topics } => related_topics.extend(
+                    topics
+                        .into_iter()
+                        .filter_map(|topic| topic.into_related(Some(name.clone()))),
+                ),
+            }
+        }
+
+        Self {
+            heading: non_empty(api.heading),
+            abstract_text: non_empty(api.abstract_text),
+            abstract_source: non_empty(api.abstract_source),
+            abstract_url: non_empty(api.abstract_url),
+            answer,
+            answer_type: non_empty(api.answer_type),
+            definition: non_empty(api.definition),
+            definition_source: non_empty(api.definition_source),
+            definition_url: non_empty(api.definition_url),
+            image: non_empty(api.image).map(|image| absolute_duckduckgo_url(&image)),
+            answer_category: non_empty(api.r#type),
+            infobox,
+            related_topics,
+        }
+    }
+}
+
+impl ApiTopic {
+    fn into_related(self, group: Option<String>) -> Option<RelatedTopic> {
+        let text = non_empty(self.text)?;
+        Some(RelatedTopic {
+            text,
+            url: self.first_url.and_then(non_empty),
+            icon: self
+                .icon
+                .and_then(|icon| non_empty(icon.url))
+                .map(|icon| absolute_duckduckgo_url(&icon)),
+            group,
+        })
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.trim().is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Instant Answer image paths are relative to duckduckgo.com
+fn absolute_duckduckgo_url(path: &str) -> String {
+    if path.starts_with('/') && !path.starts_with("//") {
+        format!("https://duckduckgo.com{path}")
+    } else {
+        http::normalize_url(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESULTS_HTML: &str = r##"
+        <div class="results">
+          <div class="result results_links results_links_deep result--ad">
+            <div class="links_main links_deep result__body">
+              <h2 class="result__title">
+                <a class="result__a" href="https://duckduckgo.com/y.js?ad_domain=ads.example&u3=x">Buy Rust Now</a>
+              </h2>
+              <a class="result__snippet" href="#">Sponsored snippet</a>
+            </div>
+          </div>
+          <div class="result results_links results_links_deep web-result">
+            <div class="links_main links_deep result__body">
+              <h2 class="result__title">
+                <a class="result__a" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fwww.rust-lang.org%2F&rut=abc">Rust <b>Programming</b> Language</a>
+              </h2>
+              <div class="result__extras"><div class="result__extras__url">
+                <span class="result__icon"><a href="#"><img class="result__icon__img" src="//external-content.duckduckgo.com/ip3/www.rust-lang.org.ico"></a></span>
+                <a class="result__url" href="#"> www.rust-lang.org </a>
+              </div></div>
+              <a class="result__snippet" href="#">A language empowering everyone.</a>
+            </div>
+          </div>
+          <div class="result results_links results_links_deep web-result">
+            <div class="links_main links_deep result__body">
+              <h2 class="result__title">
+                <a class="result__a" href="https://doc.rust-lang.org/book/">The Book</a>
+              </h2>
+            </div>
+          </div>
+          <div class="result results_links results_links_deep web-result">
+            <div class="links_main links_deep result__body">
+              <h2 class="result__title">
+                <a class="result__a" href="https://crates.io/">crates.io</a>
+              </h2>
+              <a class="result__snippet" href="#">The Rust community's crate registry</a>
+            </div>
+          </div>
+        </div>
+    "##;
+
+    #[test]
+    fn test_parse_text_results_keeps_fields_together() {
+        let provider = DuckDuckGoProvider::new();
+        let results = provider
+            .parse_text_results(&Html::parse_document(RESULTS_HTML), 10)
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+
+        assert_eq!(results[0].url, "https://www.rust-lang.org/");
+        assert_eq!(results[0].title, "Rust Programming Language");
+        assert_eq!(
+            results[0].snippet.as_deref(),
+            Some("A language empowering everyone.")
+        );
+        let raw = results[0].raw.as_ref().unwrap();
+        assert_eq!(raw["displayed_url"], "www.rust-lang.org");
+        assert_eq!(
+            raw["favicon"],
+            "https://external-content.duckduckgo.com/ip3/www.rust-lang.org.ico"
+        );
+        assert_eq!(raw["sponsored"], false);
+
+        assert_eq!(results[1].title, "The Book");
+        assert_eq!(results[1].snippet, None);
+
+        assert_eq!(results[2].title, "crates.io");
+        assert_eq!(
+            results[2].snippet.as_deref(),
+            Some("The Rust community's crate registry")
+        );
+    }
+
+    #[test]
+    fn test_instant_answer_from_api_response() {
+        let json = r#"{
+            "Heading": "Rust (programming language)",
+            "AbstractText": "Rust is a general-purpose programming language.",
+            "AbstractSource": "Wikipedia",
+            "AbstractURL": "https://en.wikipedia.org/wiki/Rust_(programming_language)",
+            "Answer": "",
+            "AnswerType": "",
+            "Definition": "",
+            "Image": "/i/rust.png",
+            "Type": "A",
+            "Infobox": {"content": [
+                {"label": "Designed by", "value": "Graydon Hoare"},
+                {"label": "First appeared", "value": 2015},
+                {"label": "Website", "value": {"url": "rust-lang.org"}}
+            ]},
+            "RelatedTopics": [
+                {"Text": "Cargo - package manager", "FirstURL": "https://duckduckgo.com/Cargo", "Icon": {"URL": ""}},
+                {"Name": "See also", "Topics": [
+                    {"Text": "Go (programming language)", "FirstURL": "https://duckduckgo.com/Go", "Icon": {"URL": "/i/go.png"}}
+                ]}
+            ]
+        }"#;
+
+        let api: ApiInstantAnswer = serde_json::from_str(json).unwrap();
+        let answer = InstantAnswer::from(api);
+
+        assert_eq!(
+            answer.heading.as_deref(),
+            Some("Rust (programming language)")
+        );
+        assert_eq!(answer.abstract_source.as_deref(), Some("Wikipedia"));
+        assert_eq!(answer.answer, None);
+        assert_eq!(answer.answer_category.as_deref(), Some("A"));
+        assert_eq!(
+            answer.image.as_deref(),
+            Some("https://duckduckgo.com/i/rust.png")
+        );
+        assert_eq!(answer.infobox.len(), 2);
+        assert_eq!(answer.infobox[1].value, "2015");
+        assert_eq!(answer.related_topics.len(), 2);
+        assert_eq!(answer.related_topics[0].icon, None);
+        assert_eq!(answer.related_topics[1].group.as_deref(), Some("See also"));
+        assert_eq!(
+            answer.related_topics[1].icon.as_deref(),
+            Some("https://duckduckgo.com/i/go.png")
+        );
+        assert!(!answer.is_empty());
+    }
+
+    #[test]
+    fn test_instant_answer_empty_response() {
+        let json = r#"{"Heading": "", "AbstractText": "", "Answer": "", "Infobox": "", "RelatedTopics": []}"#;
+        let answer = InstantAnswer::from(serde_json::from_str::<ApiInstantAnswer>(json).unwrap());
+
+        assert!(answer.is_empty());
+    }
+
+    #[test]
+    fn test_parse_page_extras() {
+        let html = r#"
+            <div id="did_you_mean" class="msg msg--spelling">
+              Including results for <a href="/html/?q=rust+programming">rust programming</a>.
+              Search only for <a href="/html/?q=rust+progrmaming">rust progrmaming</a>
+            </div>
+            <div class="related-searches">
+              <a href="/html/?q=rust+book">rust book</a>
+              <a href="/html/?q=rust+vs+go">rust vs go</a>
+            </div>
+        "#;
+        let response = parse_page_extras(&Html::parse_document(html)).unwrap();
+
+        assert_eq!(
+            response.corrected_query.as_deref(),
+            Some("rust programming")
+        );
+        assert_eq!(response.suggested_query, None);
+        assert_eq!(response.related_searches, vec!["rust book", "rust vs go"]);
+
+        let did_you_mean = r#"
+            <div class="msg msg--spelling">Did you mean <a href="/html/?q=rustacean">rustacean</a>?</div>
+        "#;
+        let response = parse_page_extras(&Html::parse_document(did_you_mean)).unwrap();
+        assert_eq!(response.corrected_query, None);
+        assert_eq!(response.suggested_query.as_deref(), Some("rustacean"));
+
+        let response = parse_page_extras(&Html::parse_document(RESULTS_HTML)).unwrap();
+        assert!(response.related_searches.is_empty());
+    }
+
+    #[test]
+    fn test_name_is_unambiguous_with_suggest_in_scope() {
+        // Both SearchProvider and SuggestProvider are in scope here
+        let provider = DuckDuckGoProvider::new();
+        assert_eq!(provider.name(), "duckduckgo");
+        let suggest: &dyn SuggestProvider = &provider;
+        assert_eq!(suggest.name(), "duckduckgo");
+    }
+
+    #[test]
+    fn test_parse_suggestions() {
+        let list = serde_json::json!(["rust", ["rust lang", "rust game", "rust lang"]]);
+        assert_eq!(parse_suggestions(&list), vec!["rust lang", "rust game"]);
+
+        let phrases = serde_json::json!([{"phrase": "rust book"}, {"phrase": "rustup"}]);
+        assert_eq!(parse_suggestions(&phrases), vec!["rust book", "rustup"]);
+
+        assert!(parse_suggestions(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_is_anomaly_page() {
+        let anomaly = r#"
+            <form id="challenge-form" action="//duckduckgo.com/anomaly.js?sv=html" method="POST">
+              <div class="anomaly-modal__title">Unfortunately, bots use DuckDuckGo too.</div>
+            </form>
+        "#;
+
+        let is_anomaly = |html: &str| is_anomaly_page(&Html::parse_document(html)).unwrap();
+
+        assert!(is_anomaly(anomaly));
+        assert!(!is_anomaly(RESULTS_HTML));
+        assert!(!is_anomaly("<div class=\"no-results\">No results.</div>"));
+        // A result quoting the challenge page is still a result
+        assert!(!is_anomaly(
+            r#"<div class="result results_links web-result">
+                 <h2 class="result__title"><a class="result__a" href="https://example.com/">Bot checks</a></h2>
+                 <a class="result__snippet">The challenge-form says "bots use DuckDuckGo too".</a>
+               </div>"#
+        ));
+    }
+
+    #[test]
+    fn test_parse_text_results_marks_ads_when_included() {
+        let provider = DuckDuckGoProvider::with_config(DuckDuckGoConfig {
+            include_ads: true,
+            ..Default::default()
+        });
+        let results = provider
+            .parse_text_results(&Html::parse_document(RESULTS_HTML), 2)
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "Buy Rust Now");
+        assert_eq!(results[0].raw.as_ref().unwrap()["sponsored"], true);
+        assert_eq!(results[1].raw.as_ref().unwrap()["sponsored"], false);
+    }
+}
@@ -0,0 +1,678 @@
+//! Elasticsearch and OpenSearch provider
+//!
+//! Sends a `multi_match` query, or a user-supplied query template, to the
+//! `_search` endpoint of an index and maps hits to results through a
+//! configurable field mapping. Highlight fragments become snippets when the
+//! cluster returns them. The request body is the same for OpenSearch.
+//!
+//! ```rust,no_run
+//! use websearch::providers::elasticsearch::{ElasticsearchConfig, ElasticsearchProvider};
+//! use websearch::{web_search, types::SearchOptions};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = ElasticsearchProvider::with_config(ElasticsearchConfig {
+//!     base_url: "https://search.internal:9200".to_string(),
+//!     index: "wiki".to_string(),
+//!     fields: vec!["title^3".to_string(), "body".to_string()],
+//!     ..Default::default()
+//! });
+//! let results = web_search(SearchOptions {
+//!     query: "vpn setup".to_string(),
+//!     provider: Box::new(provider),
+//!     ..Default::default()
+//! })
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SortBy, SortOrder,
+    },
+    utils::http::{extract_domain, normalize_text, truncate_text, HttpClient},
+};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Placeholder replaced by the query in [`ElasticsearchConfig::query_template`]
+pub const QUERY_PLACEHOLDER: &str = "{{query}}";
+
+/// Largest `from + size` an index allows by default (`index.max_result_window`)
+pub const MAX_RESULT_WINDOW: u32 = 10_000;
+
+/// How to authenticate with the cluster
+#[derive(Debug, Clone)]
+pub enum ElasticsearchAuth {
+    Basic {
+        username: String,
+        password: String,
+    },
+    /// The `encoded` value returned when the API key was created
+    ApiKey(String),
+    Bearer(String),
+}
+
+impl ElasticsearchAuth {
+    fn header_value(&self) -> String {
+        match self {
+            ElasticsearchAuth::Basic { username, password } => format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(format!("{username}:{password}"))
+            ),
+            ElasticsearchAuth::ApiKey(key) => format!("ApiKey {key}"),
+            ElasticsearchAuth::Bearer(token) => format!("Bearer {token}"),
+        }
+    }
+}
+
+/// Which `_source` fields hold each part of a result
+///
+/// Names may be dotted paths into nested objects (`meta.title`); for arrays
+/// the first element is used.
+#[derive(Debug, Clone)]
+pub struct FieldMapping {
+    pub title: String,
+    /// Document URL; when absent, results link to the document in the index
+    pub url: Option<String>,
+    /// Text used as the snippet and highlighted
+    pub snippet: String,
+    /// Date used for `published_date` and date sorting
+    pub date: Option<String>,
+}
+
+impl Default for FieldMapping {
+    fn default() -> Self {
+        Self {
+            title: "title".to_string(),
+            url: Some("url".to_string()),
+            snippet: "content".to_string(),
+            date: Some("date".to_string()),
+        }
+    }
+}
+
+/// Elasticsearch configuration
+#[derive(Debug, Clone)]
+pub struct ElasticsearchConfig {
+    /// Cluster URL
+    pub base_url: String,
+    /// Index, alias or comma-separated list of indices to search
+    pub index: String,
+    pub auth: Option<ElasticsearchAuth>,
+    /// Fields for the `multi_match` query, with optional boosts (`title^2`)
+    pub fields: Vec<String>,
+    /// Query DSL used instead of `multi_match`; every [`QUERY_PLACEHOLDER`]
+    /// in its strings is replaced by the query
+    pub query_template: Option<Value>,
+    pub mapping: FieldMapping,
+    /// Request highlight fragments for the snippet field
+    pub highlight: bool,
+    /// Markers placed around highlighted terms
+    pub highlight_tags: (String, String),
+    /// Approximate fragment length in characters
+    pub fragment_size: u32,
+    /// Fragments joined into a snippet
+    pub fragments: u32,
+    /// Snippet length when the snippet field is used without highlighting
+    pub snippet_length: usize,
+    /// Largest `from + size` requested, matching the index's
+    /// `index.max_result_window` setting
+    pub max_result_window: u32,
+}
+
+impl Default for ElasticsearchConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:9200".to_string(),
+            index: String::new(),
+            auth: None,
+            fields: vec!["title^2".to_string(), "content".to_string()],
+            query_template: None,
+            mapping: FieldMapping::default(),
+            highlight: true,
+            highlight_tags: ("**".to_string(), "**".to_string()),
+            fragment_size: 150,
+            fragments: 2,
+            snippet_length: 300,
+            max_result_window: MAX_RESULT_WINDOW,
+        }
+    }
+}
+
+impl ProviderConfig for ElasticsearchConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        if self.index.trim().is_empty() {
+            return Err(SearchError::ConfigError(
+                "An index to search is required".to_string(),
+            ));
+        }
+        if self.query_template.is_none() && self.fields.is_empty() {
+            return Err(SearchError::ConfigError(
+                "Either query fields or a query template is required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn api_key(&self) -> Option<&str> {
+        match &self.auth {
+            Some(ElasticsearchAuth::ApiKey(key)) | Some(ElasticsearchAuth::Bearer(key)) => {
+                Some(key)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchBody {
+    hits: Hits,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hits {
+    total: Option<HitsTotal>,
+    #[serde(default)]
+    hits: Vec<ElasticsearchHit>,
+}
+
+/// `{"value": 42, "relation": "eq"}` since 7.0, a bare number before
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum HitsTotal {
+    Object { value: u64 },
+    Count(u64),
+}
+
+/// A hit as returned by `_search`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ElasticsearchHit {
+    #[serde(rename = "_index")]
+    pub index: String,
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_score")]
+    pub score: Option<f64>,
+    #[serde(rename = "_source", default)]
+    pub source: Value,
+    /// Highlight fragments by field
+    #[serde(default)]
+    pub highlight: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: ErrorCause,
+}
+
+/// Older clusters report some errors as a plain string
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ErrorCause {
+    Object {
+        #[serde(rename = "type")]
+        kind: String,
+        reason: Option<String>,
+    },
+    Message(String),
+}
+
+/// Look up a dotted path in a document, taking the first element of arrays
+fn field<'a>(source: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut value = source;
+    for key in path.split('.') {
+        if let Value::Array(items) = value {
+            value = items.first()?;
+        }
+        value = value.get(key)?;
+    }
+    match value {
+        Value::Array(items) => items.first(),
+        Value::Null => None,
+        value => Some(value),
+    }
+}
+
+fn field_text(source: &Value, path: &str) -> Option<String> {
+    match field(source, path)? {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+    .map(|text| normalize_text(&text))
+    .filter(|text| !text.is_empty())
+}
+
+/// Dates are kept as stored; epoch milliseconds become RFC 3339
+fn field_date(source: &Value, path: &str) -> Option<String> {
+    match field(source, path)? {
+        Value::Number(millis) => {
+            DateTime::<Utc>::from_timestamp_millis(millis.as_i64()?).map(|date| date.to_rfc3339())
+        }
+        Value::String(date) => Some(date.clone()).filter(|date| !date.is_empty()),
+        _ => None,
+    }
+}
+
+/// Replace the query placeholder in every string of a template
+fn fill_template(template: &Value, query: &str) -> Value {
+    match template {
+        Value::String(text) => Value::String(text.replace(QUERY_PLACEHOLDER, query)),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| fill_template(item, query))
+                .collect(),
+        ),
+        Value::Object(object) => Value::Object(
+            object
+                .iter()
+                .map(|(key, value)| (key.clone(), fill_template(value, query)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+impl ElasticsearchHit {
+    /// Convert to the provider-independent result format using `config`'s
+    /// field mapping
+    ///
+    /// The snippet is the highlight fragments of the snippet field, or the
+    /// start of the field itself; `raw` carries `index`, `id`, `score` and
+    /// the `_source` document.
+    pub fn to_search_result(&self, config: &ElasticsearchConfig) -> SearchResultType {
+        let mapping = &config.mapping;
+        let fragments = self
+            .highlight
+            .get(&mapping.snippet)
+            .or_else(|| self.highlight.values().next())
+            .filter(|fragments| !fragments.is_empty())
+            .map(|fragments| {
+                fragments
+                    .iter()
+                    .map(|fragment| normalize_text(fragment))
+                    .collect::<Vec<_>>()
+                    .join(" ... ")
+            });
+        let url = mapping
+            .url
+            .as_deref()
+            .and_then(|path| field_text(&self.source, path))
+            .unwrap_or_else(|| {
+                format!(
+                    "{}/{}/_doc/{}",
+                    config.base_url.trim_end_matches('/'),
+                    self.index,
+                    self.id
+                )
+            });
+
+        SearchResultType {
+            title: field_text(&self.source, &mapping.title).unwrap_or_else(|| self.id.clone()),
+            snippet: fragments.or_else(|| {
+                field_text(&self.source, &mapping.snippet)
+                    .map(|text| truncate_text(&text, config.snippet_length))
+            }),
+            domain: extract_domain(&url),
+            url,
+            published_date: mapping
+                .date
+                .as_deref()
+                .and_then(|path| field_date(&self.source, path)),
+            provider: Some("elasticsearch".to_string()),
+            raw: Some(json!({
+                "index": self.index,
+                "id": self.id,
+                "score": self.score,
+                "source": self.source,
+            })),
+        }
+    }
+}
+
+/// Map cluster error responses to the matching `SearchError`
+fn map_error(error: SearchError) -> SearchError {
+    let SearchError::HttpError {
+        status_code: Some(status),
+        response_body,
+        ..
+    } = &error
+    else {
+        return error;
+    };
+    let reason = response_body
+        .as_deref()
+        .and_then(|body| serde_json::from_str::<ErrorBody>(body).ok())
+        .map(|body| match body.error {
+            ErrorCause::Object {
+                kind,
+                reason: Some(reason),
+            } => format!("{kind}: {reason}"),
+            ErrorCause::Object { kind, reason: None } => kind,
+            ErrorCause::Message(message) => message,
+        })
+        .unwrap_or_else(|| format!("status {status}"));
+
+    match status {
+        400 => SearchError::InvalidInput(format!("Elasticsearch rejected the query: {reason}")),
+        401 | 403 => SearchError::AuthenticationError(format!(
+            "Elasticsearch refused the credentials: {reason}"
+        )),
+        404 => SearchError::ConfigError(format!("Elasticsearch index not found: {reason}")),
+        429 => SearchError::RateLimit(format!("Elasticsearch is overloaded: {reason}")),
+        _ => SearchError::ProviderError(format!("Elasticsearch error: {reason}")),
+    }
+}
+
+/// Elasticsearch search provider
+#[derive(Debug)]
+pub struct ElasticsearchProvider {
+    config: ElasticsearchConfig,
+    http_client: HttpClient,
+}
+
+impl ElasticsearchProvider {
+    /// Search `index` on a local cluster with the default field mapping
+    pub fn new(index: impl Into<String>) -> Self {
+        Self::with_config(ElasticsearchConfig {
+            index: index.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_config(config: ElasticsearchConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// The `_search` request body for `options`
+    pub fn request_body(&self, options: &SearchOptions) -> SearchResult<Value> {
+        let query = options.query.trim();
+        if query.is_empty() {
+            return Err(SearchError::InvalidInput(
+                "Elasticsearch search requires a query".to_string(),
+            ));
+        }
+
+        let size = options.max_results.unwrap_or(10).max(1);
+        let from = options
+            .page
+            .unwrap_or(1)
+            .saturating_sub(1)
+            .checked_mul(size)
+            .filter(|from| {
+                from.checked_add(size)
+                    .is_some_and(|end| end <= self.config.max_result_window)
+            })
+            .ok_or_else(|| {
+                SearchError::InvalidInput(format!(
+                    "Elasticsearch pages stop at {} results",
+                    self.config.max_result_window
+                ))
+            })?;
+
+        let mut body = json!({
+            "query": match &self.config.query_template {
+                Some(template) => fill_template(template, query),
+                None => json!({
+                    "multi_match": {
+                        "query": query,
+                        "fields": self.config.fields,
+                    }
+                }),
+            },
+            "from": from,
+            "size": size,
+            "track_total_hits": true,
+        });
+
+        if self.config.highlight {
+            body["highlight"] = json!({
+                "pre_tags": [self.config.highlight_tags.0],
+                "post_tags": [self.config.highlight_tags.1],
+                "fields": {
+                    self.config.mapping.snippet.clone(): {
+                        "fragment_size": self.config.fragment_size,
+                        "number_of_fragments": self.config.fragments,
+                    }
+                },
+            });
+        }
+
+        match (&options.sort_by, &self.config.mapping.date) {
+            (Some(SortBy::SubmittedDate | SortBy::LastUpdatedDate), Some(date)) => {
+                let order = match options.sort_order {
+                    Some(SortOrder::Ascending) => "asc",
+                    _ => "desc",
+                };
+                body["sort"] = json!([{ date.clone(): { "order": order } }, "_score"]);
+            }
+            (Some(SortBy::SubmittedDate | SortBy::LastUpdatedDate), None) => {
+                return Err(SearchError::InvalidInput(
+                    "Sorting by date requires a date field in the mapping".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        Ok(body)
+    }
+
+    /// Run a search and return the hits with the total match count
+    pub async fn search_hits(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<(Vec<ElasticsearchHit>, Option<u64>)> {
+        self.config.validate()?;
+        let body = self.request_body(options)?;
+        let url = format!(
+            "{}/{}/_search",
+            self.config.base_url.trim_end_matches('/'),
+            self.config.index.trim()
+        );
+
+        let mut headers = HashMap::new();
+        if let Some(auth) = &self.config.auth {
+            headers.insert("Authorization".to_string(), auth.header_value());
+        }
+        let response: SearchBody = self
+            .http_client
+            .post_json_with_headers(&url, &body, headers)
+            .await
+            .map_err(map_error)?;
+
+        let total = response.hits.total.map(|total| match total {
+            HitsTotal::Object { value } | HitsTotal::Count(value) => value,
+        });
+        Ok((response.hits.hits, total))
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for ElasticsearchProvider {
+    fn name(&self) -> &str {
+        "elasticsearch"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let (hits, total_results) = self.search_hits(options).await?;
+        Ok(SearchResponse {
+            results: hits
+                .iter()
+                .map(|hit| hit.to_search_result(&self.config))
+                .collect(),
+            total_results,
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "elasticsearch".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config.insert("index".to_string(), self.config.index.clone());
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(query: &str) -> SearchOptions {
+        SearchOptions {
+            query: query.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_field_paths() {
+        let source = json!({
+            "meta": {"title": "  VPN   setup "},
+            "links": [{"href": "https://wiki.example.com/vpn"}],
+            "updated": 1700000000000u64,
+        });
+
+        assert_eq!(
+            field_text(&source, "meta.title").as_deref(),
+            Some("VPN setup")
+        );
+        assert_eq!(
+            field_text(&source, "links.href").as_deref(),
+            Some("https://wiki.example.com/vpn")
+        );
+        assert_eq!(field_text(&source, "meta.missing"), None);
+        assert_eq!(
+            field_date(&source, "updated").as_deref(),
+            Some("2023-11-14T22:13:20+00:00")
+        );
+    }
+
+    #[test]
+    fn test_query_template() {
+        let provider = ElasticsearchProvider::with_config(ElasticsearchConfig {
+            index: "wiki".to_string(),
+            query_template: Some(json!({
+                "bool": {
+                    "must": {"simple_query_string": {"query": "{{query}}"}},
+                    "filter": {"term": {"status": "published"}}
+                }
+            })),
+            highlight: false,
+            ..Default::default()
+        });
+        let body = provider.request_body(&options("vpn")).unwrap();
+
+        assert_eq!(
+            body["query"]["bool"]["must"]["simple_query_string"]["query"],
+            "vpn"
+        );
+        assert_eq!(
+            body["query"]["bool"]["filter"]["term"]["status"],
+            "published"
+        );
+        assert!(body.get("highlight").is_none());
+    }
+
+    #[test]
+    fn test_date_sort_requires_date_field() {
+        let mut config = ElasticsearchConfig {
+            index: "wiki".to_string(),
+            ..Default::default()
+        };
+        config.mapping.date = None;
+        let provider = ElasticsearchProvider::with_config(config);
+        let options = SearchOptions {
+            sort_by: Some(SortBy::LastUpdatedDate),
+            ..options("vpn")
+        };
+
+        assert!(matches!(
+            provider.request_body(&options),
+            Err(SearchError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_pages_past_the_result_window_are_rejected() {
+        let provider = ElasticsearchProvider::with_config(ElasticsearchConfig {
+            index: "wiki".to_string(),
+            ..Default::default()
+        });
+        let last_page = SearchOptions {
+            max_results: Some(100),
+            page: Some(100),
+            ..options("vpn")
+        };
+        assert_eq!(provider.request_body(&last_page).unwrap()["from"], 9_900);
+
+        for (max_results, page) in [(100, 101), (u32::MAX, 2), (u32::MAX, 1), (2, u32::MAX)] {
+            let options = SearchOptions {
+                max_results: Some(max_results),
+                page: Some(page),
+                ..options("vpn")
+            };
+            assert!(matches!(
+                provider.request_body(&options),
+                Err(SearchError::InvalidInput(_))
+            ));
+        }
+
+        let provider = ElasticsearchProvider::with_config(ElasticsearchConfig {
+            index: "wiki".to_string(),
+            max_result_window: 50_000,
+            ..Default::default()
+        });
+        let deep_page = SearchOptions {
+            max_results: Some(100),
+            page: Some(500),
+            ..options("vpn")
+        };
+        assert_eq!(provider.request_body(&deep_page).unwrap()["from"], 49_900);
+    }
+
+    #[test]
+    fn test_hit_without_highlight_or_url() {
+        let config = ElasticsearchConfig {
+            base_url: "http://es:9200/".to_string(),
+            index: "wiki".to_string(),
+            snippet_length: 20,
+            ..Default::default()
+        };
+        let hit: ElasticsearchHit = serde_json::from_value(json!({
+            "_index": "wiki-2024",
+            "_id": "42",
+            "_score": 1.5,
+            "_source": {"title": "Onboarding", "content": "Welcome to the team, here is everything you need."}
+        }))
+        .unwrap();
+        let result = hit.to_search_result(&config);
+
+        assert_eq!(result.url, "http://es:9200/wiki-2024/_doc/42");
+        assert_eq!(result.snippet.as_deref(), Some("Welcome to the team,..."));
+        assert_eq!(result.raw.unwrap()["score"], 1.5);
+    }
+}
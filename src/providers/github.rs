@@ -0,0 +1,597 @@
+//! GitHub search provider
+//!
+//! Repository, code and issue search through the REST search endpoints. The
+//! query is passed through unchanged, so GitHub qualifiers such as
+//! `language:rust`, `repo:owner/name` or `is:open` work as they do on the site.
+//! See <https://docs.github.com/en/rest/search/search>.
+
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SortBy, SortOrder,
+    },
+    utils::http::{truncate_text, HttpClient},
+};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use url::Url;
+
+/// Largest page the search endpoints return
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Longest issue body kept as a snippet
+const SNIPPET_LENGTH: usize = 300;
+
+/// Which search endpoint to query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GitHubSearchKind {
+    #[default]
+    Repositories,
+    Code,
+    /// Issues and pull requests
+    Issues,
+}
+
+impl GitHubSearchKind {
+    fn path(self) -> &'static str {
+        match self {
+            GitHubSearchKind::Repositories => "search/repositories",
+            GitHubSearchKind::Code => "search/code",
+            GitHubSearchKind::Issues => "search/issues",
+        }
+    }
+
+    /// GitHub's sort key for `sort_by`, when the endpoint supports one
+    fn sort_key(self, sort_by: &SortBy) -> SearchResult<Option<&'static str>> {
+        match (self, sort_by) {
+            (_, SortBy::Relevance) => Ok(None),
+            (
+                GitHubSearchKind::Repositories | GitHubSearchKind::Issues,
+                SortBy::LastUpdatedDate,
+            ) => Ok(Some("updated")),
+            (GitHubSearchKind::Issues, SortBy::SubmittedDate) => Ok(Some("created")),
+            (kind, sort_by) => Err(SearchError::InvalidInput(format!(
+                "GitHub {kind} search cannot be sorted by {sort_by}"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for GitHubSearchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GitHubSearchKind::Repositories => write!(f, "repositories"),
+            GitHubSearchKind::Code => write!(f, "code"),
+            GitHubSearchKind::Issues => write!(f, "issues"),
+        }
+    }
+}
+
+/// GitHub configuration
+#[derive(Debug, Clone)]
+pub struct GitHubConfig {
+    /// REST API root
+    pub base_url: String,
+    /// Personal access token; required for code search and raises the rate limit
+    pub token: Option<String>,
+    /// Endpoint used by [`SearchProvider::search`]
+    pub kind: GitHubSearchKind,
+    /// GitHub-native sort key (e.g. `stars`, `forks`, `comments`), used
+    /// instead of the mapping from `SortBy`
+    pub sort: Option<String>,
+}
+
+impl Default for GitHubConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.github.com".to_string(),
+            token: None,
+            kind: GitHubSearchKind::default(),
+            sort: None,
+        }
+    }
+}
+
+impl ProviderConfig for GitHubConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn api_key(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+}
+
+/// One page of a search endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubSearchPage<T> {
+    pub total_count: u64,
+    /// Set when GitHub timed out before searching everything
+    #[serde(default)]
+    pub incomplete_results: bool,
+    #[serde(default = "Vec::new")]
+    pub items: Vec<T>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubUser {
+    pub login: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubLabel {
+    pub name: String,
+}
+
+/// A highlighted fragment, returned with the `text-match` media type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextMatch {
+    pub fragment: String,
+}
+
+/// A repository search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubRepository {
+    pub full_name: String,
+    pub html_url: String,
+    pub description: Option<String>,
+    pub language: Option<String>,
+    #[serde(default)]
+    pub stargazers_count: u64,
+    #[serde(default)]
+    pub forks_count: u64,
+    #[serde(default)]
+    pub open_issues_count: u64,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub archived: bool,
+    pub created_at: Option<String>,
+    pub pushed_at: Option<String>,
+}
+
+/// The repository a code hit belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubRepositoryRef {
+    pub full_name: String,
+    pub html_url: String,
+}
+
+/// A code search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubCode {
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub html_url: String,
+    pub repository: GitHubRepositoryRef,
+    #[serde(default)]
+    pub text_matches: Vec<TextMatch>,
+}
+
+/// An issue or pull request search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubIssue {
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    /// API URL of the repository, e.g. `https://api.github.com/repos/owner/name`
+    pub repository_url: String,
+    pub state: String,
+    pub body: Option<String>,
+    pub user: Option<GitHubUser>,
+    #[serde(default)]
+    pub labels: Vec<GitHubLabel>,
+    #[serde(default)]
+    pub comments: u64,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub closed_at: Option<String>,
+    /// Present only for pull requests
+    pub pull_request: Option<serde_json::Value>,
+}
+
+fn github_result(
+    url: String,
+    title: String,
+    snippet: Option<String>,
+    published_date: Option<String>,
+    raw: serde_json::Value,
+) -> SearchResultType {
+    SearchResultType {
+        url,
+        title,
+        snippet: snippet.filter(|snippet| !snippet.is_empty()),
+        domain: Some("github.com".to_string()),
+        published_date,
+        provider: Some("github".to_string()),
+        raw: Some(raw),
+    }
+}
+
+/// Serialize `record` and tag it with the endpoint it came from
+fn raw_record(record: &impl Serialize, kind: GitHubSearchKind) -> serde_json::Value {
+    let mut raw = serde_json::to_value(record).unwrap_or_default();
+    raw["kind"] = kind.to_string().into();
+    raw
+}
+
+impl GitHubRepository {
+    /// Convert to the provider-independent result format
+    pub fn to_search_result(&self) -> SearchResultType {
+        github_result(
+            self.html_url.clone(),
+            self.full_name.clone(),
+            self.description.clone(),
+            self.pushed_at.clone(),
+            raw_record(self, GitHubSearchKind::Repositories),
+        )
+    }
+}
+
+impl GitHubCode {
+    /// Convert to the provider-independent result format
+    ///
+    /// The title is `owner/name:path`; the snippet is the first matching
+    /// fragment.
+    pub fn to_search_result(&self) -> SearchResultType {
+        github_result(
+            self.html_url.clone(),
+            format!("{}:{}", self.repository.full_name, self.path),
+            self.text_matches
+                .first()
+                .map(|text_match| text_match.fragment.trim().to_string()),
+            None,
+            raw_record(self, GitHubSearchKind::Code),
+        )
+    }
+}
+
+impl GitHubIssue {
+    /// `owner/name` taken from the repository API URL
+    pub fn repository(&self) -> Option<&str> {
+        let mut parts = self.repository_url.rsplitn(3, '/');
+        let _name = parts.next()?;
+        let _owner = parts.next()?;
+        let prefix = parts.next()?;
+        Some(&self.repository_url[prefix.len() + 1..])
+    }
+
+    pub fn is_pull_request(&self) -> bool {
+        self.pull_request.is_some()
+    }
+
+    /// Convert to the provider-independent result format
+    pub fn to_search_result(&self) -> SearchResultType {
+        let snippet = self
+            .body
+            .as_deref()
+            .map(|body| truncate_text(body.trim(), SNIPPET_LENGTH));
+
+        let mut raw = raw_record(self, GitHubSearchKind::Issues);
+        raw["repository"] = self.repository().into();
+        raw["is_pull_request"] = self.is_pull_request().into();
+
+        github_result(
+            self.html_url.clone(),
+            self.title.clone(),
+            snippet,
+            self.updated_at.clone(),
+            raw,
+        )
+    }
+}
+
+/// GitHub search provider
+#[derive(Debug)]
+pub struct GitHubProvider {
+    config: GitHubConfig,
+    http_client: HttpClient,
+}
+
+impl GitHubProvider {
+    pub fn new() -> Self {
+        Self::with_config(GitHubConfig::default())
+    }
+
+    /// Create a provider that authenticates with a personal access token
+    pub fn with_token(token: impl Into<String>) -> Self {
+        Self::with_config(GitHubConfig {
+            token: Some(token.into()),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_config(config: GitHubConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// Search repositories
+    pub async fn search_repositories(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<GitHubSearchPage<GitHubRepository>> {
+        self.search_page(GitHubSearchKind::Repositories, options)
+            .await
+    }
+
+    /// Search code; GitHub only allows this with a token
+    pub async fn search_code(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<GitHubSearchPage<GitHubCode>> {
+        if self.config.token.is_none() {
+            return Err(SearchError::AuthenticationError(
+                "GitHub code search requires a token".to_string(),
+            ));
+        }
+        self.search_page(GitHubSearchKind::Code, options).await
+    }
+
+    /// Search issues and pull requests
+    pub async fn search_issues(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<GitHubSearchPage<GitHubIssue>> {
+        self.search_page(GitHubSearchKind::Issues, options).await
+    }
+
+    async fn search_page<T: DeserializeOwned>(
+        &self,
+        kind: GitHubSearchKind,
+        options: &SearchOptions,
+    ) -> SearchResult<GitHubSearchPage<T>> {
+        self.config.validate()?;
+        let query = options.query.trim();
+        if query.is_empty() {
+            return Err(SearchError::InvalidInput(
+                "GitHub search requires a query".to_string(),
+            ));
+        }
+
+        let sort = match (&self.config.sort, &options.sort_by) {
+            (Some(sort), _) => Some(sort.as_str()),
+            (None, Some(sort_by)) => kind.sort_key(sort_by)?,
+            (None, None) => None,
+        };
+
+        let mut url = Url::parse(&format!(
+            "{}/{}",
+            self.config.base_url.trim_end_matches('/'),
+            kind.path()
+        ))?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs
+                .append_pair("q", query)
+                .append_pair(
+                    "per_page",
+                    &options
+                        .max_results
+                        .unwrap_or(10)
+                        .clamp(1, MAX_PAGE_SIZE)
+                        .to_string(),
+                )
+                .append_pair("page", &options.page.unwrap_or(1).max(1).to_string());
+            if let Some(sort) = sort {
+                pairs.append_pair("sort", sort);
+                if let Some(order) = &options.sort_order {
+                    pairs.append_pair(
+                        "order",
+                        match order {
+                            SortOrder::Ascending => "asc",
+                            SortOrder::Descending => "desc",
+                        },
+                    );
+                }
+            }
+        }
+
+        let mut headers = HashMap::new();
+        headers.insert(
+            "Accept".to_string(),
+            "application/vnd.github.text-match+json".to_string(),
+        );
+        headers.insert("X-GitHub-Api-Version".to_string(), "2022-11-28".to_string());
+        if let Some(token) = &self.config.token {
+            headers.insert("Authorization".to_string(), format!("Bearer {token}"));
+        }
+
+        let response = self
+            .http_client
+            .get_response_with_headers(url.as_str(), headers)
+            .await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let rate_limit = rate_limit_message(response.headers(), status.as_u16());
+        let body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|error| error["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| format!("Request failed with status: {status}"));
+
+        Err(match (status.as_u16(), rate_limit) {
+            (_, Some(rate_limit)) => SearchError::RateLimit(rate_limit),
+            (401 | 403, None) => SearchError::AuthenticationError(format!("GitHub: {message}")),
+            (422, None) => {
+                SearchError::InvalidInput(format!("GitHub rejected the query: {message}"))
+            }
+            (status_code, None) => SearchError::HttpError {
+                message: format!("GitHub: {message}"),
+                status_code: Some(status_code),
+                response_body: Some(body),
+            },
+        })
+    }
+}
+
+impl Default for GitHubProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Describe a primary or secondary rate limit, if the response is one
+///
+/// Primary limits report `x-ratelimit-remaining: 0` with the reset time as a
+/// Unix timestamp in `x-ratelimit-reset`; secondary limits send `retry-after`.
+fn rate_limit_message(headers: &HeaderMap, status: u16) -> Option<String> {
+    if !matches!(status, 403 | 429) {
+        return None;
+    }
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    if let Some(seconds) = header("retry-after").and_then(|value| value.parse::<u64>().ok()) {
+        return Some(format!(
+            "GitHub secondary rate limit reached; retry after {seconds}s"
+        ));
+    }
+    if header("x-ratelimit-remaining") == Some("0") {
+        let reset = header("x-ratelimit-reset")
+            .and_then(|value| value.parse::<i64>().ok())
+            .and_then(|timestamp| DateTime::<Utc>::from_timestamp(timestamp, 0));
+        return Some(match reset {
+            Some(reset) => format!(
+                "GitHub rate limit exhausted; resets at {} (in {}s)",
+                reset.to_rfc3339(),
+                (reset - Utc::now()).num_seconds().max(0)
+            ),
+            None => "GitHub rate limit exhausted".to_string(),
+        });
+    }
+    (status == 429).then(|| "GitHub rate limit reached".to_string())
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for GitHubProvider {
+    fn name(&self) -> &str {
+        "github"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let (results, total_count) = match self.config.kind {
+            GitHubSearchKind::Repositories => {
+                let page = self.search_repositories(options).await?;
+                let results = page.items.iter().map(GitHubRepository::to_search_result);
+                (results.collect(), page.total_count)
+            }
+            GitHubSearchKind::Code => {
+                let page = self.search_code(options).await?;
+                let results = page.items.iter().map(GitHubCode::to_search_result);
+                (results.collect(), page.total_count)
+            }
+            GitHubSearchKind::Issues => {
+                let page = self.search_issues(options).await?;
+                let results = page.items.iter().map(GitHubIssue::to_search_result);
+                (results.collect(), page.total_count)
+            }
+        };
+
+        Ok(SearchResponse {
+            results,
+            total_results: Some(total_count),
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "github".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config.insert("kind".to_string(), self.config.kind.to_string());
+        config.insert(
+            "authenticated".to_string(),
+            self.config.token.is_some().to_string(),
+        );
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_sort_mapping() {
+        let kind = GitHubSearchKind::Issues;
+        assert_eq!(kind.sort_key(&SortBy::Relevance).unwrap(), None);
+        assert_eq!(
+            kind.sort_key(&SortBy::SubmittedDate).unwrap(),
+            Some("created")
+        );
+        assert_eq!(
+            GitHubSearchKind::Repositories
+                .sort_key(&SortBy::LastUpdatedDate)
+                .unwrap(),
+            Some("updated")
+        );
+        assert!(GitHubSearchKind::Code
+            .sort_key(&SortBy::LastUpdatedDate)
+            .is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_message() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(rate_limit_message(&headers, 403), None);
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1700000000"));
+        let message = rate_limit_message(&headers, 403).unwrap();
+        assert!(message.contains("2023-11-14T22:13:20+00:00"), "{message}");
+
+        headers.insert("retry-after", HeaderValue::from_static("60"));
+        assert!(rate_limit_message(&headers, 403).unwrap().contains("60s"));
+        assert_eq!(rate_limit_message(&headers, 500), None);
+    }
+
+    #[test]
+    fn test_issue_to_search_result() {
+        let issue: GitHubIssue = serde_json::from_str(
+            r#"{
+                "number": 42,
+                "title": "Panic on empty input",
+                "html_url": "https://github.com/owner/name/issues/42",
+                "repository_url": "https://api.github.com/repos/owner/name",
+                "state": "open",
+                "body": "Steps to reproduce",
+                "user": {"login": "octocat"},
+                "labels": [{"name": "bug"}],
+                "comments": 3,
+                "updated_at": "2024-01-02T03:04:05Z",
+                "pull_request": {"url": "https://api.github.com/repos/owner/name/pulls/42"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(issue.repository(), Some("owner/name"));
+
+        let result = issue.to_search_result();
+        assert_eq!(result.snippet.as_deref(), Some("Steps to reproduce"));
+        let raw = result.raw.unwrap();
+        assert_eq!(raw["kind"], "issues");
+        assert_eq!(raw["repository"], "owner/name");
+        assert_eq!(raw["is_pull_request"], true);
+    }
+}
@@ -0,0 +1,482 @@
+//! Hacker News search provider
+//!
+//! Stories and comments through the HN Search API hosted by Algolia. See
+//! <https://hn.algolia.com/api>.
+
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SortBy,
+    },
+    utils::http::{extract_domain, html_to_text, truncate_text, HttpClient},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use url::Url;
+
+/// Largest page the API returns
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Longest story or comment text kept as a snippet
+const SNIPPET_LENGTH: usize = 300;
+
+/// Item types to search; several are OR'ed together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HackerNewsTag {
+    Story,
+    Comment,
+    AskHn,
+    ShowHn,
+    Poll,
+    /// Stories currently on the front page
+    FrontPage,
+}
+
+impl HackerNewsTag {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HackerNewsTag::Story => "story",
+            HackerNewsTag::Comment => "comment",
+            HackerNewsTag::AskHn => "ask_hn",
+            HackerNewsTag::ShowHn => "show_hn",
+            HackerNewsTag::Poll => "poll",
+            HackerNewsTag::FrontPage => "front_page",
+        }
+    }
+}
+
+impl fmt::Display for HackerNewsTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Hacker News configuration
+#[derive(Debug, Clone)]
+pub struct HackerNewsConfig {
+    /// API root
+    pub base_url: String,
+    /// Item types to include; empty searches everything
+    pub tags: Vec<HackerNewsTag>,
+    /// Only items by this user
+    pub author: Option<String>,
+    /// Newest first instead of by relevance; `SortBy::SubmittedDate` and
+    /// `SortBy::LastUpdatedDate` also select this
+    pub by_date: bool,
+    /// Only items with at least this many points
+    pub min_points: Option<u32>,
+    /// Only items with at least this many comments
+    pub min_comments: Option<u32>,
+    /// Only items created at or after this time
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only items created before this time
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+impl Default for HackerNewsConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://hn.algolia.com/api/v1".to_string(),
+            tags: vec![HackerNewsTag::Story],
+            author: None,
+            by_date: false,
+            min_points: None,
+            min_comments: None,
+            created_after: None,
+            created_before: None,
+        }
+    }
+}
+
+impl HackerNewsConfig {
+    /// The `tags` parameter: item types OR'ed, then AND'ed with the author
+    fn tags_param(&self) -> Option<String> {
+        let mut filters = Vec::new();
+        match self.tags.as_slice() {
+            [] => {}
+            [tag] => filters.push(tag.to_string()),
+            tags => filters.push(format!(
+                "({})",
+                tags.iter()
+                    .map(|tag| tag.as_str())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )),
+        }
+        if let Some(author) = &self.author {
+            filters.push(format!("author_{author}"));
+        }
+        (!filters.is_empty()).then(|| filters.join(","))
+    }
+
+    /// The `numericFilters` parameter
+    fn numeric_filters(&self) -> Option<String> {
+        let filters: Vec<String> = [
+            self.min_points.map(|points| format!("points>={points}")),
+            self.min_comments
+                .map(|comments| format!("num_comments>={comments}")),
+            self.created_after
+                .map(|after| format!("created_at_i>={}", after.timestamp())),
+            self.created_before
+                .map(|before| format!("created_at_i<{}", before.timestamp())),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        (!filters.is_empty()).then(|| filters.join(","))
+    }
+}
+
+impl ProviderConfig for HackerNewsConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        if let (Some(after), Some(before)) = (self.created_after, self.created_before) {
+            if after >= before {
+                return Err(SearchError::ConfigError(
+                    "created_after must be earlier than created_before".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+/// A story or comment search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HackerNewsHit {
+    #[serde(rename = "objectID")]
+    pub object_id: String,
+    /// Story title; comments carry theirs in `story_title`
+    pub title: Option<String>,
+    /// Link the story points to; absent for Ask HN and text posts
+    pub url: Option<String>,
+    pub author: Option<String>,
+    pub points: Option<i64>,
+    pub num_comments: Option<u64>,
+    /// Unix timestamp
+    pub created_at_i: i64,
+    pub story_text: Option<String>,
+    pub comment_text: Option<String>,
+    /// For comments, the story they belong to
+    pub story_id: Option<u64>,
+    pub story_title: Option<String>,
+    pub story_url: Option<String>,
+    #[serde(rename = "_tags", default)]
+    pub tags: Vec<String>,
+}
+
+impl HackerNewsHit {
+    pub fn is_comment(&self) -> bool {
+        self.comment_text.is_some() || self.tags.iter().any(|tag| tag == "comment")
+    }
+
+    /// The item's page on news.ycombinator.com
+    pub fn discussion_url(&self) -> String {
+        format!("https://news.ycombinator.com/item?id={}", self.object_id)
+    }
+
+    /// Convert to the provider-independent result format
+    ///
+    /// Stories link to their target URL (or the discussion for text posts);
+    /// comments link to the comment itself. `raw` carries `points`,
+    /// `num_comments`, `discussion_url` and `story_url`.
+    pub fn to_search_result(&self) -> SearchResultType {
+        let story_url = match self.is_comment() {
+            true => self.story_url.clone(),
+            false => self.url.clone(),
+        }
+        .filter(|url| !url.is_empty());
+        let discussion_url = self.discussion_url();
+
+        let url = match (self.is_comment(), &story_url) {
+            (false, Some(url)) => url.clone(),
+            _ => discussion_url.clone(),
+        };
+        let title = match self.is_comment() {
+            true => format!(
+                "Comment on: {}",
+                self.story_title.as_deref().unwrap_or("(deleted story)")
+            ),
+            false => self.title.clone().unwrap_or_default(),
+        };
+        let snippet = self
+            .comment_text
+            .as_deref()
+            .or(self.story_text.as_deref())
+            .map(html_to_text)
+            .map(|text| truncate_text(&text, SNIPPET_LENGTH))
+            .filter(|text| !text.is_empty());
+
+        let raw = serde_json::json!({
+            "object_id": self.object_id,
+            "author": self.author,
+            "points": self.points,
+            "num_comments": self.num_comments,
+            "discussion_url": discussion_url,
+            "story_url": story_url,
+            "story_id": self.story_id,
+            "tags": self.tags,
+        });
+
+        SearchResultType {
+            domain: extract_domain(&url),
+            url,
+            title,
+            snippet,
+            published_date: DateTime::from_timestamp(self.created_at_i, 0)
+                .map(|date| date.format("%Y-%m-%d").to_string()),
+            provider: Some("hackernews".to_string()),
+            raw: Some(raw),
+        }
+    }
+}
+
+/// One page of hits
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HackerNewsPage {
+    #[serde(default)]
+    pub hits: Vec<HackerNewsHit>,
+    #[serde(default)]
+    pub nb_hits: u64,
+    /// Zero-based
+    #[serde(default)]
+    pub page: u32,
+    #[serde(default)]
+    pub nb_pages: u32,
+}
+
+/// Hacker News search provider
+#[derive(Debug)]
+pub struct HackerNewsProvider {
+    config: HackerNewsConfig,
+    http_client: HttpClient,
+}
+
+impl HackerNewsProvider {
+    pub fn new() -> Self {
+        Self::with_config(HackerNewsConfig::default())
+    }
+
+    pub fn with_config(config: HackerNewsConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// Search and return the typed page
+    ///
+    /// `options.page` is one-based, like the other providers.
+    pub async fn search_hits(&self, options: &SearchOptions) -> SearchResult<HackerNewsPage> {
+        self.config.validate()?;
+
+        let by_date = self.config.by_date
+            || matches!(
+                options.sort_by,
+                Some(SortBy::SubmittedDate | SortBy::LastUpdatedDate)
+            );
+        let endpoint = if by_date { "search_by_date" } else { "search" };
+
+        let mut url = Url::parse(&format!(
+            "{}/{endpoint}",
+            self.config.base_url.trim_end_matches('/')
+        ))?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs
+                .append_pair("query", options.query.trim())
+                .append_pair(
+                    "hitsPerPage",
+                    &options
+                        .max_results
+                        .unwrap_or(10)
+                        .clamp(1, MAX_PAGE_SIZE)
+                        .to_string(),
+                )
+                .append_pair(
+                    "page",
+                    &options.page.unwrap_or(1).saturating_sub(1).to_string(),
+                );
+            if let Some(tags) = self.config.tags_param() {
+                pairs.append_pair("tags", &tags);
+            }
+            if let Some(filters) = self.config.numeric_filters() {
+                pairs.append_pair("numericFilters", &filters);
+            }
+        }
+
+        self.http_client
+            .get_json(url.as_str())
+            .await
+            .map_err(|error| match error {
+                SearchError::HttpError {
+                    status_code: Some(429),
+                    ..
+                } => SearchError::RateLimit(
+                    "HN Search allows 10,000 requests per hour per IP".to_string(),
+                ),
+                SearchError::HttpError {
+                    status_code: Some(400),
+                    response_body,
+                    ..
+                } => SearchError::InvalidInput(format!(
+                    "HN Search rejected the request: {}",
+                    response_body.unwrap_or_default()
+                )),
+                other => other,
+            })
+    }
+}
+
+impl Default for HackerNewsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for HackerNewsProvider {
+    fn name(&self) -> &str {
+        "hackernews"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let page = self.search_hits(options).await?;
+        Ok(SearchResponse {
+            results: page
+                .hits
+                .iter()
+                .map(HackerNewsHit::to_search_result)
+                .collect(),
+            total_results: Some(page.nb_hits),
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "hackernews".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        if let Some(tags) = self.config.tags_param() {
+            config.insert("tags".to_string(), tags);
+        }
+        if let Some(filters) = self.config.numeric_filters() {
+            config.insert("numeric_filters".to_string(), filters);
+        }
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_filter_params() {
+        let config = HackerNewsConfig {
+            tags: vec![HackerNewsTag::Story, HackerNewsTag::Poll],
+            author: Some("pg".to_string()),
+            min_points: Some(100),
+            created_after: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.tags_param().as_deref(),
+            Some("(story,poll),author_pg")
+        );
+        assert_eq!(
+            config.numeric_filters().as_deref(),
+            Some("points>=100,created_at_i>=1704067200")
+        );
+
+        let everything = HackerNewsConfig {
+            tags: Vec::new(),
+            ..Default::default()
+        };
+        assert_eq!(everything.tags_param(), None);
+        assert_eq!(everything.numeric_filters(), None);
+    }
+
+    #[test]
+    fn test_hits_to_search_results() {
+        let page: HackerNewsPage = serde_json::from_str(
+            r#"{
+                "hits": [
+                    {
+                        "objectID": "1",
+                        "title": "Show HN: A search CLI",
+                        "url": "https://example.com/cli",
+                        "author": "alice",
+                        "points": 321,
+                        "num_comments": 45,
+                        "created_at_i": 1704067200,
+                        "_tags": ["story", "author_alice", "story_1", "show_hn"]
+                    },
+                    {
+                        "objectID": "2",
+                        "title": "Ask HN: What are you reading?",
+                        "url": null,
+                        "points": 12,
+                        "created_at_i": 1704067200,
+                        "story_text": "<p>Books &amp; papers",
+                        "_tags": ["story", "ask_hn"]
+                    },
+                    {
+                        "objectID": "3",
+                        "comment_text": "Nice work<p>Second paragraph",
+                        "story_id": 1,
+                        "story_title": "Show HN: A search CLI",
+                        "story_url": "https://example.com/cli",
+                        "created_at_i": 1704067200,
+                        "_tags": ["comment", "story_1"]
+                    }
+                ],
+                "nbHits": 3, "page": 0, "nbPages": 1
+            }"#,
+        )
+        .unwrap();
+        let results: Vec<_> = page
+            .hits
+            .iter()
+            .map(HackerNewsHit::to_search_result)
+            .collect();
+
+        assert_eq!(results[0].url, "https://example.com/cli");
+        let raw = results[0].raw.as_ref().unwrap();
+        assert_eq!(raw["points"], 321);
+        assert_eq!(raw["num_comments"], 45);
+        assert_eq!(
+            raw["discussion_url"],
+            "https://news.ycombinator.com/item?id=1"
+        );
+
+        assert_eq!(results[1].url, "https://news.ycombinator.com/item?id=2");
+        assert_eq!(results[1].snippet.as_deref(), Some("Books & papers"));
+
+        assert_eq!(results[2].title, "Comment on: Show HN: A search CLI");
+        assert_eq!(results[2].url, "https://news.ycombinator.com/item?id=3");
+        assert_eq!(
+            results[2].snippet.as_deref(),
+            Some("Nice work Second paragraph")
+        );
+        assert_eq!(
+            results[2].raw.as_ref().unwrap()["story_url"],
+            "https://example.com/cli"
+        );
+    }
+}
@@ -0,0 +1,690 @@
+//! Local full-text index provider
+//!
+//! Indexes a directory of Markdown, text and HTML files into an inverted
+//! index stored as JSON next to the documents, and ranks matches with BM25.
+//! Searches run offline through the same [`SearchProvider`] API as the web
+//! providers.
+//!
+//! ```rust,no_run
+//! use websearch::providers::local_index::LocalIndexProvider;
+//! use websearch::{web_search, types::SearchOptions};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // Loads ./docs/.websearch-index.json, rebuilding it if any file changed
+//! let provider = LocalIndexProvider::open("./docs")?;
+//! let results = web_search(SearchOptions {
+//!     query: "deploy rollback".to_string(),
+//!     provider: Box::new(provider),
+//!     ..Default::default()
+//! })
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType,
+    },
+    utils::http::{html_to_text, normalize_text},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use url::Url;
+
+/// File name of the index inside the indexed directory
+pub const INDEX_FILE_NAME: &str = ".websearch-index.json";
+
+/// Bumped whenever the on-disk format or tokenizer changes
+const INDEX_VERSION: u32 = 2;
+
+/// Local index configuration
+#[derive(Debug, Clone)]
+pub struct LocalIndexConfig {
+    /// Directory to index, searched recursively; hidden entries are skipped
+    pub root: PathBuf,
+    /// Where the index is stored; defaults to [`INDEX_FILE_NAME`] in `root`
+    pub index_path: Option<PathBuf>,
+    /// File extensions to index, without the dot
+    pub extensions: Vec<String>,
+    /// BM25 term-frequency saturation
+    pub k1: f64,
+    /// BM25 length normalization
+    pub b: f64,
+    /// Approximate snippet length in characters
+    pub snippet_length: usize,
+    /// Markers placed around matched terms in snippets
+    pub highlight: (String, String),
+}
+
+impl Default for LocalIndexConfig {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("."),
+            index_path: None,
+            extensions: ["md", "markdown", "txt", "html", "htm"]
+                .map(str::to_string)
+                .to_vec(),
+            k1: 1.2,
+            b: 0.75,
+            snippet_length: 200,
+            highlight: ("**".to_string(), "**".to_string()),
+        }
+    }
+}
+
+impl LocalIndexConfig {
+    pub fn index_path(&self) -> PathBuf {
+        self.index_path
+            .clone()
+            .unwrap_or_else(|| self.root.join(INDEX_FILE_NAME))
+    }
+}
+
+impl ProviderConfig for LocalIndexConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if !self.root.is_dir() {
+            return Err(SearchError::ConfigError(format!(
+                "Index root is not a directory: {}",
+                self.root.display()
+            )));
+        }
+        if self.extensions.is_empty() {
+            return Err(SearchError::ConfigError(
+                "At least one file extension is required".to_string(),
+            ));
+        }
+        if self.k1 < 0.0 || !(0.0..=1.0).contains(&self.b) {
+            return Err(SearchError::ConfigError(
+                "BM25 parameters must satisfy k1 >= 0 and 0 <= b <= 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        self.root.to_str().unwrap_or_default()
+    }
+}
+
+/// A document in the index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedDocument {
+    /// Path relative to the index root, with `/` separators
+    pub path: String,
+    pub title: String,
+    /// Number of tokens
+    pub length: u32,
+    /// File size and modification time, used to detect changes
+    pub size: u64,
+    pub modified: i64,
+}
+
+/// Occurrences of a term in one document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Posting {
+    doc: u32,
+    tf: u32,
+}
+
+/// Inverted index over a directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalIndex {
+    version: u32,
+    documents: Vec<IndexedDocument>,
+    postings: BTreeMap<String, Vec<Posting>>,
+    total_length: u64,
+    /// Size and modification time of files left out because they aren't
+    /// UTF-8, so they don't make the index look stale
+    skipped: BTreeMap<String, (u64, i64)>,
+}
+
+/// A ranked match
+#[derive(Debug, Clone)]
+pub struct ScoredDocument<'a> {
+    pub document: &'a IndexedDocument,
+    pub score: f64,
+    /// Query terms found in the document
+    pub matched_terms: Vec<String>,
+}
+
+/// Lowercased alphanumeric runs, skipping single characters
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| token.chars().count() > 1)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Plain text and title of a file, by extension
+fn extract(path: &Path, contents: &str) -> (String, Option<String>) {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    match extension.as_str() {
+        "html" | "htm" => {
+            let document = scraper::Html::parse_document(contents);
+            let title = ["title", "h1"].iter().find_map(|tag| {
+                let selector = scraper::Selector::parse(tag).ok()?;
+                let element = document.select(&selector).next()?;
+                Some(normalize_text(&element.text().collect::<String>()))
+                    .filter(|title| !title.is_empty())
+            });
+            (html_to_text(contents), title)
+        }
+        "md" | "markdown" => {
+            let title = contents.lines().find_map(|line| {
+                line.strip_prefix("# ")
+                    .map(|title| title.trim().to_string())
+                    .filter(|title| !title.is_empty())
+            });
+            (markdown_to_text(contents), title)
+        }
+        _ => {
+            let title = contents
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string);
+            (normalize_text(contents), title)
+        }
+    }
+}
+
+/// Drop the Markdown syntax that would otherwise show up in snippets
+///
+/// Link targets are removed (`[text](url)` becomes `text`); emphasis,
+/// heading and quote markers become spaces.
+fn markdown_to_text(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    let mut chars = markdown.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ']' if chars.peek() == Some(&'(') => {
+                for c in chars.by_ref() {
+                    if c == ')' {
+                        break;
+                    }
+                }
+            }
+            '#' | '*' | '_' | '`' | '>' | '[' | ']' | '|' => text.push(' '),
+            '!' if chars.peek() == Some(&'[') => {}
+            c => text.push(c),
+        }
+    }
+    normalize_text(&text)
+}
+
+fn modified_secs(metadata: &fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Indexable files under `root`, sorted, as (relative path, absolute path)
+fn collect_files(config: &LocalIndexConfig) -> SearchResult<Vec<(String, PathBuf)>> {
+    let index_path = config.index_path();
+    let mut files = Vec::new();
+    let mut pending = vec![config.root.clone()];
+
+    while let Some(directory) = pending.pop() {
+        for entry in fs::read_dir(&directory)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') || path == index_path {
+                continue;
+            }
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if file_type.is_file()
+                && path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| {
+                        config
+                            .extensions
+                            .iter()
+                            .any(|allowed| allowed.eq_ignore_ascii_case(extension))
+                    })
+            {
+                let relative = path
+                    .strip_prefix(&config.root)
+                    .unwrap_or(&path)
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((relative, path));
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+impl LocalIndex {
+    /// Index every matching file under `config.root`
+    pub fn build(config: &LocalIndexConfig) -> SearchResult<Self> {
+        config.validate()?;
+        let mut index = LocalIndex {
+            version: INDEX_VERSION,
+            documents: Vec::new(),
+            postings: BTreeMap::new(),
+            total_length: 0,
+            skipped: BTreeMap::new(),
+        };
+
+        for (relative, path) in collect_files(config)? {
+            let metadata = fs::metadata(&path)?;
+            // Skip files that aren't UTF-8 rather than failing the whole build
+            let Ok(contents) = fs::read_to_string(&path) else {
+                log::warn!("Skipping non-UTF-8 file {}", path.display());
+                index
+                    .skipped
+                    .insert(relative, (metadata.len(), modified_secs(&metadata)));
+                continue;
+            };
+            let (text, title) = extract(&path, &contents);
+            let tokens = tokenize(&text);
+
+            let doc = index.documents.len() as u32;
+            let mut frequencies: HashMap<String, u32> = HashMap::new();
+            for token in &tokens {
+                *frequencies.entry(token.clone()).or_default() += 1;
+            }
+            for (term, tf) in frequencies {
+                index
+                    .postings
+                    .entry(term)
+                    .or_default()
+                    .push(Posting { doc, tf });
+            }
+
+            index.total_length += tokens.len() as u64;
+            index.documents.push(IndexedDocument {
+                title: title.unwrap_or_else(|| {
+                    path.file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                        .unwrap_or_else(|| relative.clone())
+                }),
+                path: relative,
+                length: tokens.len() as u32,
+                size: metadata.len(),
+                modified: modified_secs(&metadata),
+            });
+        }
+
+        Ok(index)
+    }
+
+    /// Read an index written by [`LocalIndex::save`]
+    pub fn load(path: &Path) -> SearchResult<Self> {
+        let index: LocalIndex = serde_json::from_slice(&fs::read(path)?)?;
+        if index.version != INDEX_VERSION {
+            return Err(SearchError::ParseError(format!(
+                "Index {} has format version {}, expected {INDEX_VERSION}",
+                path.display(),
+                index.version
+            )));
+        }
+        Ok(index)
+    }
+
+    /// Write the index atomically
+    pub fn save(&self, path: &Path) -> SearchResult<()> {
+        let partial = path.with_extension("json.part");
+        fs::write(&partial, serde_json::to_vec(self)?)?;
+        fs::rename(&partial, path)?;
+        Ok(())
+    }
+
+    /// Whether files were added, removed or modified since the index was built
+    pub fn is_stale(&self, config: &LocalIndexConfig) -> SearchResult<bool> {
+        let files = collect_files(config)?;
+        if files.len() != self.documents.len() + self.skipped.len() {
+            return Ok(true);
+        }
+        let stamps: HashMap<&str, (u64, i64)> = self
+            .documents
+            .iter()
+            .map(|document| (document.path.as_str(), (document.size, document.modified)))
+            .chain(
+                self.skipped
+                    .iter()
+                    .map(|(path, stamp)| (path.as_str(), *stamp)),
+            )
+            .collect();
+        for (relative, path) in &files {
+            let Some(&(size, modified)) = stamps.get(relative.as_str()) else {
+                return Ok(true);
+            };
+            let metadata = fs::metadata(path)?;
+            if metadata.len() != size || modified_secs(&metadata) != modified {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    pub fn documents(&self) -> &[IndexedDocument] {
+        &self.documents
+    }
+
+    /// Rank documents containing any query term with BM25
+    pub fn search(&self, query: &str, k1: f64, b: f64) -> Vec<ScoredDocument<'_>> {
+        let terms: Vec<String> = {
+            let mut seen = HashSet::new();
+            tokenize(query)
+                .into_iter()
+                .filter(|term| seen.insert(term.clone()))
+                .collect()
+        };
+        if terms.is_empty() || self.documents.is_empty() {
+            return Vec::new();
+        }
+
+        let count = self.documents.len() as f64;
+        let average_length = (self.total_length as f64 / count).max(1.0);
+        let mut scores: HashMap<u32, (f64, Vec<String>)> = HashMap::new();
+
+        for term in &terms {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let df = postings.len() as f64;
+            let idf = (1.0 + (count - df + 0.5) / (df + 0.5)).ln();
+            for posting in postings {
+                let length = self.documents[posting.doc as usize].length as f64;
+                let tf = posting.tf as f64;
+                let weight =
+                    idf * tf * (k1 + 1.0) / (tf + k1 * (1.0 - b + b * length / average_length));
+                let entry = scores.entry(posting.doc).or_default();
+                entry.0 += weight;
+                entry.1.push(term.clone());
+            }
+        }
+
+        let mut ranked: Vec<ScoredDocument<'_>> = scores
+            .into_iter()
+            .map(|(doc, (score, matched_terms))| ScoredDocument {
+                document: &self.documents[doc as usize],
+                score,
+                matched_terms,
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.document.path.cmp(&b.document.path))
+        });
+        ranked
+    }
+}
+
+/// A window of `text` around the first matched term, with matches wrapped in
+/// the highlight markers
+pub fn snippet(
+    text: &str,
+    terms: &[String],
+    length: usize,
+    highlight: &(String, String),
+) -> String {
+    let is_term = |word: &str| terms.iter().any(|term| word.to_lowercase() == *term);
+
+    // Word boundaries as byte ranges
+    let mut words = Vec::new();
+    let mut start = None;
+    for (index, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(begin)) => {
+                words.push(begin..index);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(begin) = start {
+        words.push(begin..text.len());
+    }
+
+    let first_match = words
+        .iter()
+        .find(|range| is_term(&text[(*range).clone()]))
+        .map(|range| range.start)
+        .unwrap_or(0);
+    // Start a few words before the match so it has some context
+    let context = length / 4;
+    let window_start = words
+        .iter()
+        .map(|range| range.start)
+        .filter(|&start| start <= first_match && first_match - start <= context)
+        .min()
+        .unwrap_or(first_match);
+    let window_end = words
+        .iter()
+        .map(|range| range.end)
+        .filter(|&end| end > window_start && end - window_start <= length)
+        .max()
+        .unwrap_or(text.len());
+    // Keep trailing punctuation when the rest of the text fits
+    let window_end = if text.len() - window_start <= length {
+        text.len()
+    } else {
+        window_end
+    };
+
+    let mut snippet = String::new();
+    if window_start > 0 {
+        snippet.push_str("...");
+    }
+    let mut cursor = window_start;
+    for range in words
+        .iter()
+        .filter(|range| range.start >= window_start && range.end <= window_end)
+    {
+        let word = &text[range.clone()];
+        if is_term(word) {
+            snippet.push_str(&text[cursor..range.start]);
+            snippet.push_str(&highlight.0);
+            snippet.push_str(word);
+            snippet.push_str(&highlight.1);
+            cursor = range.end;
+        }
+    }
+    snippet.push_str(&text[cursor..window_end]);
+    if window_end < text.len() {
+        snippet.push_str("...");
+    }
+    snippet
+}
+
+/// Search provider over a [`LocalIndex`]
+#[derive(Debug)]
+pub struct LocalIndexProvider {
+    config: LocalIndexConfig,
+    index: LocalIndex,
+}
+
+impl LocalIndexProvider {
+    /// Open the index for `root` with default settings
+    pub fn open(root: impl Into<PathBuf>) -> SearchResult<Self> {
+        Self::with_config(LocalIndexConfig {
+            root: root.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Load the stored index, rebuilding and saving it when it is missing,
+    /// unreadable or out of date
+    ///
+    /// A read-only root still works: if the rebuilt index can't be saved, it
+    /// is kept in memory and rebuilt again next time.
+    pub fn with_config(config: LocalIndexConfig) -> SearchResult<Self> {
+        config.validate()?;
+        let index_path = config.index_path();
+        let index = match LocalIndex::load(&index_path) {
+            // A file vanishing mid-check means the index is out of date too
+            Ok(index) if !index.is_stale(&config).unwrap_or(true) => index,
+            _ => {
+                let index = LocalIndex::build(&config)?;
+                if let Err(error) = index.save(&index_path) {
+                    log::warn!(
+                        "Could not save the local index to {}: {error}",
+                        index_path.display()
+                    );
+                }
+                index
+            }
+        };
+        Ok(Self { config, index })
+    }
+
+    /// Rebuild the index from the files on disk and save it
+    pub fn reindex(&mut self) -> SearchResult<()> {
+        self.index = LocalIndex::build(&self.config)?;
+        self.index.save(&self.config.index_path())
+    }
+
+    pub fn index(&self) -> &LocalIndex {
+        &self.index
+    }
+
+    async fn to_search_result(&self, scored: &ScoredDocument<'_>) -> SearchResultType {
+        let path = self.config.root.join(&scored.document.path);
+        let path = path.canonicalize().unwrap_or(path);
+        // The file may have changed since indexing; fall back to no snippet
+        let snippet = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                let (text, _) = extract(&path, &contents);
+                Some(snippet(
+                    &text,
+                    &scored.matched_terms,
+                    self.config.snippet_length,
+                    &self.config.highlight,
+                ))
+            }
+            Err(_) => None,
+        };
+
+        SearchResultType {
+            url: Url::from_file_path(&path)
+                .map(String::from)
+                .unwrap_or_else(|_| path.display().to_string()),
+            title: scored.document.title.clone(),
+            snippet,
+            domain: None,
+            published_date: DateTime::<Utc>::from_timestamp(scored.document.modified, 0)
+                .map(|date| date.format("%Y-%m-%d").to_string()),
+            provider: Some("local".to_string()),
+            raw: Some(serde_json::json!({
+                "path": scored.document.path,
+                "score": scored.score,
+                "matched_terms": scored.matched_terms,
+            })),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for LocalIndexProvider {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        if tokenize(&options.query).is_empty() {
+            return Err(SearchError::InvalidInput(
+                "Local search requires a query with at least one word".to_string(),
+            ));
+        }
+
+        let ranked = self
+            .index
+            .search(&options.query, self.config.k1, self.config.b);
+        let per_page = options.max_results.unwrap_or(10).max(1) as usize;
+        let skip = options.page.unwrap_or(1).saturating_sub(1) as usize * per_page;
+
+        let mut results = Vec::new();
+        for scored in ranked.iter().skip(skip).take(per_page) {
+            results.push(self.to_search_result(scored).await);
+        }
+
+        Ok(SearchResponse {
+            results,
+            total_results: Some(ranked.len() as u64),
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "local".to_string());
+        config.insert("root".to_string(), self.config.root.display().to_string());
+        config.insert(
+            "documents".to_string(),
+            self.index.documents.len().to_string(),
+        );
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("Deploy the API-server (v2), a B test!"),
+            ["deploy", "the", "api", "server", "v2", "test"]
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_text() {
+        assert_eq!(
+            markdown_to_text(
+                "# Title\n\nSee [the guide](https://example.com) and **bold** `code`."
+            ),
+            "Title See the guide and bold code ."
+        );
+    }
+
+    #[test]
+    fn test_snippet_highlights_terms() {
+        let text = "Intro text. To roll back a deploy, run the rollback script and deploy again.";
+        let terms = vec!["rollback".to_string(), "deploy".to_string()];
+        let highlight = ("**".to_string(), "**".to_string());
+
+        assert_eq!(
+            snippet(text, &terms, 200, &highlight),
+            "Intro text. To roll back a **deploy**, run the **rollback** script and **deploy** again."
+        );
+        let short = snippet(text, &terms, 30, &highlight);
+        assert!(
+            short.starts_with("...") && short.ends_with("..."),
+            "{short}"
+        );
+        assert!(short.contains("**deploy**"), "{short}");
+    }
+}
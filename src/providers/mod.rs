@@ -1,8 +1,30 @@
 //! Search provider implementations
 
 pub mod arxiv;
+pub mod crossref;
 pub mod duckduckgo;
+pub mod elasticsearch;
+pub mod github;
+pub mod hackernews;
+pub mod local_index;
+pub mod openalex;
+pub mod packages;
+pub mod pubmed;
+pub mod semantic_scholar;
+pub mod stackexchange;
 
 // Re-export providers for convenience
-pub use arxiv::ArxivProvider;
+pub use arxiv::{
+    ArxivConfig, ArxivFeedProvider, ArxivId, ArxivPaper, ArxivProvider, ArxivQuery,
+};
+pub use crossref::CrossrefProvider;
 pub use duckduckgo::DuckDuckGoProvider;
+pub use elasticsearch::ElasticsearchProvider;
+pub use github::GitHubProvider;
+pub use hackernews::HackerNewsProvider;
+pub use local_index::LocalIndexProvider;
+pub use openalex::OpenAlexProvider;
+pub use packages::{CratesIoProvider, NpmProvider, PyPiProvider};
+pub use pubmed::PubMedProvider;
+pub use semantic_scholar::SemanticScholarProvider;
+pub use stackexchange::StackExchangeProvider;
@@ -0,0 +1,550 @@
+//! OpenAlex works provider
+//!
+//! Searches OpenAlex's `works` endpoint with its `filter` syntax and cursor
+//! paging. See <https://docs.openalex.org/>.
+
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SortBy, SortOrder, SuggestProvider,
+    },
+    utils::http::{normalize_text, HttpClient},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// Largest page OpenAlex returns
+pub const MAX_PER_PAGE: u32 = 200;
+
+/// OpenAlex configuration
+#[derive(Debug, Clone)]
+pub struct OpenAlexConfig {
+    /// API root
+    pub base_url: String,
+    /// Contact address; identifies the client and selects the polite pool
+    pub mailto: Option<String>,
+    /// Filter applied to every search, in OpenAlex syntax, e.g.
+    /// `publication_year:>2019,is_oa:true`
+    pub filter: Option<String>,
+}
+
+impl Default for OpenAlexConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.openalex.org".to_string(),
+            mailto: None,
+            filter: None,
+        }
+    }
+}
+
+impl ProviderConfig for OpenAlexConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+/// A work record from OpenAlex
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenAlexWork {
+    /// OpenAlex ID, e.g. `https://openalex.org/W2741809807`
+    pub id: String,
+    /// DOI as a `https://doi.org/` URL
+    pub doi: Option<String>,
+    pub display_name: Option<String>,
+    pub publication_year: Option<i32>,
+    pub publication_date: Option<String>,
+    #[serde(rename = "type")]
+    pub work_type: Option<String>,
+    #[serde(default)]
+    pub authorships: Vec<OpenAlexAuthorship>,
+    pub primary_location: Option<OpenAlexLocation>,
+    pub open_access: Option<OpenAlexOpenAccess>,
+    pub cited_by_count: Option<u64>,
+    /// Abstract as word → positions, as OpenAlex distributes it
+    pub abstract_inverted_index: Option<HashMap<String, Vec<usize>>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenAlexAuthorship {
+    pub author: OpenAlexAuthor,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenAlexAuthor {
+    pub id: Option<String>,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenAlexLocation {
+    pub landing_page_url: Option<String>,
+    pub pdf_url: Option<String>,
+    pub source: Option<OpenAlexSource>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenAlexSource {
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OpenAlexOpenAccess {
+    #[serde(default)]
+    pub is_oa: bool,
+    pub oa_status: Option<String>,
+    pub oa_url: Option<String>,
+}
+
+impl OpenAlexWork {
+    /// Bare DOI, without the `https://doi.org/` prefix
+    pub fn bare_doi(&self) -> Option<&str> {
+        let doi = self.doi.as_deref()?;
+        Some(doi.strip_prefix("https://doi.org/").unwrap_or(doi))
+    }
+
+    pub fn venue(&self) -> Option<&str> {
+        self.primary_location
+            .as_ref()?
+            .source
+            .as_ref()?
+            .display_name
+            .as_deref()
+    }
+
+    /// Rebuild the abstract from its inverted index
+    pub fn abstract_text(&self) -> Option<String> {
+        let index = self.abstract_inverted_index.as_ref()?;
+        let mut positioned: Vec<(usize, &str)> = index
+            .iter()
+            .flat_map(|(word, positions)| {
+                positions
+                    .iter()
+                    .map(move |&position| (position, word.as_str()))
+            })
+            .collect();
+        if positioned.is_empty() {
+            return None;
+        }
+        positioned.sort_unstable_by_key(|(position, _)| *position);
+        Some(
+            positioned
+                .into_iter()
+                .map(|(_, word)| word)
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    /// Convert to the provider-independent result format
+    pub fn to_search_result(&self) -> SearchResultType {
+        let url = self
+            .primary_location
+            .as_ref()
+            .and_then(|location| location.landing_page_url.clone())
+            .or_else(|| self.doi.clone())
+            .unwrap_or_else(|| self.id.clone());
+        let open_access_url = self
+            .open_access
+            .as_ref()
+            .and_then(|open_access| open_access.oa_url.clone())
+            .or_else(|| {
+                self.primary_location
+                    .as_ref()
+                    .and_then(|location| location.pdf_url.clone())
+            });
+
+        let mut raw = serde_json::json!({
+            "openalex_id": self.id,
+            "authors": self
+                .authorships
+                .iter()
+                .filter_map(|authorship| authorship.author.display_name.as_deref())
+                .collect::<Vec<_>>(),
+            "is_open_access": self.open_access.as_ref().is_some_and(|open_access| open_access.is_oa),
+            "cited_by_count": self.cited_by_count,
+            "work": self,
+        });
+        if let Some(doi) = self.bare_doi() {
+            raw["doi"] = doi.into();
+        }
+        if let Some(venue) = self.venue() {
+            raw["venue"] = venue.into();
+        }
+        if let Some(year) = self.publication_year {
+            raw["year"] = year.into();
+        }
+        if let Some(open_access_url) = open_access_url {
+            raw["open_access_url"] = open_access_url.into();
+        }
+
+        SearchResultType {
+            domain: crate::utils::http::extract_domain(&url),
+            url,
+            title: self.display_name.clone().unwrap_or_default(),
+            snippet: self
+                .abstract_text()
+                .or_else(|| self.venue().map(str::to_string)),
+            published_date: self
+                .publication_date
+                .clone()
+                .or_else(|| self.publication_year.map(|year| year.to_string())),
+            provider: Some("openalex".to_string()),
+            raw: Some(raw),
+        }
+    }
+}
+
+/// One page of works, with the cursor for the next page
+#[derive(Debug, Clone, Default)]
+pub struct OpenAlexPage {
+    pub works: Vec<OpenAlexWork>,
+    /// Cursor for the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+    pub total_results: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorksResponse {
+    meta: WorksMeta,
+    #[serde(default)]
+    results: Vec<OpenAlexWork>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorksMeta {
+    count: Option<u64>,
+    next_cursor: Option<String>,
+}
+
+/// OpenAlex search provider
+#[derive(Debug)]
+pub struct OpenAlexProvider {
+    config: OpenAlexConfig,
+    http_client: HttpClient,
+}
+
+impl OpenAlexProvider {
+    pub fn new() -> Self {
+        Self::with_config(OpenAlexConfig::default())
+    }
+
+    /// Create a provider in the polite pool
+    pub fn with_mailto(mailto: impl Into<String>) -> Self {
+        Self::with_config(OpenAlexConfig {
+            mailto: Some(mailto.into()),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_config(config: OpenAlexConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// Fetch one page of works; pass `"*"` as the cursor for the first page
+    pub async fn search_page(
+        &self,
+        options: &SearchOptions,
+        cursor: &str,
+    ) -> SearchResult<OpenAlexPage> {
+        self.fetch_works(options, ("cursor", cursor)).await
+    }
+
+    /// Fetch works positioned by either a `cursor` or a basic `page` parameter
+    ///
+    /// OpenAlex rejects requests that send both.
+    async fn fetch_works(
+        &self,
+        options: &SearchOptions,
+        (paging_key, paging_value): (&str, &str),
+    ) -> SearchResult<OpenAlexPage> {
+        self.config.validate()?;
+        let query = options.query.trim();
+        if query.is_empty() && self.config.filter.is_none() {
+            return Err(SearchError::InvalidInput(
+                "OpenAlex search requires a query or a filter".to_string(),
+            ));
+        }
+
+        let mut url = Url::parse(&format!(
+            "{}/works",
+            self.config.base_url.trim_end_matches('/')
+        ))?;
+        {
+            let mut params = url.query_pairs_mut();
+            if !query.is_empty() {
+                params.append_pair("search", query);
+            }
+            if let Some(filter) = &self.config.filter {
+                params.append_pair("filter", filter);
+            }
+            params
+                .append_pair(
+                    "per-page",
+                    &options
+                        .max_results
+                        .unwrap_or(10)
+                        .clamp(1, MAX_PER_PAGE)
+                        .to_string(),
+                )
+                .append_pair(paging_key, paging_value);
+            if let Some(sort_by) = &options.sort_by {
+                let field = match sort_by {
+                    SortBy::Relevance => "relevance_score",
+                    SortBy::SubmittedDate => "publication_date",
+                    SortBy::LastUpdatedDate => "updated_date",
+                };
+                let order = match options.sort_order {
+                    Some(SortOrder::Ascending) => "asc",
+                    _ => "desc",
+                };
+                params.append_pair("sort", &format!("{field}:{order}"));
+            }
+            if let Some(mailto) = &self.config.mailto {
+                params.append_pair("mailto", mailto);
+            }
+        }
+
+        let response: WorksResponse = self
+            .http_client
+            .get_json(url.as_str())
+            .await
+            .map_err(map_error)?;
+
+        Ok(OpenAlexPage {
+            // OpenAlex keeps returning a cursor on the final, empty page
+            next_cursor: response
+                .meta
+                .next_cursor
+                .filter(|_| !response.results.is_empty()),
+            works: response.results,
+            total_results: response.meta.count,
+        })
+    }
+
+    /// Retrieve every matching work, up to `limit`, following cursors
+    pub async fn search_all_works(
+        &self,
+        options: &SearchOptions,
+        limit: Option<usize>,
+    ) -> SearchResult<Vec<OpenAlexWork>> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut works = Vec::new();
+        let mut cursor = "*".to_string();
+
+        while works.len() < limit {
+            let page = self.search_page(options, &cursor).await?;
+            works.extend(page.works);
+            match page.next_cursor {
+                Some(next) => cursor = next,
+                None => break,
+            }
+        }
+
+        works.truncate(limit);
+        Ok(works)
+    }
+}
+
+/// Map HTTP failures to the errors callers act on
+fn map_error(error: SearchError) -> SearchError {
+    match error {
+        SearchError::HttpError {
+            status_code: Some(429),
+            ..
+        } => SearchError::RateLimit(
+            "OpenAlex rate limit reached; set mailto or slow down".to_string(),
+        ),
+        SearchError::HttpError {
+            status_code: Some(400),
+            response_body,
+            ..
+        } => SearchError::InvalidInput(format!(
+            "OpenAlex rejected the request: {}",
+            response_body.unwrap_or_default()
+        )),
+        other => other,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AutocompleteResponse {
+    #[serde(default)]
+    results: Vec<AutocompleteResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AutocompleteResult {
+    display_name: Option<String>,
+}
+
+impl Default for OpenAlexProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for OpenAlexProvider {
+    fn name(&self) -> &str {
+        "openalex"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        // Cursors can't jump ahead, so numbered pages use basic paging
+        let page = match options.page {
+            Some(page) if page > 1 => {
+                self.fetch_works(options, ("page", &page.to_string()))
+                    .await?
+            }
+            _ => self.search_page(options, "*").await?,
+        };
+        Ok(SearchResponse {
+            results: page
+                .works
+                .iter()
+                .map(OpenAlexWork::to_search_result)
+                .collect(),
+            total_results: page.total_results,
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "openalex".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config.insert(
+            "polite_pool".to_string(),
+            self.config.mailto.is_some().to_string(),
+        );
+        config.insert("max_results".to_string(), MAX_PER_PAGE.to_string());
+        config
+    }
+}
+
+#[async_trait::async_trait]
+impl SuggestProvider for OpenAlexProvider {
+    /// Complete `query` to work titles through `/autocomplete/works`
+    async fn suggest(&self, query: &str) -> SearchResult<Vec<String>> {
+        self.config.validate()?;
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut url = Url::parse(&format!(
+            "{}/autocomplete/works",
+            self.config.base_url.trim_end_matches('/')
+        ))?;
+        url.query_pairs_mut().append_pair("q", query);
+        if let Some(mailto) = &self.config.mailto {
+            url.query_pairs_mut().append_pair("mailto", mailto);
+        }
+
+        let response: AutocompleteResponse = self
+            .http_client
+            .get_json(url.as_str())
+            .await
+            .map_err(map_error)?;
+
+        let mut suggestions: Vec<String> = Vec::new();
+        for title in response
+            .results
+            .into_iter()
+            .filter_map(|result| result.display_name)
+        {
+            let title = normalize_text(&title);
+            if !title.is_empty() && !suggestions.contains(&title) {
+                suggestions.push(title);
+            }
+        }
+        Ok(suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORK_JSON: &str = r#"{
+        "id": "https://openalex.org/W2741809807",
+        "doi": "https://doi.org/10.7717/peerj.4375",
+        "display_name": "The state of OA",
+        "publication_year": 2018,
+        "publication_date": "2018-02-13",
+        "type": "article",
+        "authorships": [
+            {"author_position": "first", "author": {"id": "https://openalex.org/A1", "display_name": "Heather Piwowar"}},
+            {"author_position": "last", "author": {"id": "https://openalex.org/A2", "display_name": "Stefanie Haustein"}}
+        ],
+        "primary_location": {
+            "landing_page_url": "https://doi.org/10.7717/peerj.4375",
+            "pdf_url": "https://peerj.com/articles/4375.pdf",
+            "source": {"display_name": "PeerJ"}
+        },
+        "open_access": {"is_oa": true, "oa_status": "gold", "oa_url": "https://peerj.com/articles/4375.pdf"},
+        "cited_by_count": 900,
+        "abstract_inverted_index": {"Despite": [0], "growing": [1], "interest": [2], "in": [3, 5], "OA,": [4], "practice": [6]}
+    }"#;
+
+    #[test]
+    fn test_work_to_search_result() {
+        let work: OpenAlexWork = serde_json::from_str(WORK_JSON).unwrap();
+        assert_eq!(
+            work.abstract_text().as_deref(),
+            Some("Despite growing interest in OA, in practice")
+        );
+
+        let result = work.to_search_result();
+        assert_eq!(result.url, "https://doi.org/10.7717/peerj.4375");
+        assert_eq!(result.published_date.as_deref(), Some("2018-02-13"));
+
+        let raw = result.raw.as_ref().unwrap();
+        assert_eq!(raw["doi"], "10.7717/peerj.4375");
+        assert_eq!(raw["venue"], "PeerJ");
+        assert_eq!(raw["year"], 2018);
+        assert_eq!(raw["is_open_access"], true);
+        assert_eq!(
+            raw["open_access_url"],
+            "https://peerj.com/articles/4375.pdf"
+        );
+
+        let citation = crate::citation::Citation::from_search_result(&result).unwrap();
+        assert_eq!(citation.key, "piwowar2018state");
+        assert_eq!(citation.doi.as_deref(), Some("10.7717/peerj.4375"));
+    }
+
+    #[test]
+    fn test_work_without_abstract_falls_back_to_venue() {
+        let work = OpenAlexWork {
+            id: "https://openalex.org/W1".to_string(),
+            primary_location: Some(OpenAlexLocation {
+                source: Some(OpenAlexSource {
+                    display_name: Some("Nature".to_string()),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let result = work.to_search_result();
+        assert_eq!(result.url, "https://openalex.org/W1");
+        assert_eq!(result.snippet.as_deref(), Some("Nature"));
+    }
+}
@@ -0,0 +1,138 @@
+//! Package registry providers: crates.io, npm and PyPI
+//!
+//! Each registry has its own provider; all of them return [`Package`]
+//! records so results can be compared across ecosystems.
+
+use crate::{
+    error::SearchError, types::SearchResult as SearchResultType, utils::http::extract_domain,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub mod crates_io;
+pub mod npm;
+pub mod pypi;
+
+pub use crates_io::{CratesIoConfig, CratesIoProvider};
+pub use npm::{NpmConfig, NpmProvider};
+pub use pypi::{PyPiConfig, PyPiProvider};
+
+/// User agent for registry requests; crates.io requires one that identifies
+/// the client
+pub(crate) const USER_AGENT: &str = concat!(
+    "websearch/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_REPOSITORY"),
+    ")"
+);
+
+/// Package registry a [`Package`] came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Registry {
+    #[serde(rename = "crates.io")]
+    CratesIo,
+    #[serde(rename = "npm")]
+    Npm,
+    #[serde(rename = "pypi")]
+    PyPi,
+}
+
+impl Registry {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Registry::CratesIo => "crates.io",
+            Registry::Npm => "npm",
+            Registry::PyPi => "pypi",
+        }
+    }
+
+    /// Name of the provider that searches this registry
+    fn provider_name(self) -> &'static str {
+        match self {
+            Registry::CratesIo => "crates",
+            Registry::Npm => "npm",
+            Registry::PyPi => "pypi",
+        }
+    }
+}
+
+impl fmt::Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A package as listed by its registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Package {
+    pub registry: Registry,
+    pub name: String,
+    /// Latest (stable, where the registry distinguishes) version
+    pub version: Option<String>,
+    pub description: Option<String>,
+    /// All-time downloads for crates.io, monthly downloads for npm; PyPI
+    /// does not report downloads
+    pub downloads: Option<u64>,
+    /// Source repository URL
+    pub repository: Option<String>,
+    pub homepage: Option<String>,
+    /// Package page on the registry's website
+    pub registry_url: String,
+    /// When the latest version was published, as RFC 3339
+    pub updated: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+impl Package {
+    /// Convert to the provider-independent result format
+    ///
+    /// The URL is the registry page; `raw` carries `name`, `version`,
+    /// `downloads`, `repository` and the other package fields.
+    pub fn to_search_result(&self) -> SearchResultType {
+        let title = match &self.version {
+            Some(version) => format!("{} {version}", self.name),
+            None => self.name.clone(),
+        };
+
+        SearchResultType {
+            url: self.registry_url.clone(),
+            title,
+            snippet: self
+                .description
+                .as_deref()
+                .map(str::trim)
+                .filter(|description| !description.is_empty())
+                .map(str::to_string),
+            domain: extract_domain(&self.registry_url),
+            published_date: self.updated.clone(),
+            provider: Some(self.registry.provider_name().to_string()),
+            raw: serde_json::to_value(self).ok(),
+        }
+    }
+}
+
+/// Map registry HTTP errors to the matching `SearchError`
+pub(crate) fn map_registry_error(registry: Registry, error: SearchError) -> SearchError {
+    match error {
+        SearchError::HttpError {
+            status_code: Some(429),
+            ..
+        } => SearchError::RateLimit(format!("{registry} rate limit reached; slow down")),
+        SearchError::HttpError {
+            status_code: Some(403),
+            ..
+        } => SearchError::Blocked(format!(
+            "{registry} refused the request; check the user agent"
+        )),
+        other => other,
+    }
+}
+
+/// Drop empty strings the registries use for missing fields
+pub(crate) fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
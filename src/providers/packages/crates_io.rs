@@ -0,0 +1,231 @@
+//! crates.io search
+//!
+//! See <https://crates.io/data-access> for the crawler policy: one request
+//! per second and a user agent that identifies the client.
+
+use super::{map_registry_error, non_empty, Package, Registry, USER_AGENT};
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SortBy,
+    },
+    utils::http::HttpClient,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use url::Url;
+
+/// Largest page the API returns
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Minimum spacing between requests asked for by the crawler policy
+pub const CRAWLER_DELAY: Duration = Duration::from_secs(1);
+
+/// crates.io configuration
+#[derive(Debug, Clone)]
+pub struct CratesIoConfig {
+    /// API root
+    pub base_url: String,
+    pub user_agent: String,
+    /// API sort key (`downloads`, `recent-downloads`, ...), used instead of
+    /// the mapping from `SortBy`
+    pub sort: Option<String>,
+    /// Minimum spacing between requests
+    pub request_delay: Duration,
+}
+
+impl Default for CratesIoConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://crates.io/api/v1".to_string(),
+            user_agent: USER_AGENT.to_string(),
+            sort: None,
+            request_delay: CRAWLER_DELAY,
+        }
+    }
+}
+
+impl ProviderConfig for CratesIoConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        if self.user_agent.trim().is_empty() {
+            return Err(SearchError::ConfigError(
+                "crates.io requires a user agent".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CratesResponse {
+    #[serde(default)]
+    crates: Vec<CrateRecord>,
+    meta: Option<CratesMeta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CratesMeta {
+    total: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateRecord {
+    name: String,
+    description: Option<String>,
+    max_stable_version: Option<String>,
+    max_version: Option<String>,
+    #[serde(default)]
+    downloads: u64,
+    repository: Option<String>,
+    homepage: Option<String>,
+    updated_at: Option<String>,
+    #[serde(default)]
+    keywords: Option<Vec<String>>,
+}
+
+impl From<CrateRecord> for Package {
+    fn from(record: CrateRecord) -> Self {
+        Package {
+            registry: Registry::CratesIo,
+            registry_url: format!("https://crates.io/crates/{}", record.name),
+            name: record.name,
+            version: non_empty(record.max_stable_version).or(non_empty(record.max_version)),
+            description: non_empty(record.description),
+            downloads: Some(record.downloads),
+            repository: non_empty(record.repository),
+            homepage: non_empty(record.homepage),
+            updated: record.updated_at,
+            keywords: record.keywords.unwrap_or_default(),
+        }
+    }
+}
+
+/// crates.io search provider
+#[derive(Debug)]
+pub struct CratesIoProvider {
+    config: CratesIoConfig,
+    http_client: HttpClient,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl CratesIoProvider {
+    pub fn new() -> Self {
+        Self::with_config(CratesIoConfig::default())
+    }
+
+    pub fn with_config(config: CratesIoConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Search crates and return them with the total match count
+    pub async fn search_packages(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<(Vec<Package>, Option<u64>)> {
+        self.config.validate()?;
+        let query = options.query.trim();
+        if query.is_empty() {
+            return Err(SearchError::InvalidInput(
+                "crates.io search requires a query".to_string(),
+            ));
+        }
+
+        let sort = match (&self.config.sort, &options.sort_by) {
+            (Some(sort), _) => sort.as_str(),
+            (None, Some(SortBy::LastUpdatedDate)) => "recent-updates",
+            (None, Some(SortBy::SubmittedDate)) => "new",
+            (None, Some(SortBy::Relevance) | None) => "relevance",
+        };
+
+        let mut url = Url::parse(&format!(
+            "{}/crates",
+            self.config.base_url.trim_end_matches('/')
+        ))?;
+        url.query_pairs_mut()
+            .append_pair("q", query)
+            .append_pair("sort", sort)
+            .append_pair(
+                "per_page",
+                &options
+                    .max_results
+                    .unwrap_or(10)
+                    .clamp(1, MAX_PAGE_SIZE)
+                    .to_string(),
+            )
+            .append_pair("page", &options.page.unwrap_or(1).max(1).to_string());
+
+        self.wait_for_turn().await;
+
+        let mut headers = HashMap::new();
+        headers.insert("User-Agent".to_string(), self.config.user_agent.clone());
+        let response: CratesResponse = self
+            .http_client
+            .get_json_with_headers(url.as_str(), headers)
+            .await
+            .map_err(|error| map_registry_error(Registry::CratesIo, error))?;
+
+        Ok((
+            response.crates.into_iter().map(Package::from).collect(),
+            response.meta.and_then(|meta| meta.total),
+        ))
+    }
+
+    /// Sleep until `request_delay` has passed since the previous request
+    async fn wait_for_turn(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(previous) = *last_request {
+            let elapsed = previous.elapsed();
+            if elapsed < self.config.request_delay {
+                tokio::time::sleep(self.config.request_delay - elapsed).await;
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+impl Default for CratesIoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for CratesIoProvider {
+    fn name(&self) -> &str {
+        "crates"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let (packages, total_results) = self.search_packages(options).await?;
+        Ok(SearchResponse {
+            results: packages.iter().map(Package::to_search_result).collect(),
+            total_results,
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "crates".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config
+    }
+}
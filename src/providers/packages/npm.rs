@@ -0,0 +1,201 @@
+//! npm registry search
+//!
+//! See <https://github.com/npm/registry/blob/main/docs/REGISTRY-API.md>.
+
+use super::{map_registry_error, non_empty, Package, Registry};
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType,
+    },
+    utils::http::HttpClient,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use url::Url;
+
+/// Largest page the search endpoint returns
+pub const MAX_PAGE_SIZE: u32 = 250;
+
+/// npm configuration
+#[derive(Debug, Clone)]
+pub struct NpmConfig {
+    /// Registry root
+    pub base_url: String,
+}
+
+impl Default for NpmConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://registry.npmjs.org".to_string(),
+        }
+    }
+}
+
+impl ProviderConfig for NpmConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmSearchPage {
+    #[serde(default)]
+    objects: Vec<SearchObject>,
+    total: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchObject {
+    package: NpmPackage,
+    downloads: Option<NpmDownloads>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmPackage {
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+    /// Publish time of this version
+    date: Option<String>,
+    #[serde(default)]
+    links: NpmLinks,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NpmLinks {
+    npm: Option<String>,
+    homepage: Option<String>,
+    repository: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmDownloads {
+    monthly: Option<u64>,
+}
+
+impl From<SearchObject> for Package {
+    fn from(object: SearchObject) -> Self {
+        let package = object.package;
+        Package {
+            registry: Registry::Npm,
+            registry_url: non_empty(package.links.npm)
+                .unwrap_or_else(|| format!("https://www.npmjs.com/package/{}", package.name)),
+            name: package.name,
+            version: non_empty(package.version),
+            description: non_empty(package.description),
+            downloads: object.downloads.and_then(|downloads| downloads.monthly),
+            repository: non_empty(package.links.repository),
+            homepage: non_empty(package.links.homepage),
+            updated: package.date,
+            keywords: package.keywords,
+        }
+    }
+}
+
+/// npm search provider
+#[derive(Debug)]
+pub struct NpmProvider {
+    config: NpmConfig,
+    http_client: HttpClient,
+}
+
+impl NpmProvider {
+    pub fn new() -> Self {
+        Self::with_config(NpmConfig::default())
+    }
+
+    pub fn with_config(config: NpmConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// Search packages and return them with the total match count
+    ///
+    /// The query accepts npm's qualifiers, e.g. `keywords:cli` or
+    /// `author:sindresorhus`.
+    pub async fn search_packages(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<(Vec<Package>, Option<u64>)> {
+        self.config.validate()?;
+        let query = options.query.trim();
+        if query.is_empty() {
+            return Err(SearchError::InvalidInput(
+                "npm search requires a query".to_string(),
+            ));
+        }
+
+        let size = options.max_results.unwrap_or(10).clamp(1, MAX_PAGE_SIZE);
+        let from = options
+            .page
+            .unwrap_or(1)
+            .saturating_sub(1)
+            .checked_mul(size)
+            .ok_or_else(|| SearchError::InvalidInput("npm page is out of range".to_string()))?;
+        let mut url = Url::parse(&format!(
+            "{}/-/v1/search",
+            self.config.base_url.trim_end_matches('/')
+        ))?;
+        url.query_pairs_mut()
+            .append_pair("text", query)
+            .append_pair("size", &size.to_string())
+            .append_pair("from", &from.to_string());
+
+        let response: NpmSearchPage = self
+            .http_client
+            .get_json(url.as_str())
+            .await
+            .map_err(|error| map_registry_error(Registry::Npm, error))?;
+
+        Ok((
+            response.objects.into_iter().map(Package::from).collect(),
+            response.total,
+        ))
+    }
+}
+
+impl Default for NpmProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for NpmProvider {
+    fn name(&self) -> &str {
+        "npm"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let (packages, total_results) = self.search_packages(options).await?;
+        Ok(SearchResponse {
+            results: packages.iter().map(Package::to_search_result).collect(),
+            total_results,
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "npm".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config
+    }
+}
@@ -0,0 +1,332 @@
+//! PyPI package lookup
+//!
+//! PyPI has no search API, so the query is read as one or more project
+//! names, which are looked up through the JSON API
+//! (<https://docs.pypi.org/api/json/>). Names are normalized as in PEP 503,
+//! so `Flask_SQLAlchemy` finds `flask-sqlalchemy`.
+
+use super::{map_registry_error, non_empty, Package, Registry, USER_AGENT};
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType,
+    },
+    utils::http::HttpClient,
+};
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::HashMap;
+use url::Url;
+
+/// Project lookups in flight at once
+const MAX_CONCURRENT_LOOKUPS: usize = 4;
+
+/// Project URL labels that point at the source repository, in order of
+/// preference
+const REPOSITORY_LABELS: [&str; 6] = [
+    "source",
+    "repository",
+    "source code",
+    "code",
+    "github",
+    "homepage",
+];
+
+/// PyPI configuration
+#[derive(Debug, Clone)]
+pub struct PyPiConfig {
+    /// Site root
+    pub base_url: String,
+}
+
+impl Default for PyPiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://pypi.org".to_string(),
+        }
+    }
+}
+
+impl ProviderConfig for PyPiConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectResponse {
+    info: ProjectInfo,
+    /// Files of the latest release
+    #[serde(default)]
+    urls: Vec<ReleaseFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectInfo {
+    name: String,
+    version: Option<String>,
+    summary: Option<String>,
+    home_page: Option<String>,
+    keywords: Option<String>,
+    package_url: Option<String>,
+    project_urls: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseFile {
+    upload_time_iso_8601: Option<String>,
+}
+
+/// Normalize a project name as in PEP 503
+pub fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+/// The repository among a project's URLs, falling back to a code-hosting
+/// home page
+fn repository_url(info: &ProjectInfo) -> Option<String> {
+    let project_urls = info.project_urls.as_ref();
+    let labelled = REPOSITORY_LABELS.iter().find_map(|label| {
+        project_urls?
+            .iter()
+            .find(|(key, url)| key.eq_ignore_ascii_case(label) && is_code_host(url))
+            .map(|(_, url)| url.clone())
+    });
+    labelled.or_else(|| info.home_page.clone().filter(|url| is_code_host(url)))
+}
+
+fn is_code_host(url: &str) -> bool {
+    ["github.com", "gitlab.com", "bitbucket.org", "codeberg.org"]
+        .iter()
+        .any(|host| url.contains(host))
+}
+
+impl From<ProjectResponse> for Package {
+    fn from(response: ProjectResponse) -> Self {
+        let info = response.info;
+        let repository = repository_url(&info);
+        let homepage = non_empty(info.home_page.clone()).or_else(|| {
+            info.project_urls.as_ref().and_then(|urls| {
+                urls.iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case("homepage"))
+                    .map(|(_, url)| url.clone())
+            })
+        });
+
+        Package {
+            registry: Registry::PyPi,
+            registry_url: non_empty(info.package_url)
+                .unwrap_or_else(|| format!("https://pypi.org/project/{}/", info.name)),
+            name: info.name,
+            version: non_empty(info.version),
+            description: non_empty(info.summary),
+            downloads: None,
+            repository,
+            homepage,
+            updated: response
+                .urls
+                .into_iter()
+                .filter_map(|file| file.upload_time_iso_8601)
+                .max(),
+            keywords: info
+                .keywords
+                .unwrap_or_default()
+                .split([',', ' '])
+                .map(str::trim)
+                .filter(|keyword| !keyword.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// PyPI lookup provider
+#[derive(Debug)]
+pub struct PyPiProvider {
+    config: PyPiConfig,
+    http_client: HttpClient,
+}
+
+impl PyPiProvider {
+    pub fn new() -> Self {
+        Self::with_config(PyPiConfig::default())
+    }
+
+    pub fn with_config(config: PyPiConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// Look up one project; `Ok(None)` when PyPI has no such project
+    pub async fn package(&self, name: &str) -> SearchResult<Option<Package>> {
+        self.config.validate()?;
+        let name = normalize_name(name);
+        if name.is_empty() || name.contains('/') {
+            return Err(SearchError::InvalidInput(format!(
+                "Invalid PyPI project name: '{name}'"
+            )));
+        }
+
+        let mut url = Url::parse(self.config.base_url.trim_end_matches('/'))?;
+        url.path_segments_mut()
+            .map_err(|_| SearchError::ConfigError("Invalid base URL".to_string()))?
+            .extend(["pypi", name.as_str(), "json"]);
+
+        let mut headers = HashMap::new();
+        headers.insert("User-Agent".to_string(), USER_AGENT.to_string());
+        match self
+            .http_client
+            .get_json_with_headers::<ProjectResponse>(url.as_str(), headers)
+            .await
+        {
+            Ok(response) => Ok(Some(response.into())),
+            Err(SearchError::HttpError {
+                status_code: Some(404),
+                ..
+            }) => Ok(None),
+            Err(error) => Err(map_registry_error(Registry::PyPi, error)),
+        }
+    }
+
+    /// Look up every project named in the query, separated by spaces or
+    /// commas, skipping names PyPI doesn't know
+    pub async fn search_packages(&self, options: &SearchOptions) -> SearchResult<Vec<Package>> {
+        let mut names: Vec<String> = Vec::new();
+        for name in options
+            .query
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(normalize_name)
+            .filter(|name| !name.is_empty())
+        {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        if names.is_empty() {
+            return Err(SearchError::InvalidInput(
+                "PyPI lookup requires one or more project names".to_string(),
+            ));
+        }
+        names.truncate(options.max_results.unwrap_or(10).max(1) as usize);
+
+        let lookups: Vec<_> = names.iter().map(|name| self.package(name)).collect();
+        let results: Vec<_> = futures::stream::iter(lookups)
+            .buffered(MAX_CONCURRENT_LOOKUPS)
+            .collect()
+            .await;
+        let mut packages = Vec::new();
+        for package in results {
+            packages.extend(package?);
+        }
+        Ok(packages)
+    }
+}
+
+impl Default for PyPiProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for PyPiProvider {
+    fn name(&self) -> &str {
+        "pypi"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let packages = self.search_packages(options).await?;
+        Ok(SearchResponse::from(
+            packages
+                .iter()
+                .map(Package::to_search_result)
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "pypi".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Flask_SQLAlchemy"), "flask-sqlalchemy");
+        assert_eq!(normalize_name("zope.interface"), "zope-interface");
+        assert_eq!(normalize_name("a-_.b"), "a-b");
+    }
+
+    #[test]
+    fn test_project_to_package() {
+        let response: ProjectResponse = serde_json::from_str(
+            r#"{
+                "info": {
+                    "name": "requests",
+                    "version": "2.32.3",
+                    "summary": "Python HTTP for Humans.",
+                    "home_page": "https://requests.readthedocs.io",
+                    "keywords": "",
+                    "package_url": "https://pypi.org/project/requests/",
+                    "project_urls": {
+                        "Documentation": "https://requests.readthedocs.io",
+                        "Source": "https://github.com/psf/requests"
+                    }
+                },
+                "urls": [
+                    {"upload_time_iso_8601": "2024-05-29T15:37:47.027Z"},
+                    {"upload_time_iso_8601": "2024-05-29T15:37:49.143Z"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let package = Package::from(response);
+
+        assert_eq!(package.version.as_deref(), Some("2.32.3"));
+        assert_eq!(
+            package.repository.as_deref(),
+            Some("https://github.com/psf/requests")
+        );
+        assert_eq!(
+            package.homepage.as_deref(),
+            Some("https://requests.readthedocs.io")
+        );
+        assert_eq!(package.updated.as_deref(), Some("2024-05-29T15:37:49.143Z"));
+        assert!(package.keywords.is_empty());
+
+        let result = package.to_search_result();
+        assert_eq!(result.title, "requests 2.32.3");
+        assert_eq!(result.provider.as_deref(), Some("pypi"));
+        assert_eq!(result.raw.unwrap()["registry"], "pypi");
+    }
+}
@@ -0,0 +1,889 @@
+//! PubMed provider using NCBI E-utilities
+//!
+//! Searches run `esearch` for matching PMIDs, then `esummary` for citation
+//! metadata and, optionally, `efetch` for abstracts. See
+//! <https://www.ncbi.nlm.nih.gov/books/NBK25501/>.
+
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SortBy,
+    },
+    utils::http::{normalize_text, HttpClient},
+};
+use chrono::NaiveDate;
+use quick_xml::events::Event;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use url::Url;
+
+/// Largest number of PMIDs requested per call
+pub const MAX_PAGE_SIZE: u32 = 500;
+
+/// Request spacing allowed without an API key (3 requests per second)
+pub const ANONYMOUS_REQUEST_INTERVAL: Duration = Duration::from_millis(334);
+
+/// Request spacing allowed with an API key (10 requests per second)
+pub const API_KEY_REQUEST_INTERVAL: Duration = Duration::from_millis(100);
+
+/// PubMed search field tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PubMedField {
+    All,
+    Title,
+    TitleAbstract,
+    Author,
+    FirstAuthor,
+    Journal,
+    MeshTerms,
+    Affiliation,
+    PublicationType,
+    Language,
+}
+
+impl PubMedField {
+    /// Tag appended to a term, e.g. `au` in `smith j[au]`
+    pub fn tag(&self) -> &'static str {
+        match self {
+            PubMedField::All => "all",
+            PubMedField::Title => "ti",
+            PubMedField::TitleAbstract => "tiab",
+            PubMedField::Author => "au",
+            PubMedField::FirstAuthor => "1au",
+            PubMedField::Journal => "ta",
+            PubMedField::MeshTerms => "mh",
+            PubMedField::Affiliation => "ad",
+            PubMedField::PublicationType => "pt",
+            PubMedField::Language => "la",
+        }
+    }
+}
+
+/// Which date a PubMed date range applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PubMedDateType {
+    /// Publication date
+    #[default]
+    Publication,
+    /// Date the record entered PubMed
+    Entrez,
+    /// Date the record was last modified
+    Modification,
+}
+
+impl PubMedDateType {
+    fn tag(&self) -> &'static str {
+        match self {
+            PubMedDateType::Publication => "dp",
+            PubMedDateType::Entrez => "edat",
+            PubMedDateType::Modification => "mdat",
+        }
+    }
+}
+
+/// A PubMed query built from tagged terms
+///
+/// ```
+/// use websearch::providers::pubmed::{PubMedDateType, PubMedField, PubMedQuery};
+/// use chrono::NaiveDate;
+///
+/// let query = PubMedQuery::field(PubMedField::MeshTerms, "asthma")
+///     .and(PubMedQuery::field(PubMedField::Author, "smith j"))
+///     .and(PubMedQuery::date_range(
+///         PubMedDateType::Publication,
+///         NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+///         NaiveDate::from_ymd_opt(2020, 12, 31).unwrap(),
+///     ).unwrap());
+/// assert_eq!(
+///     query.to_string(),
+///     r#"(("asthma"[mh] AND "smith j"[au]) AND 2020/01/01:2020/12/31[dp])"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PubMedQuery(String);
+
+impl PubMedQuery {
+    /// Untagged term, searched in all fields with automatic term mapping
+    pub fn term(text: impl AsRef<str>) -> Self {
+        Self(sanitize(text.as_ref()))
+    }
+
+    /// Exact term restricted to one field
+    pub fn field(field: PubMedField, text: impl AsRef<str>) -> Self {
+        Self(format!("\"{}\"[{}]", sanitize(text.as_ref()), field.tag()))
+    }
+
+    /// Records whose date of the given type falls within `from..=to`
+    pub fn date_range(
+        date_type: PubMedDateType,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> SearchResult<Self> {
+        if from > to {
+            return Err(SearchError::InvalidInput(format!(
+                "Date range starts after it ends ({from} > {to})"
+            )));
+        }
+        Ok(Self(format!(
+            "{}:{}[{}]",
+            from.format("%Y/%m/%d"),
+            to.format("%Y/%m/%d"),
+            date_type.tag()
+        )))
+    }
+
+    pub fn and(self, other: PubMedQuery) -> Self {
+        Self(format!("({} AND {})", self.0, other.0))
+    }
+
+    pub fn or(self, other: PubMedQuery) -> Self {
+        Self(format!("({} OR {})", self.0, other.0))
+    }
+
+    pub fn not(self, other: PubMedQuery) -> Self {
+        Self(format!("({} NOT {})", self.0, other.0))
+    }
+}
+
+impl fmt::Display for PubMedQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Drop characters that would change the query's structure
+fn sanitize(text: &str) -> String {
+    normalize_text(&text.replace(['"', '(', ')', '[', ']'], " "))
+}
+
+/// PubMed configuration
+#[derive(Debug, Clone)]
+pub struct PubMedConfig {
+    /// E-utilities root
+    pub base_url: String,
+    /// NCBI API key; raises the rate limit from 3 to 10 requests per second
+    pub api_key: Option<String>,
+    /// Tool name reported to NCBI
+    pub tool: String,
+    /// Contact address reported to NCBI
+    pub email: Option<String>,
+    /// Fetch abstracts with `efetch` (one extra request per page)
+    pub fetch_abstracts: bool,
+}
+
+impl Default for PubMedConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://eutils.ncbi.nlm.nih.gov/entrez/eutils".to_string(),
+            api_key: None,
+            tool: "websearch".to_string(),
+            email: None,
+            fetch_abstracts: true,
+        }
+    }
+}
+
+impl PubMedConfig {
+    /// Minimum spacing between requests for this configuration's rate tier
+    pub fn request_interval(&self) -> Duration {
+        if self.api_key.is_some() {
+            API_KEY_REQUEST_INTERVAL
+        } else {
+            ANONYMOUS_REQUEST_INTERVAL
+        }
+    }
+}
+
+impl ProviderConfig for PubMedConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+}
+
+/// Citation metadata for one PubMed record
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PubMedArticle {
+    pub pmid: String,
+    pub title: String,
+    /// Names as PubMed lists them, e.g. `Smith J`
+    pub authors: Vec<String>,
+    /// Full journal name
+    pub journal: Option<String>,
+    /// Publication date as `YYYY-MM-DD`, when PubMed reports a full date
+    pub published: Option<String>,
+    pub year: Option<i32>,
+    pub volume: Option<String>,
+    pub issue: Option<String>,
+    pub pages: Option<String>,
+    pub doi: Option<String>,
+    pub pmcid: Option<String>,
+    pub abstract_text: Option<String>,
+}
+
+impl PubMedArticle {
+    pub fn url(&self) -> String {
+        format!("https://pubmed.ncbi.nlm.nih.gov/{}/", self.pmid)
+    }
+
+    /// Convert to the provider-independent result format
+    pub fn to_search_result(&self) -> SearchResultType {
+        let mut raw = serde_json::json!({
+            "pmid": self.pmid,
+            "authors": self.authors.iter().map(|name| inverted_name(name)).collect::<Vec<_>>(),
+            "article": self,
+        });
+        let optional = [
+            ("doi", self.doi.clone()),
+            ("journal", self.journal.clone()),
+            ("pmcid", self.pmcid.clone()),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                raw[key] = serde_json::Value::String(value);
+            }
+        }
+        if let Some(year) = self.year {
+            raw["year"] = year.into();
+        }
+
+        let snippet = self.abstract_text.clone().or_else(|| {
+            let journal = self.journal.as_deref()?;
+            Some(match (&self.volume, &self.pages) {
+                (Some(volume), Some(pages)) => format!("{journal} {volume}:{pages}"),
+                _ => journal.to_string(),
+            })
+        });
+
+        SearchResultType {
+            url: self.url(),
+            title: self.title.clone(),
+            snippet,
+            domain: Some("pubmed.ncbi.nlm.nih.gov".to_string()),
+            published_date: self
+                .published
+                .clone()
+                .or_else(|| self.year.map(|year| year.to_string())),
+            provider: Some("pubmed".to_string()),
+            raw: Some(raw),
+        }
+    }
+}
+
+/// `Smith JA` becomes `Smith, JA`, so family names survive citation export
+fn inverted_name(name: &str) -> String {
+    match name.rsplit_once(' ') {
+        Some((family, initials))
+            if !initials.is_empty() && initials.chars().all(|c| c.is_ascii_uppercase()) =>
+        {
+            format!("{family}, {initials}")
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// PubMed search provider
+#[derive(Debug)]
+pub struct PubMedProvider {
+    config: PubMedConfig,
+    http_client: HttpClient,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl PubMedProvider {
+    pub fn new() -> Self {
+        Self::with_config(PubMedConfig::default())
+    }
+
+    /// Create a provider using an NCBI API key
+    pub fn with_api_key(api_key: impl Into<String>) -> Self {
+        Self::with_config(PubMedConfig {
+            api_key: Some(api_key.into()),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_config(config: PubMedConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Search and return typed articles with the total match count
+    ///
+    /// `options.id_list` (comma-separated PMIDs) skips `esearch` and fetches
+    /// those records directly.
+    pub async fn search_articles(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<(Vec<PubMedArticle>, Option<u64>)> {
+        self.config.validate()?;
+
+        let (pmids, total) = match &options.id_list {
+            Some(ids) => {
+                let pmids = parse_pmid_list(ids)?;
+                let total = pmids.len() as u64;
+                (pmids, Some(total))
+            }
+            None => self.esearch(options).await?,
+        };
+
+        Ok((self.articles(&pmids).await?, total))
+    }
+
+    /// Fetch metadata (and abstracts, if configured) for the given PMIDs
+    pub async fn articles(&self, pmids: &[String]) -> SearchResult<Vec<PubMedArticle>> {
+        if pmids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut articles = Vec::with_capacity(pmids.len());
+        for chunk in pmids.chunks(MAX_PAGE_SIZE as usize) {
+            let ids = chunk.join(",");
+            let summary: Value = self
+                .get_json(
+                    "esummary.fcgi",
+                    &[("id", ids.as_str()), ("retmode", "json")],
+                )
+                .await?;
+            let mut chunk_articles = parse_esummary(&summary)?;
+
+            if self.config.fetch_abstracts {
+                let xml = self
+                    .get_text("efetch.fcgi", &[("id", ids.as_str()), ("retmode", "xml")])
+                    .await?;
+                let abstracts = parse_efetch_abstracts(&xml)?;
+                for article in &mut chunk_articles {
+                    article.abstract_text = abstracts.get(&article.pmid).cloned();
+                }
+            }
+            articles.extend(chunk_articles);
+        }
+        Ok(articles)
+    }
+
+    async fn esearch(&self, options: &SearchOptions) -> SearchResult<(Vec<String>, Option<u64>)> {
+        let term = options.query.trim();
+        if term.is_empty() {
+            return Err(SearchError::InvalidInput(
+                "PubMed search requires either a query or a PMID list".to_string(),
+            ));
+        }
+
+        let retmax = options
+            .max_results
+            .unwrap_or(10)
+            .clamp(1, MAX_PAGE_SIZE)
+            .to_string();
+        let retstart = options.start.unwrap_or(0).to_string();
+        let sort = match options.sort_by {
+            Some(SortBy::SubmittedDate | SortBy::LastUpdatedDate) => "pub_date",
+            _ => "relevance",
+        };
+
+        let response: Value = self
+            .get_json(
+                "esearch.fcgi",
+                &[
+                    ("term", term),
+                    ("retmax", retmax.as_str()),
+                    ("retstart", retstart.as_str()),
+                    ("sort", sort),
+                    ("retmode", "json"),
+                ],
+            )
+            .await?;
+        parse_esearch(&response)
+    }
+
+    fn endpoint(&self, utility: &str, params: &[(&str, &str)]) -> SearchResult<Url> {
+        let mut url = Url::parse(&format!(
+            "{}/{utility}",
+            self.config.base_url.trim_end_matches('/')
+        ))?;
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("db", "pubmed").extend_pairs(params);
+            query.append_pair("tool", &self.config.tool);
+            if let Some(email) = &self.config.email {
+                query.append_pair("email", email);
+            }
+            if let Some(api_key) = &self.config.api_key {
+                query.append_pair("api_key", api_key);
+            }
+        }
+        Ok(url)
+    }
+
+    async fn get_json(&self, utility: &str, params: &[(&str, &str)]) -> SearchResult<Value> {
+        let url = self.endpoint(utility, params)?;
+        self.wait_for_turn().await;
+        self.http_client
+            .get_json(url.as_str())
+            .await
+            .map_err(map_http_error)
+    }
+
+    async fn get_text(&self, utility: &str, params: &[(&str, &str)]) -> SearchResult<String> {
+        let url = self.endpoint(utility, params)?;
+        self.wait_for_turn().await;
+        self.http_client
+            .get_text(url.as_str())
+            .await
+            .map_err(map_http_error)
+    }
+
+    /// Wait until this rate tier allows another request
+    async fn wait_for_turn(&self) {
+        let interval = self.config.request_interval();
+        let mut last_request = self.last_request.lock().await;
+        if let Some(previous) = *last_request {
+            let elapsed = previous.elapsed();
+            if elapsed < interval {
+                tokio::time::sleep(interval - elapsed).await;
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+impl Default for PubMedProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for PubMedProvider {
+    fn name(&self) -> &str {
+        "pubmed"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let (articles, total_results) = self.search_articles(options).await?;
+        Ok(SearchResponse {
+            results: articles
+                .iter()
+                .map(PubMedArticle::to_search_result)
+                .collect(),
+            total_results,
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "pubmed".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config.insert(
+            "authenticated".to_string(),
+            self.config.api_key.is_some().to_string(),
+        );
+        config.insert("max_results".to_string(), MAX_PAGE_SIZE.to_string());
+        config
+    }
+}
+
+type Value = serde_json::Value;
+
+fn map_http_error(error: SearchError) -> SearchError {
+    match error {
+        SearchError::HttpError {
+            status_code: Some(429),
+            ..
+        } => SearchError::RateLimit(
+            "NCBI allows 3 requests per second without an API key and 10 with one".to_string(),
+        ),
+        other => other,
+    }
+}
+
+/// Split and check a comma-separated PMID list
+pub fn parse_pmid_list(id_list: &str) -> SearchResult<Vec<String>> {
+    let pmids: Vec<String> = id_list
+        .split(',')
+        .map(|id| id.trim().trim_start_matches("PMID:").trim())
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let invalid: Vec<&str> = pmids
+        .iter()
+        .filter(|id| !id.chars().all(|c| c.is_ascii_digit()))
+        .map(String::as_str)
+        .collect();
+    if !invalid.is_empty() {
+        return Err(SearchError::InvalidInput(format!(
+            "Invalid PMID(s): {}",
+            invalid.join(", ")
+        )));
+    }
+    if pmids.is_empty() {
+        return Err(SearchError::InvalidInput("PMID list is empty".to_string()));
+    }
+    Ok(pmids)
+}
+
+fn parse_esearch(response: &Value) -> SearchResult<(Vec<String>, Option<u64>)> {
+    let result = response
+        .get("esearchresult")
+        .ok_or_else(|| SearchError::ParseError("esearch response has no result".to_string()))?;
+
+    if let Some(error) = result.get("ERROR").and_then(Value::as_str) {
+        return Err(SearchError::InvalidInput(format!(
+            "PubMed rejected the query: {error}"
+        )));
+    }
+
+    let pmids = result
+        .get("idlist")
+        .and_then(Value::as_array)
+        .map(|ids| {
+            ids.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let total = result
+        .get("count")
+        .and_then(Value::as_str)
+        .and_then(|count| count.parse().ok());
+    Ok((pmids, total))
+}
+
+#[derive(Debug, Deserialize)]
+struct Summary {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    authors: Vec<SummaryAuthor>,
+    fulljournalname: Option<String>,
+    source: Option<String>,
+    /// e.g. `2020/01/15 00:00`
+    sortpubdate: Option<String>,
+    pubdate: Option<String>,
+    volume: Option<String>,
+    issue: Option<String>,
+    pages: Option<String>,
+    #[serde(default)]
+    articleids: Vec<SummaryArticleId>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SummaryAuthor {
+    name: String,
+    authtype: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SummaryArticleId {
+    idtype: String,
+    value: String,
+}
+
+/// Parse an `esummary` JSON response, keeping the server's PMID order
+fn parse_esummary(response: &Value) -> SearchResult<Vec<PubMedArticle>> {
+    let result = response
+        .get("result")
+        .ok_or_else(|| SearchError::ParseError("esummary response has no result".to_string()))?;
+    let uids = result
+        .get("uids")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut articles = Vec::new();
+    for uid in uids.iter().filter_map(Value::as_str) {
+        let Some(entry) = result.get(uid) else {
+            continue;
+        };
+        let summary: Summary = serde_json::from_value(entry.clone())?;
+        if summary.error.is_some() {
+            continue;
+        }
+
+        let article_id = |kind: &str| {
+            summary
+                .articleids
+                .iter()
+                .find(|id| id.idtype == kind)
+                .map(|id| id.value.clone())
+                .filter(|value| !value.is_empty())
+        };
+        let published = summary
+            .sortpubdate
+            .as_deref()
+            .and_then(|date| date.split_whitespace().next())
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y/%m/%d").ok());
+        let year = published
+            .map(|date| chrono::Datelike::year(&date))
+            .or_else(|| summary.pubdate.as_deref()?.get(..4)?.parse().ok());
+        let non_empty = |value: Option<String>| value.filter(|value| !value.trim().is_empty());
+
+        articles.push(PubMedArticle {
+            pmid: uid.to_string(),
+            title: normalize_text(summary.title.trim_end_matches('.')),
+            authors: summary
+                .authors
+                .iter()
+                .filter(|author| {
+                    author
+                        .authtype
+                        .as_deref()
+                        .is_none_or(|kind| kind == "Author")
+                })
+                .map(|author| author.name.clone())
+                .collect(),
+            journal: non_empty(summary.fulljournalname.clone())
+                .or(non_empty(summary.source.clone())),
+            published: published.map(|date| date.format("%Y-%m-%d").to_string()),
+            year,
+            volume: non_empty(summary.volume.clone()),
+            issue: non_empty(summary.issue.clone()),
+            pages: non_empty(summary.pages.clone()),
+            doi: article_id("doi"),
+            pmcid: article_id("pmc"),
+            abstract_text: None,
+        });
+    }
+    Ok(articles)
+}
+
+/// Collect abstracts from an `efetch` XML response, keyed by PMID
+///
+/// Abstract text can contain inline markup (`<i>`, `<sup>`) and is split into
+/// labelled sections in structured abstracts, so it is read event by event.
+fn parse_efetch_abstracts(xml: &str) -> SearchResult<HashMap<String, String>> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut abstracts = HashMap::new();
+
+    let mut pmid: Option<String> = None;
+    let mut in_pmid = false;
+    let mut sections: Vec<String> = Vec::new();
+    let mut current: Option<String> = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| SearchError::ParseError(format!("Failed to parse efetch XML: {e}")))?;
+        match event {
+            Event::Start(element) => match element.name().as_ref() {
+                b"PubmedArticle" => {
+                    pmid = None;
+                    sections.clear();
+                }
+                // The first PMID belongs to the article; later ones are in comments/corrections
+                b"PMID" if pmid.is_none() => in_pmid = true,
+                b"AbstractText" => {
+                    let label = element
+                        .try_get_attribute("Label")
+                        .ok()
+                        .flatten()
+                        .and_then(|label| label.unescape_value().ok())
+                        .map(|label| format!("{label}: "));
+                    current = Some(label.unwrap_or_default());
+                }
+                _ => {}
+            },
+            Event::Text(text) => {
+                let text = text
+                    .unescape()
+                    .map_err(|e| SearchError::ParseError(format!("Invalid efetch text: {e}")))?;
+                if in_pmid {
+                    pmid = Some(text.trim().to_string());
+                } else if let Some(current) = &mut current {
+                    current.push_str(&text);
+                }
+            }
+            Event::End(element) => match element.name().as_ref() {
+                b"PMID" => in_pmid = false,
+                b"AbstractText" => {
+                    if let Some(section) = current.take() {
+                        let section = normalize_text(&section);
+                        if !section.is_empty() {
+                            sections.push(section);
+                        }
+                    }
+                }
+                b"PubmedArticle" => {
+                    if let (Some(pmid), false) = (pmid.take(), sections.is_empty()) {
+                        abstracts.insert(pmid, sections.join("\n"));
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(abstracts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ESUMMARY_JSON: &str = r#"{
+        "header": {"type": "esummary", "version": "0.3"},
+        "result": {
+            "uids": ["31452104", "1"],
+            "31452104": {
+                "uid": "31452104",
+                "pubdate": "2019 Aug 26",
+                "source": "Nature",
+                "authors": [
+                    {"name": "Smith JA", "authtype": "Author", "clusterid": ""},
+                    {"name": "Jones B", "authtype": "Author", "clusterid": ""},
+                    {"name": "Study Group", "authtype": "CollectiveName", "clusterid": ""}
+                ],
+                "title": "Asthma in mice.",
+                "volume": "572",
+                "issue": "7770",
+                "pages": "123-127",
+                "fulljournalname": "Nature",
+                "sortpubdate": "2019/08/26 00:00",
+                "articleids": [
+                    {"idtype": "pubmed", "idtypen": 1, "value": "31452104"},
+                    {"idtype": "doi", "idtypen": 3, "value": "10.1038/s41586-019-1500-0"},
+                    {"idtype": "pmc", "idtypen": 8, "value": "PMC6789"}
+                ]
+            },
+            "1": {"uid": "1", "error": "cannot get document summary"}
+        }
+    }"#;
+
+    const EFETCH_XML: &str = r#"<?xml version="1.0" ?>
+<!DOCTYPE PubmedArticleSet PUBLIC "-//NLM//DTD PubMedArticle, 1st January 2024//EN" "https://dtd.nlm.nih.gov/ncbi/pubmed/out/pubmed_240101.dtd">
+<PubmedArticleSet>
+  <PubmedArticle>
+    <MedlineCitation Status="MEDLINE" Owner="NLM">
+      <PMID Version="1">31452104</PMID>
+      <Article>
+        <Abstract>
+          <AbstractText Label="BACKGROUND">Asthma is <i>common</i> in
+            mice &amp; men.</AbstractText>
+          <AbstractText Label="RESULTS">It got better.</AbstractText>
+        </Abstract>
+      </Article>
+      <CommentsCorrectionsList>
+        <CommentsCorrections RefType="CommentIn"><PMID Version="1">999</PMID></CommentsCorrections>
+      </CommentsCorrectionsList>
+    </MedlineCitation>
+  </PubmedArticle>
+  <PubmedArticle>
+    <MedlineCitation><PMID Version="1">42</PMID><Article></Article></MedlineCitation>
+  </PubmedArticle>
+</PubmedArticleSet>"#;
+
+    #[test]
+    fn test_parse_esummary() {
+        let response: Value = serde_json::from_str(ESUMMARY_JSON).unwrap();
+        let articles = parse_esummary(&response).unwrap();
+        assert_eq!(articles.len(), 1);
+
+        let article = &articles[0];
+        assert_eq!(article.pmid, "31452104");
+        assert_eq!(article.title, "Asthma in mice");
+        assert_eq!(article.authors, vec!["Smith JA", "Jones B"]);
+        assert_eq!(article.published.as_deref(), Some("2019-08-26"));
+        assert_eq!(article.year, Some(2019));
+        assert_eq!(article.doi.as_deref(), Some("10.1038/s41586-019-1500-0"));
+        assert_eq!(article.pmcid.as_deref(), Some("PMC6789"));
+
+        let result = article.to_search_result();
+        assert_eq!(result.url, "https://pubmed.ncbi.nlm.nih.gov/31452104/");
+        assert_eq!(result.snippet.as_deref(), Some("Nature 572:123-127"));
+        let citation = crate::citation::Citation::from_search_result(&result).unwrap();
+        assert_eq!(citation.key, "smith2019asthma");
+        assert_eq!(citation.venue.as_deref(), Some("Nature"));
+    }
+
+    #[test]
+    fn test_parse_efetch_abstracts() {
+        let abstracts = parse_efetch_abstracts(EFETCH_XML).unwrap();
+        assert_eq!(
+            abstracts.get("31452104").map(String::as_str),
+            Some("BACKGROUND: Asthma is common in mice & men.\nRESULTS: It got better.")
+        );
+        assert!(!abstracts.contains_key("999"));
+        assert!(!abstracts.contains_key("42"));
+    }
+
+    #[test]
+    fn test_parse_esearch() {
+        let response: Value = serde_json::from_str(
+            r#"{"esearchresult": {"count": "1234", "retmax": "2", "retstart": "0", "idlist": ["1", "2"]}}"#,
+        )
+        .unwrap();
+        let (pmids, total) = parse_esearch(&response).unwrap();
+        assert_eq!(pmids, vec!["1", "2"]);
+        assert_eq!(total, Some(1234));
+
+        let error: Value =
+            serde_json::from_str(r#"{"esearchresult": {"ERROR": "Invalid query"}}"#).unwrap();
+        assert!(matches!(
+            parse_esearch(&error),
+            Err(SearchError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_pmid_list() {
+        assert_eq!(
+            parse_pmid_list("31452104, PMID:1 ,").unwrap(),
+            vec!["31452104", "1"]
+        );
+        assert!(parse_pmid_list("123,abc").is_err());
+        assert!(parse_pmid_list(" , ").is_err());
+    }
+
+    #[test]
+    fn test_query_builder() {
+        let query = PubMedQuery::field(PubMedField::Title, "covid (long)")
+            .or(PubMedQuery::term("sars-cov-2"))
+            .not(PubMedQuery::field(PubMedField::PublicationType, "review"));
+        assert_eq!(
+            query.to_string(),
+            r#"(("covid long"[ti] OR sars-cov-2) NOT "review"[pt])"#
+        );
+
+        let from = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        assert!(PubMedQuery::date_range(PubMedDateType::Entrez, from, to).is_err());
+    }
+
+    #[test]
+    fn test_rate_tiers() {
+        assert_eq!(
+            PubMedConfig::default().request_interval(),
+            ANONYMOUS_REQUEST_INTERVAL
+        );
+        let keyed = PubMedConfig {
+            api_key: Some("key".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(keyed.request_interval(), API_KEY_REQUEST_INTERVAL);
+    }
+}
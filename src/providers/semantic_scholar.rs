@@ -0,0 +1,536 @@
+//! Semantic Scholar Academic Graph provider
+//!
+//! Paper search plus reference/citation lookups through the Graph API. See
+//! <https://api.semanticscholar.org/api-docs/graph>.
+
+use crate::{
+    error::{SearchError, SearchResult},
+    providers::arxiv::ArxivId,
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SuggestProvider,
+    },
+    utils::http::{normalize_text, HttpClient},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// Fields requested for every paper
+const PAPER_FIELDS: &str = "paperId,externalIds,url,title,abstract,venue,year,publicationDate,\
+authors,citationCount,influentialCitationCount,referenceCount,openAccessPdf,fieldsOfStudy";
+
+/// Largest page the paper search endpoint returns
+pub const MAX_SEARCH_PAGE_SIZE: u32 = 100;
+
+/// Largest page the references/citations endpoints return
+const MAX_GRAPH_PAGE_SIZE: usize = 1000;
+
+/// Semantic Scholar configuration
+#[derive(Debug, Clone)]
+pub struct SemanticScholarConfig {
+    /// Graph API root
+    pub base_url: String,
+    /// Optional API key; unauthenticated requests share a small public rate limit
+    pub api_key: Option<String>,
+}
+
+impl Default for SemanticScholarConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.semanticscholar.org/graph/v1".to_string(),
+            api_key: None,
+        }
+    }
+}
+
+impl ProviderConfig for SemanticScholarConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+}
+
+/// A paper record from the Graph API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticScholarPaper {
+    /// Empty for citation edges Semantic Scholar couldn't match to a paper
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub paper_id: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub external_ids: ExternalIds,
+    pub url: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub title: String,
+    #[serde(rename = "abstract")]
+    pub abstract_text: Option<String>,
+    pub venue: Option<String>,
+    pub year: Option<i32>,
+    /// `YYYY-MM-DD`
+    pub publication_date: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<SemanticScholarAuthor>,
+    pub citation_count: Option<u64>,
+    pub influential_citation_count: Option<u64>,
+    pub reference_count: Option<u64>,
+    pub open_access_pdf: Option<OpenAccessPdf>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub fields_of_study: Vec<String>,
+}
+
+/// Identifiers of the same paper in other databases
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExternalIds {
+    #[serde(rename = "ArXiv")]
+    pub arxiv: Option<String>,
+    #[serde(rename = "DOI")]
+    pub doi: Option<String>,
+    #[serde(rename = "PubMed")]
+    pub pubmed: Option<String>,
+    #[serde(rename = "CorpusId")]
+    pub corpus_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticScholarAuthor {
+    pub author_id: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAccessPdf {
+    pub url: String,
+    pub status: Option<String>,
+}
+
+impl SemanticScholarPaper {
+    /// Convert to the provider-independent result format
+    ///
+    /// `raw` carries the flat `authors`, `doi`, `arxiv_id`, `venue`, `year`
+    /// and citation-count keys, plus the full record under `paper`.
+    pub fn to_search_result(&self) -> SearchResultType {
+        let url = self
+            .url
+            .clone()
+            .unwrap_or_else(|| format!("https://www.semanticscholar.org/paper/{}", self.paper_id));
+
+        let mut raw = serde_json::json!({
+            "paper_id": self.paper_id,
+            "authors": self.authors.iter().map(|author| author.name.as_str()).collect::<Vec<_>>(),
+            "citation_count": self.citation_count,
+            "influential_citation_count": self.influential_citation_count,
+            "paper": self,
+        });
+        let optional = [
+            ("doi", self.external_ids.doi.clone()),
+            ("arxiv_id", self.external_ids.arxiv.clone()),
+            ("pmid", self.external_ids.pubmed.clone()),
+            (
+                "venue",
+                self.venue.clone().filter(|venue| !venue.is_empty()),
+            ),
+            (
+                "open_access_pdf",
+                self.open_access_pdf.as_ref().map(|pdf| pdf.url.clone()),
+            ),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                raw[key] = serde_json::Value::String(value);
+            }
+        }
+        if let Some(year) = self.year {
+            raw["year"] = year.into();
+        }
+
+        SearchResultType {
+            domain: crate::utils::http::extract_domain(&url),
+            url,
+            title: self.title.clone(),
+            snippet: self.abstract_text.clone(),
+            published_date: self
+                .publication_date
+                .clone()
+                .or_else(|| self.year.map(|year| year.to_string())),
+            provider: Some("semanticscholar".to_string()),
+            raw: Some(raw),
+        }
+    }
+
+    /// Recover the paper from a result produced by [`Self::to_search_result`]
+    pub fn from_search_result(result: &SearchResultType) -> Option<Self> {
+        let paper = result.raw.as_ref()?.get("paper")?;
+        serde_json::from_value(paper.clone()).ok()
+    }
+}
+
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchPage {
+    total: Option<u64>,
+    #[serde(default)]
+    data: Vec<SemanticScholarPaper>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AutocompletePage {
+    #[serde(default)]
+    matches: Vec<AutocompleteMatch>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AutocompleteMatch {
+    title: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphPage {
+    next: Option<usize>,
+    #[serde(default)]
+    data: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphEdge {
+    cited_paper: Option<SemanticScholarPaper>,
+    citing_paper: Option<SemanticScholarPaper>,
+}
+
+/// Semantic Scholar search provider
+#[derive(Debug)]
+pub struct SemanticScholarProvider {
+    config: SemanticScholarConfig,
+    http_client: HttpClient,
+}
+
+impl SemanticScholarProvider {
+    pub fn new() -> Self {
+        Self::with_config(SemanticScholarConfig::default())
+    }
+
+    /// Create a provider that authenticates with an API key
+    pub fn with_api_key(api_key: impl Into<String>) -> Self {
+        Self::with_config(SemanticScholarConfig {
+            api_key: Some(api_key.into()),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_config(config: SemanticScholarConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+        }
+    }
+
+    /// Search and return typed paper records with the total match count
+    pub async fn search_papers(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<(Vec<SemanticScholarPaper>, Option<u64>)> {
+        let query = options.query.trim();
+        if query.is_empty() {
+            return Err(SearchError::InvalidInput(
+                "Semantic Scholar search requires a query".to_string(),
+            ));
+        }
+
+        let limit = options
+            .max_results
+            .unwrap_or(10)
+            .clamp(1, MAX_SEARCH_PAGE_SIZE);
+        let mut url = self.endpoint("paper/search")?;
+        url.query_pairs_mut()
+            .append_pair("query", query)
+            .append_pair("offset", &options.start.unwrap_or(0).to_string())
+            .append_pair("limit", &limit.to_string())
+            .append_pair("fields", PAPER_FIELDS);
+
+        let page: SearchPage = self.get(&url).await?;
+        Ok((page.data, page.total))
+    }
+
+    /// Look up a paper by Semantic Scholar ID or prefixed external ID,
+    /// e.g. `arXiv:2301.00001`, `DOI:10.18653/v1/N18-3011` or `PMID:19872477`
+    pub async fn paper(&self, paper_id: &str) -> SearchResult<SemanticScholarPaper> {
+        let mut url = self.endpoint(&format!("paper/{}", validate_paper_id(paper_id)?))?;
+        url.query_pairs_mut().append_pair("fields", PAPER_FIELDS);
+        self.get(&url).await
+    }
+
+    /// Resolve an arXiv identifier; Semantic Scholar tracks papers, not versions
+    pub async fn paper_by_arxiv_id(&self, id: &ArxivId) -> SearchResult<SemanticScholarPaper> {
+        self.paper(&format!("arXiv:{}", id.base())).await
+    }
+
+    /// Resolve a DOI, with or without a `https://doi.org/` prefix
+    pub async fn paper_by_doi(&self, doi: &str) -> SearchResult<SemanticScholarPaper> {
+        let doi = doi
+            .trim()
+            .trim_start_matches("https://doi.org/")
+            .trim_start_matches("http://doi.org/")
+            .trim_start_matches("doi:");
+        self.paper(&format!("DOI:{doi}")).await
+    }
+
+    /// Papers cited by `paper_id`, up to `limit`
+    pub async fn references(
+        &self,
+        paper_id: &str,
+        limit: usize,
+    ) -> SearchResult<Vec<SemanticScholarPaper>> {
+        let edges = self.graph_edges(paper_id, "references", limit).await?;
+        Ok(edges
+            .into_iter()
+            .filter_map(|edge| edge.cited_paper)
+            .filter(|paper| !paper.paper_id.is_empty())
+            .collect())
+    }
+
+    /// Papers citing `paper_id`, up to `limit`
+    pub async fn citations(
+        &self,
+        paper_id: &str,
+        limit: usize,
+    ) -> SearchResult<Vec<SemanticScholarPaper>> {
+        let edges = self.graph_edges(paper_id, "citations", limit).await?;
+        Ok(edges
+            .into_iter()
+            .filter_map(|edge| edge.citing_paper)
+            .filter(|paper| !paper.paper_id.is_empty())
+            .collect())
+    }
+
+    async fn graph_edges(
+        &self,
+        paper_id: &str,
+        relation: &str,
+        limit: usize,
+    ) -> SearchResult<Vec<GraphEdge>> {
+        let paper_id = validate_paper_id(paper_id)?;
+        let mut edges = Vec::new();
+        let mut offset = 0;
+
+        while edges.len() < limit {
+            let page_size = (limit - edges.len()).min(MAX_GRAPH_PAGE_SIZE);
+            let mut url = self.endpoint(&format!("paper/{paper_id}/{relation}"))?;
+            url.query_pairs_mut()
+                .append_pair("offset", &offset.to_string())
+                .append_pair("limit", &page_size.to_string())
+                .append_pair("fields", PAPER_FIELDS);
+
+            let page: GraphPage = self.get(&url).await?;
+            let received = page.data.len();
+            edges.extend(page.data);
+
+            match page.next {
+                Some(next) if received > 0 => offset = next,
+                _ => break,
+            }
+        }
+
+        edges.truncate(limit);
+        Ok(edges)
+    }
+
+    /// Append `path` to the base URL, percent-encoding each segment
+    fn endpoint(&self, path: &str) -> SearchResult<Url> {
+        self.config.validate()?;
+        let mut url = Url::parse(self.config.base_url.trim_end_matches('/'))?;
+        url.path_segments_mut()
+            .map_err(|_| SearchError::ConfigError("Invalid base URL".to_string()))?
+            .extend(path.split('/'));
+        Ok(url)
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, url: &Url) -> SearchResult<T> {
+        let mut headers = HashMap::new();
+        if let Some(api_key) = self.config.api_key() {
+            headers.insert("x-api-key".to_string(), api_key.to_string());
+        }
+
+        self.http_client
+            .get_json_with_headers(url.as_str(), headers)
+            .await
+            .map_err(|error| match error {
+                SearchError::HttpError {
+                    status_code: Some(429),
+                    ..
+                } => SearchError::RateLimit(
+                    "Semantic Scholar rate limit reached; use an API key or slow down".to_string(),
+                ),
+                SearchError::HttpError {
+                    status_code: Some(401 | 403),
+                    ..
+                } => SearchError::AuthenticationError(
+                    "Semantic Scholar rejected the API key".to_string(),
+                ),
+                SearchError::HttpError {
+                    status_code: Some(404),
+                    ..
+                } => SearchError::InvalidInput(format!(
+                    "Semantic Scholar has no record for {}",
+                    url.path()
+                )),
+                other => other,
+            })
+    }
+}
+
+impl Default for SemanticScholarProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Paper IDs become path segments, so reject anything that would escape them
+///
+/// DOIs contain `/`; those are split into separate segments, which is how the
+/// API expects them.
+fn validate_paper_id(paper_id: &str) -> SearchResult<String> {
+    let paper_id = paper_id.trim();
+    if paper_id.is_empty()
+        || paper_id.chars().any(char::is_whitespace)
+        || paper_id.split('/').any(|part| part == "..")
+    {
+        return Err(SearchError::InvalidInput(format!(
+            "Invalid Semantic Scholar paper ID: '{paper_id}'"
+        )));
+    }
+    Ok(paper_id.to_string())
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for SemanticScholarProvider {
+    fn name(&self) -> &str {
+        "semanticscholar"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let (papers, total_results) = self.search_papers(options).await?;
+        Ok(SearchResponse {
+            results: papers
+                .iter()
+                .map(SemanticScholarPaper::to_search_result)
+                .collect(),
+            total_results,
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "semanticscholar".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config.insert(
+            "authenticated".to_string(),
+            self.config.api_key.is_some().to_string(),
+        );
+        config
+    }
+}
+
+#[async_trait::async_trait]
+impl SuggestProvider for SemanticScholarProvider {
+    /// Complete `query` to paper titles through `/paper/autocomplete`
+    async fn suggest(&self, query: &str) -> SearchResult<Vec<String>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut url = self.endpoint("paper/autocomplete")?;
+        url.query_pairs_mut().append_pair("query", query);
+        let response: AutocompletePage = self.get(&url).await?;
+
+        let mut suggestions: Vec<String> = Vec::new();
+        for title in response.matches.into_iter().filter_map(|paper| paper.title) {
+            let title = normalize_text(&title);
+            if !title.is_empty() && !suggestions.contains(&title) {
+                suggestions.push(title);
+            }
+        }
+        Ok(suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAPER_JSON: &str = r#"{
+        "paperId": "204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+        "externalIds": {"ArXiv": "1706.03762", "DOI": "10.48550/arXiv.1706.03762", "CorpusId": 13756489},
+        "url": "https://www.semanticscholar.org/paper/204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+        "title": "Attention is All you Need",
+        "abstract": "The dominant sequence transduction models...",
+        "venue": "Neural Information Processing Systems",
+        "year": 2017,
+        "publicationDate": "2017-06-12",
+        "authors": [{"authorId": "40348417", "name": "Ashish Vaswani"}, {"authorId": null, "name": "Noam Shazeer"}],
+        "citationCount": 100000,
+        "influentialCitationCount": 15000,
+        "referenceCount": 40,
+        "openAccessPdf": {"url": "https://arxiv.org/pdf/1706.03762", "status": "GREEN"},
+        "fieldsOfStudy": null
+    }"#;
+
+    #[test]
+    fn test_paper_to_search_result() {
+        let paper: SemanticScholarPaper = serde_json::from_str(PAPER_JSON).unwrap();
+        assert!(paper.fields_of_study.is_empty());
+
+        let result = paper.to_search_result();
+        assert_eq!(result.title, "Attention is All you Need");
+        assert_eq!(result.domain.as_deref(), Some("www.semanticscholar.org"));
+        assert_eq!(result.published_date.as_deref(), Some("2017-06-12"));
+
+        let raw = result.raw.as_ref().unwrap();
+        assert_eq!(raw["arxiv_id"], "1706.03762");
+        assert_eq!(raw["citation_count"], 100000);
+        assert_eq!(raw["venue"], "Neural Information Processing Systems");
+        assert_eq!(raw["open_access_pdf"], "https://arxiv.org/pdf/1706.03762");
+        assert_eq!(raw["authors"][1], "Noam Shazeer");
+
+        let recovered = SemanticScholarPaper::from_search_result(&result).unwrap();
+        assert_eq!(recovered.paper_id, paper.paper_id);
+
+        let citation = crate::citation::Citation::from_search_result(&result).unwrap();
+        assert_eq!(citation.key, "vaswani2017attention");
+    }
+
+    #[test]
+    fn test_validate_paper_id() {
+        assert_eq!(
+            validate_paper_id(" DOI:10.18653/v1/N18-3011 ").unwrap(),
+            "DOI:10.18653/v1/N18-3011"
+        );
+        assert!(validate_paper_id("").is_err());
+        assert!(validate_paper_id("arXiv:1706.03762 extra").is_err());
+        assert!(validate_paper_id("../../admin").is_err());
+    }
+}
@@ -0,0 +1,485 @@
+//! Stack Exchange search provider
+//!
+//! Question search on any Stack Exchange site through `/search/advanced`. See
+//! <https://api.stackexchange.com/docs/advanced-search>.
+//!
+//! The API compresses every response and asks clients to pause through a
+//! `backoff` field; both are handled here. The remaining daily quota is
+//! reported on each page and through [`StackExchangeProvider::quota`].
+
+use crate::{
+    error::{SearchError, SearchResult},
+    types::{
+        ProviderConfig, SearchOptions, SearchProvider, SearchResponse,
+        SearchResult as SearchResultType, SortBy, SortOrder,
+    },
+    utils::http::{html_to_text, truncate_text, HttpClient},
+};
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use url::Url;
+
+/// Largest page the API returns
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Longest question body kept as a snippet
+const SNIPPET_LENGTH: usize = 300;
+
+/// Longest `backoff` honoured; the API normally asks for seconds, not hours
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Stack Exchange configuration
+#[derive(Debug, Clone)]
+pub struct StackExchangeConfig {
+    /// API root, including the version
+    pub base_url: String,
+    /// Site to search, e.g. `stackoverflow`, `superuser` or `unix`
+    pub site: String,
+    /// Registered application key; raises the daily quota from 300 to 10,000
+    pub key: Option<String>,
+    /// Only questions carrying all of these tags
+    pub tagged: Vec<String>,
+    /// Only questions with (`Some(true)`) or without (`Some(false)`) an
+    /// accepted answer
+    pub accepted: Option<bool>,
+    /// API-native sort key (e.g. `votes`), used instead of the mapping from
+    /// `SortBy`
+    pub sort: Option<String>,
+}
+
+impl Default for StackExchangeConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.stackexchange.com/2.3".to_string(),
+            site: "stackoverflow".to_string(),
+            key: None,
+            tagged: Vec::new(),
+            accepted: None,
+            sort: None,
+        }
+    }
+}
+
+impl ProviderConfig for StackExchangeConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.base_url.is_empty() {
+            return Err(SearchError::ConfigError("Base URL is required".to_string()));
+        }
+        if self.site.trim().is_empty() {
+            return Err(SearchError::ConfigError(
+                "A Stack Exchange site is required".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn api_key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+}
+
+/// Daily request quota for this key or IP address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StackExchangeQuota {
+    pub max: u32,
+    pub remaining: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackExchangeOwner {
+    pub display_name: Option<String>,
+    pub link: Option<String>,
+}
+
+/// A question search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackExchangeQuestion {
+    pub question_id: u64,
+    /// HTML-escaped, as the API returns it
+    pub title: String,
+    pub link: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub score: i64,
+    #[serde(default)]
+    pub answer_count: u32,
+    #[serde(default)]
+    pub is_answered: bool,
+    pub accepted_answer_id: Option<u64>,
+    #[serde(default)]
+    pub view_count: u64,
+    /// Unix timestamp
+    pub creation_date: i64,
+    /// Unix timestamp
+    pub last_activity_date: Option<i64>,
+    pub owner: Option<StackExchangeOwner>,
+    /// Question body as HTML
+    pub body: Option<String>,
+}
+
+impl StackExchangeQuestion {
+    /// The title with HTML entities decoded
+    pub fn plain_title(&self) -> String {
+        html_to_text(&self.title)
+    }
+
+    /// Convert to the provider-independent result format
+    ///
+    /// `raw` carries `score`, `answer_count`, `is_answered`, `tags` and the
+    /// other question fields, without the HTML body.
+    pub fn to_search_result(&self, site: &str) -> SearchResultType {
+        let snippet = self
+            .body
+            .as_deref()
+            .map(html_to_text)
+            .map(|body| truncate_text(&body, SNIPPET_LENGTH));
+
+        let mut raw = serde_json::to_value(Self {
+            body: None,
+            ..self.clone()
+        })
+        .unwrap_or_default();
+        raw["site"] = site.into();
+        raw["has_accepted_answer"] = self.accepted_answer_id.is_some().into();
+
+        SearchResultType {
+            domain: crate::utils::http::extract_domain(&self.link),
+            url: self.link.clone(),
+            title: self.plain_title(),
+            snippet: snippet.filter(|snippet| !snippet.is_empty()),
+            published_date: DateTime::from_timestamp(self.creation_date, 0)
+                .map(|date| date.format("%Y-%m-%d").to_string()),
+            provider: Some("stackexchange".to_string()),
+            raw: Some(raw),
+        }
+    }
+}
+
+/// One page of search results
+#[derive(Debug, Clone)]
+pub struct StackExchangePage {
+    pub questions: Vec<StackExchangeQuestion>,
+    pub has_more: bool,
+    pub quota: StackExchangeQuota,
+    /// Seconds the API asked us to wait before calling it again
+    pub backoff: Option<u64>,
+}
+
+/// The common response wrapper, which also carries errors
+#[derive(Debug, Deserialize)]
+struct Wrapper {
+    #[serde(default)]
+    items: Vec<StackExchangeQuestion>,
+    #[serde(default)]
+    has_more: bool,
+    #[serde(default)]
+    quota_max: u32,
+    #[serde(default)]
+    quota_remaining: u32,
+    backoff: Option<u64>,
+    error_id: Option<u32>,
+    error_name: Option<String>,
+    error_message: Option<String>,
+}
+
+/// Stack Exchange search provider
+#[derive(Debug)]
+pub struct StackExchangeProvider {
+    config: StackExchangeConfig,
+    http_client: HttpClient,
+    /// Earliest time the API allows the next request
+    backoff_until: Mutex<Option<Instant>>,
+    quota: std::sync::Mutex<Option<StackExchangeQuota>>,
+}
+
+impl StackExchangeProvider {
+    pub fn new() -> Self {
+        Self::with_config(StackExchangeConfig::default())
+    }
+
+    /// Search a site other than Stack Overflow
+    pub fn with_site(site: impl Into<String>) -> Self {
+        Self::with_config(StackExchangeConfig {
+            site: site.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_config(config: StackExchangeConfig) -> Self {
+        Self {
+            config,
+            http_client: HttpClient::new(),
+            backoff_until: Mutex::new(None),
+            quota: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Quota reported by the most recent response
+    pub fn quota(&self) -> Option<StackExchangeQuota> {
+        *self
+            .quota
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Search questions and return the typed page
+    pub async fn search_questions(
+        &self,
+        options: &SearchOptions,
+    ) -> SearchResult<StackExchangePage> {
+        self.config.validate()?;
+        let query = options.query.trim();
+        if query.is_empty() && self.config.tagged.is_empty() {
+            return Err(SearchError::InvalidInput(
+                "Stack Exchange search requires a query or tags".to_string(),
+            ));
+        }
+
+        let sort = match (&self.config.sort, &options.sort_by) {
+            (Some(sort), _) => sort.as_str(),
+            (None, Some(SortBy::LastUpdatedDate)) => "activity",
+            (None, Some(SortBy::SubmittedDate)) => "creation",
+            (None, Some(SortBy::Relevance) | None) => "relevance",
+        };
+        let order = match options.sort_order {
+            Some(SortOrder::Ascending) => "asc",
+            Some(SortOrder::Descending) | None => "desc",
+        };
+
+        let mut url = Url::parse(&format!(
+            "{}/search/advanced",
+            self.config.base_url.trim_end_matches('/')
+        ))?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs
+                .append_pair("site", self.config.site.trim())
+                .append_pair("sort", sort)
+                .append_pair("order", order)
+                .append_pair("page", &options.page.unwrap_or(1).max(1).to_string())
+                .append_pair(
+                    "pagesize",
+                    &options
+                        .max_results
+                        .unwrap_or(10)
+                        .clamp(1, MAX_PAGE_SIZE)
+                        .to_string(),
+                )
+                .append_pair("filter", "withbody");
+            if !query.is_empty() {
+                pairs.append_pair("q", query);
+            }
+            if !self.config.tagged.is_empty() {
+                pairs.append_pair("tagged", &self.config.tagged.join(";"));
+            }
+            if let Some(accepted) = self.config.accepted {
+                pairs.append_pair("accepted", if accepted { "True" } else { "False" });
+            }
+            if let Some(key) = &self.config.key {
+                pairs.append_pair("key", key);
+            }
+        }
+
+        let wrapper = self.get(&url).await?;
+        Ok(StackExchangePage {
+            questions: wrapper.items,
+            has_more: wrapper.has_more,
+            quota: StackExchangeQuota {
+                max: wrapper.quota_max,
+                remaining: wrapper.quota_remaining,
+            },
+            backoff: wrapper.backoff,
+        })
+    }
+
+    /// Send a request once any requested backoff has passed
+    ///
+    /// Errors arrive as a wrapper with `error_id` set, usually with HTTP 400,
+    /// so the body is parsed whatever the status.
+    async fn get(&self, url: &Url) -> SearchResult<Wrapper> {
+        let mut backoff_until = self.backoff_until.lock().await;
+        if let Some(until) = *backoff_until {
+            tokio::time::sleep_until(until.into()).await;
+        }
+
+        let response = self
+            .http_client
+            .get_response_with_headers(url.as_str(), HashMap::new())
+            .await?;
+        let status = response.status();
+        let body = response.text().await?;
+        let wrapper: Wrapper = serde_json::from_str(&body).map_err(|error| {
+            if status.is_success() {
+                SearchError::ParseError(format!("Invalid Stack Exchange response: {error}"))
+            } else {
+                SearchError::HttpError {
+                    message: format!("Request failed with status: {status}"),
+                    status_code: Some(status.as_u16()),
+                    response_body: Some(body.clone()),
+                }
+            }
+        })?;
+
+        *backoff_until = wrapper.backoff.and_then(backoff_deadline);
+        if wrapper.quota_max > 0 {
+            *self
+                .quota
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(StackExchangeQuota {
+                max: wrapper.quota_max,
+                remaining: wrapper.quota_remaining,
+            });
+        }
+
+        match wrapper.error_id {
+            None => Ok(wrapper),
+            Some(error_id) => Err(api_error(
+                error_id,
+                wrapper.error_name.as_deref().unwrap_or_default(),
+                wrapper.error_message.as_deref().unwrap_or_default(),
+            )),
+        }
+    }
+}
+
+impl Default for StackExchangeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Map an API error to the matching `SearchError`
+///
+/// See <https://api.stackexchange.com/docs/error-handling>.
+fn api_error(error_id: u32, name: &str, message: &str) -> SearchError {
+    let message = format!("Stack Exchange {name}: {message}");
+    match error_id {
+        400 | 404 => SearchError::InvalidInput(message),
+        401..=403 | 405 | 406 => SearchError::AuthenticationError(message),
+        502 => SearchError::RateLimit(message),
+        _ => SearchError::ProviderError(message),
+    }
+}
+
+#[async_trait::async_trait]
+impl SearchProvider for StackExchangeProvider {
+    fn name(&self) -> &str {
+        "stackexchange"
+    }
+
+    async fn search(&self, options: &SearchOptions) -> SearchResult<Vec<SearchResultType>> {
+        Ok(self.search_response(options).await?.results)
+    }
+
+    async fn search_response(&self, options: &SearchOptions) -> SearchResult<SearchResponse> {
+        let page = self.search_questions(options).await?;
+        Ok(SearchResponse {
+            results: page
+                .questions
+                .iter()
+                .map(|question| question.to_search_result(&self.config.site))
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    fn config(&self) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("provider".to_string(), "stackexchange".to_string());
+        config.insert("base_url".to_string(), self.config.base_url.clone());
+        config.insert("site".to_string(), self.config.site.clone());
+        if let Some(quota) = self.quota() {
+            config.insert(
+                "quota_remaining".to_string(),
+                format!("{}/{}", quota.remaining, quota.max),
+            );
+        }
+        config
+    }
+}
+
+/// When a `backoff` of `seconds` ends, capped at [`MAX_BACKOFF`]
+fn backoff_deadline(seconds: u64) -> Option<Instant> {
+    let requested = Duration::from_secs(seconds);
+    if requested > MAX_BACKOFF {
+        log::warn!(
+            "Stack Exchange asked for a {seconds}s backoff; waiting {}s",
+            MAX_BACKOFF.as_secs()
+        );
+    }
+    Instant::now().checked_add(requested.min(MAX_BACKOFF))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_question_to_search_result() {
+        let question: StackExchangeQuestion = serde_json::from_str(
+            r#"{
+                "question_id": 11227809,
+                "title": "Why is processing a sorted array faster than an &quot;unsorted&quot; one?",
+                "link": "https://stackoverflow.com/questions/11227809/why-is-processing-a-sorted-array-faster",
+                "tags": ["java", "c++", "performance"],
+                "score": 27000,
+                "answer_count": 26,
+                "is_answered": true,
+                "accepted_answer_id": 11227902,
+                "view_count": 1900000,
+                "creation_date": 1340805096,
+                "owner": {"display_name": "GManNickG"},
+                "body": "<p>Here is a piece of <code>C++</code> code &amp; more.</p>\n<p>Second.</p>"
+            }"#,
+        )
+        .unwrap();
+
+        let result = question.to_search_result("stackoverflow");
+        assert_eq!(
+            result.title,
+            "Why is processing a sorted array faster than an \"unsorted\" one?"
+        );
+        assert_eq!(
+            result.snippet.as_deref(),
+            Some("Here is a piece of C++ code & more. Second.")
+        );
+        assert_eq!(result.published_date.as_deref(), Some("2012-06-27"));
+        let raw = result.raw.unwrap();
+        assert_eq!(raw["score"], 27000);
+        assert_eq!(raw["has_accepted_answer"], true);
+        assert!(raw["body"].is_null());
+    }
+
+    #[test]
+    fn test_api_error_mapping() {
+        assert!(matches!(
+            api_error(502, "throttle_violation", "too many requests"),
+            SearchError::RateLimit(_)
+        ));
+        assert!(matches!(
+            api_error(400, "bad_parameter", "site is required"),
+            SearchError::InvalidInput(_)
+        ));
+        assert!(matches!(
+            api_error(403, "access_denied", "denied"),
+            SearchError::AuthenticationError(_)
+        ));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let deadline = backoff_deadline(u64::MAX).unwrap();
+        assert!(deadline <= Instant::now() + MAX_BACKOFF);
+
+        let deadline = backoff_deadline(2).unwrap();
+        assert!(deadline > Instant::now() + Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,561 @@
+//! Resolve mixed scholarly identifiers into full search results
+//!
+//! DOIs are resolved through content negotiation on doi.org (CSL-JSON), arXiv
+//! IDs through the arXiv API, PMIDs through PubMed and Semantic Scholar IDs
+//! through the Graph API.
+//!
+//! ```rust,no_run
+//! use websearch::resolver::Resolver;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let resolver = Resolver::new();
+//! let results = resolver
+//!     .resolve_all(&["10.1038/nature14539", "arXiv:1706.03762", "PMID:31452104"])
+//!     .await;
+//! for result in results {
+//!     match result {
+//!         Ok(paper) => println!("{}: {}", paper.title, paper.url),
+//!         Err(error) => eprintln!("{error}"),
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    error::{SearchError, SearchResult},
+    providers::{
+        arxiv::{ArxivConfig, ArxivId, ArxivPaper, ArxivProvider},
+        crossref::CrossrefDate,
+        pubmed::{PubMedConfig, PubMedProvider},
+        semantic_scholar::{SemanticScholarConfig, SemanticScholarProvider},
+    },
+    types::{ProviderConfig, SearchOptions, SearchResult as SearchResultType},
+    utils::http::{normalize_text, HttpClient},
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use url::Url;
+
+/// A recognised scholarly identifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaperIdentifier {
+    /// Bare DOI, e.g. `10.1038/nature14539`
+    Doi(String),
+    Arxiv(ArxivId),
+    /// PubMed ID
+    Pmid(String),
+    /// Semantic Scholar paper ID (40 hex characters) or `CorpusId:<n>`
+    SemanticScholar(String),
+}
+
+impl PaperIdentifier {
+    /// Recognise an identifier from its prefix, URL or shape
+    ///
+    /// Accepts `doi:`, `arXiv:`, `PMID:`, `S2:` and `CorpusId:` prefixes as
+    /// well as doi.org, arxiv.org, pubmed and semanticscholar.org URLs.
+    /// Unprefixed all-digit strings are taken to be PMIDs.
+    pub fn parse(input: &str) -> SearchResult<Self> {
+        let input = input.trim();
+        let invalid =
+            || SearchError::InvalidInput(format!("Unrecognised paper identifier: '{input}'"));
+        let lower = input.to_ascii_lowercase();
+
+        if let Some(doi) = strip_prefix_ignore_case(input, "doi:").or_else(|| {
+            [
+                "https://doi.org/",
+                "http://doi.org/",
+                "https://dx.doi.org/",
+                "http://dx.doi.org/",
+            ]
+            .into_iter()
+            .find_map(|prefix| strip_prefix_ignore_case(input, prefix))
+        }) {
+            return Ok(Self::Doi(doi.trim().to_string()));
+        }
+        if lower.starts_with("10.") && input.contains('/') {
+            return Ok(Self::Doi(input.to_string()));
+        }
+
+        if let Some(pmid) = strip_prefix_ignore_case(input, "pmid:").or_else(|| {
+            lower
+                .contains("pubmed.ncbi.nlm.nih.gov/")
+                .then(|| input.trim_end_matches('/').rsplit('/').next())
+                .flatten()
+        }) {
+            let pmid = pmid.trim();
+            return if is_pmid(pmid) {
+                Ok(Self::Pmid(pmid.to_string()))
+            } else {
+                Err(invalid())
+            };
+        }
+
+        if let Some(id) = strip_prefix_ignore_case(input, "s2:") {
+            return Ok(Self::SemanticScholar(id.trim().to_string()));
+        }
+        if lower.starts_with("corpusid:") {
+            return Ok(Self::SemanticScholar(format!(
+                "CorpusId:{}",
+                input[9..].trim()
+            )));
+        }
+        if lower.contains("semanticscholar.org/paper/") {
+            let id = input
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default();
+            return if is_s2_paper_id(id) {
+                Ok(Self::SemanticScholar(id.to_string()))
+            } else {
+                Err(invalid())
+            };
+        }
+
+        if let Ok(id) = ArxivId::parse(input) {
+            return Ok(Self::Arxiv(id));
+        }
+        if is_pmid(input) {
+            return Ok(Self::Pmid(input.to_string()));
+        }
+        if is_s2_paper_id(input) {
+            return Ok(Self::SemanticScholar(input.to_ascii_lowercase()));
+        }
+
+        Err(invalid())
+    }
+}
+
+impl fmt::Display for PaperIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaperIdentifier::Doi(doi) => write!(f, "doi:{doi}"),
+            PaperIdentifier::Arxiv(id) => write!(f, "arXiv:{id}"),
+            PaperIdentifier::Pmid(pmid) => write!(f, "PMID:{pmid}"),
+            PaperIdentifier::SemanticScholar(id) => write!(f, "S2:{id}"),
+        }
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(input: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = input.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &input[prefix.len()..])
+}
+
+fn is_pmid(value: &str) -> bool {
+    !value.is_empty() && value.len() <= 9 && value.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_s2_paper_id(value: &str) -> bool {
+    value.len() == 40 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Resolver configuration
+#[derive(Debug, Clone)]
+pub struct ResolverConfig {
+    /// DOI resolver used for content negotiation
+    pub doi_base_url: String,
+    pub arxiv: ArxivConfig,
+    pub pubmed: PubMedConfig,
+    pub semantic_scholar: SemanticScholarConfig,
+}
+
+impl Default for ResolverConfig {
+    fn default() -> Self {
+        Self {
+            doi_base_url: "https://doi.org".to_string(),
+            arxiv: ArxivConfig::default(),
+            pubmed: PubMedConfig::default(),
+            semantic_scholar: SemanticScholarConfig::default(),
+        }
+    }
+}
+
+impl ProviderConfig for ResolverConfig {
+    fn validate(&self) -> Result<(), SearchError> {
+        if self.doi_base_url.is_empty() {
+            return Err(SearchError::ConfigError(
+                "DOI resolver URL is required".to_string(),
+            ));
+        }
+        self.arxiv.validate()?;
+        self.pubmed.validate()?;
+        self.semantic_scholar.validate()
+    }
+
+    fn base_url(&self) -> &str {
+        &self.doi_base_url
+    }
+}
+
+/// Turns identifiers into normalized [`SearchResult`](crate::SearchResult)s
+#[derive(Debug)]
+pub struct Resolver {
+    doi_base_url: String,
+    http_client: HttpClient,
+    arxiv: ArxivProvider,
+    pubmed: PubMedProvider,
+    semantic_scholar: SemanticScholarProvider,
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Self::with_config(ResolverConfig::default())
+    }
+
+    pub fn with_config(config: ResolverConfig) -> Self {
+        Self {
+            doi_base_url: config.doi_base_url,
+            http_client: HttpClient::new(),
+            arxiv: ArxivProvider::with_config(config.arxiv),
+            pubmed: PubMedProvider::with_config(config.pubmed),
+            semantic_scholar: SemanticScholarProvider::with_config(config.semantic_scholar),
+        }
+    }
+
+    /// Resolve one identifier in any supported form
+    pub async fn resolve(&self, identifier: &str) -> SearchResult<SearchResultType> {
+        self.resolve_identifier(&PaperIdentifier::parse(identifier)?)
+            .await
+    }
+
+    /// Resolve an already-recognised identifier
+    pub async fn resolve_identifier(
+        &self,
+        identifier: &PaperIdentifier,
+    ) -> SearchResult<SearchResultType> {
+        match identifier {
+            PaperIdentifier::Doi(doi) => self.resolve_doi(doi).await,
+            PaperIdentifier::Arxiv(id) => {
+                let mut papers = self.arxiv_papers(std::slice::from_ref(id)).await?;
+                papers
+                    .remove(&id.to_string())
+                    .map(|paper| paper.to_search_result())
+                    .ok_or_else(|| not_found(identifier))
+            }
+            PaperIdentifier::Pmid(pmid) => self
+                .pubmed
+                .articles(std::slice::from_ref(pmid))
+                .await?
+                .first()
+                .map(|article| article.to_search_result())
+                .ok_or_else(|| not_found(identifier)),
+            PaperIdentifier::SemanticScholar(id) => {
+                Ok(self.semantic_scholar.paper(id).await?.to_search_result())
+            }
+        }
+    }
+
+    /// Resolve a mixed list, keeping input order
+    ///
+    /// Each entry succeeds or fails on its own. arXiv IDs are fetched in a
+    /// single request and PMIDs in another; DOIs and Semantic Scholar IDs are
+    /// resolved one by one.
+    pub async fn resolve_all(&self, identifiers: &[&str]) -> Vec<SearchResult<SearchResultType>> {
+        let parsed: Vec<SearchResult<PaperIdentifier>> = identifiers
+            .iter()
+            .map(|identifier| PaperIdentifier::parse(identifier))
+            .collect();
+
+        let arxiv_ids: Vec<ArxivId> = parsed
+            .iter()
+            .filter_map(|identifier| match identifier {
+                Ok(PaperIdentifier::Arxiv(id)) => Some(id.clone()),
+                _ => None,
+            })
+            .collect();
+        let pmids: Vec<String> = parsed
+            .iter()
+            .filter_map(|identifier| match identifier {
+                Ok(PaperIdentifier::Pmid(pmid)) => Some(pmid.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let arxiv_papers = match arxiv_ids.is_empty() {
+            true => Ok(HashMap::new()),
+            false => self.arxiv_papers(&arxiv_ids).await,
+        };
+        let pubmed_articles = match pmids.is_empty() {
+            true => Ok(HashMap::new()),
+            false => self.pubmed.articles(&pmids).await.map(|articles| {
+                articles
+                    .into_iter()
+                    .map(|article| (article.pmid.clone(), article.to_search_result()))
+                    .collect::<HashMap<_, _>>()
+            }),
+        };
+
+        let mut results = Vec::with_capacity(parsed.len());
+        for identifier in parsed {
+            let result = match identifier {
+                Err(error) => Err(error),
+                Ok(PaperIdentifier::Arxiv(id)) => match &arxiv_papers {
+                    Ok(papers) => papers
+                        .get(&id.to_string())
+                        .map(ArxivPaper::to_search_result)
+                        .ok_or_else(|| not_found(&PaperIdentifier::Arxiv(id))),
+                    Err(error) => Err(error.clone()),
+                },
+                Ok(PaperIdentifier::Pmid(pmid)) => match &pubmed_articles {
+                    Ok(articles) => articles
+                        .get(&pmid)
+                        .cloned()
+                        .ok_or_else(|| not_found(&PaperIdentifier::Pmid(pmid))),
+                    Err(error) => Err(error.clone()),
+                },
+                Ok(identifier) => self.resolve_identifier(&identifier).await,
+            };
+            results.push(result);
+        }
+        results
+    }
+
+    /// Fetch arXiv papers keyed by the identifier as requested
+    async fn arxiv_papers(&self, ids: &[ArxivId]) -> SearchResult<HashMap<String, ArxivPaper>> {
+        let options = SearchOptions {
+            id_list: Some(
+                ids.iter()
+                    .map(ArxivId::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            max_results: Some(ids.len() as u32),
+            ..Default::default()
+        };
+        let papers = self.arxiv.search_papers(&options).await?;
+
+        // An unversioned request returns the latest version, so match on the
+        // base identifier when the request did not pin one
+        let mut by_id = HashMap::new();
+        for id in ids {
+            let paper = papers.iter().find(|paper| match id.version() {
+                Some(_) => &paper.id == id,
+                None => paper.id.base() == id.base(),
+            });
+            if let Some(paper) = paper {
+                by_id.insert(id.to_string(), paper.clone());
+            }
+        }
+        Ok(by_id)
+    }
+
+    /// Resolve a DOI to CSL-JSON through content negotiation
+    async fn resolve_doi(&self, doi: &str) -> SearchResult<SearchResultType> {
+        // DOIs may contain `#`, `?` and `%`, so encode each segment
+        let mut url = Url::parse(self.doi_base_url.trim_end_matches('/'))?;
+        url.path_segments_mut()
+            .map_err(|_| SearchError::ConfigError("Invalid DOI base URL".to_string()))?
+            .pop_if_empty()
+            .extend(doi.split('/'));
+        let mut headers = HashMap::new();
+        headers.insert(
+            "Accept".to_string(),
+            "application/vnd.citationstyles.csl+json".to_string(),
+        );
+
+        let item: CslItem = self
+            .http_client
+            .get_json_with_headers(url.as_str(), headers)
+            .await
+            .map_err(|error| match error {
+                SearchError::HttpError {
+                    status_code: Some(404),
+                    ..
+                } => SearchError::InvalidInput(format!("DOI not found: {doi}")),
+                other => other,
+            })?;
+        Ok(item.into_search_result(doi))
+    }
+}
+
+impl Default for Resolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn not_found(identifier: &PaperIdentifier) -> SearchError {
+    SearchError::InvalidInput(format!("No record found for {identifier}"))
+}
+
+/// The subset of a CSL-JSON item used to build a result
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CslItem {
+    #[serde(default)]
+    title: Value,
+    #[serde(default)]
+    author: Vec<CslName>,
+    #[serde(default)]
+    container_title: Value,
+    issued: Option<CrossrefDate>,
+    #[serde(rename = "DOI")]
+    doi: Option<String>,
+    #[serde(rename = "URL")]
+    url: Option<String>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+    publisher: Option<String>,
+    #[serde(rename = "type")]
+    item_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CslName {
+    family: Option<String>,
+    given: Option<String>,
+    literal: Option<String>,
+}
+
+/// CSL fields may be a string or a list of strings depending on the agency
+fn first_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Array(items) => items.iter().find_map(Value::as_str).map(str::to_string),
+        _ => None,
+    }
+    .map(|text| normalize_text(&text))
+    .filter(|text| !text.is_empty())
+}
+
+impl CslItem {
+    fn into_search_result(self, requested_doi: &str) -> SearchResultType {
+        let doi = self.doi.unwrap_or_else(|| requested_doi.to_string());
+        let url = self.url.unwrap_or_else(|| format!("https://doi.org/{doi}"));
+        let venue = first_string(&self.container_title);
+        let authors: Vec<String> = self
+            .author
+            .iter()
+            .filter_map(|name| match (&name.family, &name.given) {
+                (Some(family), Some(given)) => Some(format!("{family}, {given}")),
+                (Some(family), None) => Some(family.clone()),
+                _ => name.literal.clone(),
+            })
+            .collect();
+
+        let mut raw = serde_json::json!({
+            "doi": doi,
+            "authors": authors,
+        });
+        if let Some(venue) = &venue {
+            raw["venue"] = venue.clone().into();
+        }
+        if let Some(year) = self.issued.as_ref().and_then(CrossrefDate::year) {
+            raw["year"] = year.into();
+        }
+        if let Some(publisher) = self.publisher {
+            raw["publisher"] = publisher.into();
+        }
+        if let Some(item_type) = self.item_type {
+            raw["type"] = item_type.into();
+        }
+
+        SearchResultType {
+            domain: crate::utils::http::extract_domain(&url),
+            url,
+            title: first_string(&self.title).unwrap_or_default(),
+            snippet: self
+                .abstract_text
+                .as_deref()
+                .map(crate::providers::crossref::abstract_text)
+                .filter(|text| !text.is_empty())
+                .or(venue),
+            published_date: self.issued.as_ref().and_then(CrossrefDate::to_iso),
+            provider: Some("doi".to_string()),
+            raw: Some(raw),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_identifiers() {
+        let doi = |value: &str| PaperIdentifier::Doi(value.to_string());
+        assert_eq!(
+            PaperIdentifier::parse("10.1038/nature14539").unwrap(),
+            doi("10.1038/nature14539")
+        );
+        assert_eq!(
+            PaperIdentifier::parse("https://doi.org/10.1038/nature14539").unwrap(),
+            doi("10.1038/nature14539")
+        );
+        assert_eq!(
+            PaperIdentifier::parse("DOI: 10.1038/nature14539").unwrap(),
+            doi("10.1038/nature14539")
+        );
+
+        assert_eq!(
+            PaperIdentifier::parse("arXiv:1706.03762v5").unwrap(),
+            PaperIdentifier::Arxiv(ArxivId::parse("1706.03762v5").unwrap())
+        );
+        assert_eq!(
+            PaperIdentifier::parse("hep-th/9901001").unwrap(),
+            PaperIdentifier::Arxiv(ArxivId::parse("hep-th/9901001").unwrap())
+        );
+
+        let pmid = PaperIdentifier::Pmid("31452104".to_string());
+        assert_eq!(PaperIdentifier::parse("PMID:31452104").unwrap(), pmid);
+        assert_eq!(PaperIdentifier::parse("31452104").unwrap(), pmid);
+        assert_eq!(
+            PaperIdentifier::parse("https://pubmed.ncbi.nlm.nih.gov/31452104/").unwrap(),
+            pmid
+        );
+
+        let s2 = "204e3073870fae3d05bcbc2f6a8e263d9b72e776";
+        assert_eq!(
+            PaperIdentifier::parse(s2).unwrap(),
+            PaperIdentifier::SemanticScholar(s2.to_string())
+        );
+        assert_eq!(
+            PaperIdentifier::parse(&format!(
+                "https://www.semanticscholar.org/paper/Attention-is-All-you-Need/{s2}"
+            ))
+            .unwrap(),
+            PaperIdentifier::SemanticScholar(s2.to_string())
+        );
+        assert_eq!(
+            PaperIdentifier::parse("CorpusId:13756489").unwrap(),
+            PaperIdentifier::SemanticScholar("CorpusId:13756489".to_string())
+        );
+
+        assert!(PaperIdentifier::parse("not an id").is_err());
+        assert!(PaperIdentifier::parse("PMID:abc").is_err());
+    }
+
+    #[test]
+    fn test_csl_item_to_search_result() {
+        let item: CslItem = serde_json::from_str(
+            r#"{
+                "type": "article-journal",
+                "title": "Deep learning",
+                "author": [{"family": "LeCun", "given": "Yann"}, {"literal": "The Consortium"}],
+                "container-title": "Nature",
+                "issued": {"date-parts": [[2015, 5, 27]]},
+                "DOI": "10.1038/nature14539",
+                "URL": "http://dx.doi.org/10.1038/nature14539",
+                "publisher": "Springer"
+            }"#,
+        )
+        .unwrap();
+        let result = item.into_search_result("10.1038/NATURE14539");
+
+        assert_eq!(result.title, "Deep learning");
+        assert_eq!(result.snippet.as_deref(), Some("Nature"));
+        assert_eq!(result.published_date.as_deref(), Some("2015-05-27"));
+        let raw = result.raw.as_ref().unwrap();
+        assert_eq!(raw["doi"], "10.1038/nature14539");
+        assert_eq!(raw["authors"][1], "The Consortium");
+
+        let citation = crate::citation::Citation::from_search_result(&result).unwrap();
+        assert_eq!(citation.key, "lecun2015deep");
+    }
+}
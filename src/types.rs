@@ -25,6 +25,34 @@ pub struct SearchResult {
     pub raw: Option<serde_json::Value>,
 }
 
+/// Search results together with page-level information returned by a provider
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchResponse {
+    /// The search results
+    pub results: Vec<SearchResult>,
+    /// Query the provider actually searched for ("Including results for ...")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corrected_query: Option<String>,
+    /// Spelling suggestion offered without being applied ("Did you mean ...")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_query: Option<String>,
+    /// Related follow-up searches
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_searches: Vec<String>,
+    /// Total number of matches reported by the provider, beyond this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_results: Option<u64>,
+}
+
+impl From<Vec<SearchResult>> for SearchResponse {
+    fn from(results: Vec<SearchResult>) -> Self {
+        Self {
+            results,
+            ..Default::default()
+        }
+    }
+}
+
 /// Debug options for the search SDK
 #[derive(Debug, Clone, Default)]
 pub struct DebugOptions {
@@ -93,8 +121,11 @@ impl fmt::Display for SortOrder {
 pub struct SearchOptions {
     /// The search query text
     pub query: String,
-    /// (Arxiv specific) A comma-delimited list of Arxiv IDs to fetch
+    /// A comma-delimited list of record IDs to fetch (ArXiv IDs or PubMed PMIDs)
     pub id_list: Option<String>,
+    /// Query in the provider's own syntax, used instead of `query` when set
+    /// (ArXiv `search_query`, e.g. from `ArxivQuery::render`)
+    pub native_query: Option<String>,
     /// Maximum number of results to return
     pub max_results: Option<u32>,
     /// Language/locale for results
@@ -107,9 +138,9 @@ pub struct SearchOptions {
     pub page: Option<u32>,
     /// (Arxiv specific) The starting index for results (pagination offset)
     pub start: Option<u32>,
-    /// (Arxiv specific) Sort order for results
+    /// Sort order for results (ArXiv, GitHub, Stack Exchange, Hacker News)
     pub sort_by: Option<SortBy>,
-    /// (Arxiv specific) Sort direction
+    /// Sort direction (ArXiv, GitHub, Stack Exchange)
     pub sort_order: Option<SortOrder>,
     /// Custom timeout in milliseconds
     pub timeout: Option<u64>,
@@ -124,6 +155,7 @@ impl Default for SearchOptions {
         Self {
             query: String::new(),
             id_list: None,
+            native_query: None,
             max_results: Some(10),
             language: None,
             region: None,
@@ -148,12 +180,28 @@ pub trait SearchProvider: Send + Sync + std::fmt::Debug {
     /// Search method implementation
     async fn search(&self, options: &SearchOptions) -> Result<Vec<SearchResult>, SearchError>;
 
+    /// Search and return the results with any page-level information
+    /// (spelling corrections, related searches) the provider exposes
+    async fn search_response(
+        &self,
+        options: &SearchOptions,
+    ) -> Result<SearchResponse, SearchError> {
+        self.search(options).await.map(SearchResponse::from)
+    }
+
     /// Get provider configuration (for debugging/logging)
     fn config(&self) -> HashMap<String, String> {
         HashMap::new()
     }
 }
 
+/// Trait for search providers that can also complete a partial query
+#[async_trait::async_trait]
+pub trait SuggestProvider: SearchProvider {
+    /// Return completions for `query`, best match first
+    async fn suggest(&self, query: &str) -> Result<Vec<String>, SearchError>;
+}
+
 /// Dummy provider for default implementation (should not be used)
 #[derive(Debug)]
 struct DummyProvider;
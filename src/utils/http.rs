@@ -2,7 +2,7 @@
 
 use crate::error::{SearchError, SearchResult};
 use reqwest::{Client, Response};
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
 use url::Url;
@@ -100,6 +100,22 @@ impl HttpClient {
         self.handle_response_text(response).await
     }
 
+    /// Make a GET request with headers and return the response without
+    /// checking its status, for APIs that report limits in response headers
+    pub async fn get_response_with_headers(
+        &self,
+        url: &str,
+        headers: HashMap<String, String>,
+    ) -> SearchResult<Response> {
+        let mut request = self.client.get(url).timeout(self.default_timeout);
+
+        for (key, value) in headers {
+            request = request.header(key, value);
+        }
+
+        Ok(request.send().await?)
+    }
+
     /// Make a POST request with form data and deserialize the JSON response
     pub async fn post_form_json<T>(
         &self,
@@ -158,6 +174,32 @@ impl HttpClient {
         self.handle_response_text(response).await
     }
 
+    /// Make a POST request with a JSON body and headers and deserialize the
+    /// JSON response
+    pub async fn post_json_with_headers<B, T>(
+        &self,
+        url: &str,
+        body: &B,
+        headers: HashMap<String, String>,
+    ) -> SearchResult<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let mut request = self
+            .client
+            .post(url)
+            .timeout(self.default_timeout)
+            .json(body);
+
+        for (key, value) in headers {
+            request = request.header(key, value);
+        }
+
+        let response = request.send().await?;
+        self.handle_response_json(response).await
+    }
+
     /// Handle HTTP response and deserialize as JSON
     async fn handle_response_json<T>(&self, response: Response) -> SearchResult<T>
     where
@@ -229,6 +271,68 @@ pub fn normalize_text(text: &str) -> String {
     text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
+/// Text content of an HTML fragment, with entities decoded
+///
+/// Block elements such as `<p>` and `<br>` become spaces so paragraphs
+/// don't run together; script and style contents are dropped. Namespaced
+/// JATS tags (`<jats:p>`, `<jats:sec>`) are treated like their plain names.
+pub fn html_to_text(html: &str) -> String {
+    const BLOCK_TAGS: [&str; 9] = [
+        "p",
+        "br",
+        "div",
+        "li",
+        "pre",
+        "blockquote",
+        "title",
+        "sec",
+        "list-item",
+    ];
+
+    let fragment = scraper::Html::parse_fragment(html);
+    let mut text = String::with_capacity(html.len());
+    for node in fragment.root_element().descendants() {
+        match node.value() {
+            scraper::Node::Text(content) => {
+                let in_script = node
+                    .parent()
+                    .and_then(|parent| parent.value().as_element())
+                    .is_some_and(|parent| matches!(parent.name(), "script" | "style"));
+                if !in_script {
+                    text.push_str(content);
+                }
+            }
+            scraper::Node::Element(element)
+                if element
+                    .name()
+                    .rsplit(':')
+                    .next()
+                    .is_some_and(|name| BLOCK_TAGS.contains(&name)) =>
+            {
+                text.push(' ')
+            }
+            _ => {}
+        }
+    }
+    normalize_text(&text)
+}
+
+/// Cut text at a word boundary near `max_chars` characters
+///
+/// Text that already fits is returned unchanged; otherwise the cut text
+/// ends with `...`.
+pub fn truncate_text(text: &str, max_chars: usize) -> String {
+    let Some((end, next)) = text.char_indices().nth(max_chars) else {
+        return text.to_string();
+    };
+    let cut = &text[..end];
+    let cut = match cut.rfind(' ') {
+        Some(space) if !next.is_whitespace() => &cut[..space],
+        _ => cut,
+    };
+    format!("{}...", cut.trim_end())
+}
+
 /// Normalize URL by ensuring it has a proper scheme
 pub fn normalize_url(url: &str) -> String {
     if url.starts_with("//") {
@@ -0,0 +1,395 @@
+//! ArXiv provider tests against a local mock server
+
+use std::time::Duration;
+use websearch::error::SearchError;
+use websearch::providers::arxiv::fulltext::{FullTextConfig, FullTextFetcher};
+use websearch::providers::arxiv::oai::{HarvestOptions, OaiConfig, OaiHarvester};
+use websearch::providers::arxiv::{ArxivConfig, ArxivId, ArxivProvider};
+use websearch::types::{ProviderConfig, SearchOptions, SearchProvider};
+use wiremock::matchers::{header_regex, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn oai_page(id: &str, token: &str) -> String {
+    format!(
+        r#"<OAI-PMH xmlns="http://www.openarchives.org/OAI/2.0/">
+  <ListRecords>
+    <record>
+      <header><identifier>oai:arXiv.org:{id}</identifier></header>
+      <metadata>
+        <arXiv xmlns="http://arxiv.org/OAI/arXiv/">
+          <id>{id}</id>
+          <created>2024-03-01</created>
+          <authors><author><keyname>Doe</keyname><forenames>Jane</forenames></author></authors>
+          <title>Paper {id}</title>
+          <categories>cs.CL</categories>
+          <abstract>Abstract.</abstract>
+        </arXiv>
+      </metadata>
+    </record>
+    <resumptionToken cursor="0" completeListSize="2">{token}</resumptionToken>
+  </ListRecords>
+</OAI-PMH>"#
+    )
+}
+
+fn harvester(server: &MockServer) -> OaiHarvester {
+    OaiHarvester::with_config(OaiConfig {
+        base_url: format!("{}/oai2", server.uri()),
+        request_delay: Duration::ZERO,
+        retry_delay: Duration::ZERO,
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn test_oai_harvest_follows_resumption_tokens() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("verb", "ListRecords"))
+        .and(query_param("metadataPrefix", "arXiv"))
+        .and(query_param("set", "cs"))
+        .and(query_param("from", "2024-03-01"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(oai_page("2403.00001", "next|1")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("resumptionToken", "next|1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(oai_page("2403.00002", "")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let options = HarvestOptions {
+        set: Some("cs".to_string()),
+        from: chrono::NaiveDate::from_ymd_opt(2024, 3, 1),
+        ..Default::default()
+    };
+    let papers = harvester(&server).harvest(&options, None).await.unwrap();
+
+    let ids: Vec<String> = papers.iter().map(|paper| paper.id.to_string()).collect();
+    assert_eq!(ids, vec!["2403.00001", "2403.00002"]);
+    assert_eq!(papers[0].authors[0].name, "Jane Doe");
+}
+
+#[tokio::test]
+async fn test_oai_harvest_retries_after_503() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(oai_page("2403.00001", "")))
+        .mount(&server)
+        .await;
+
+    let page = harvester(&server)
+        .list_records(&HarvestOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(page.papers.len(), 1);
+    assert_eq!(page.resumption_token, None);
+}
+
+#[tokio::test]
+async fn test_oai_harvest_honours_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(oai_page("2403.00001", "")))
+        .mount(&server)
+        .await;
+
+    // retry_delay is zero, so any wait comes from the header
+    let started = std::time::Instant::now();
+    let page = harvester(&server)
+        .list_records(&HarvestOptions::default())
+        .await
+        .unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(900));
+    assert_eq!(page.papers.len(), 1);
+}
+
+#[tokio::test]
+async fn test_pdf_download_is_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/pdf/2403.00001v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.5 test".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let cache = tempfile::tempdir().unwrap();
+    let fetcher = FullTextFetcher::with_config(FullTextConfig {
+        cache_dir: cache.path().to_path_buf(),
+        pdf_base_url: format!("{}/pdf", server.uri()),
+        ..Default::default()
+    });
+    let id = ArxivId::parse("2403.00001v1").unwrap();
+
+    let first = fetcher.download_pdf(&id).await.unwrap();
+    let second = fetcher.download_pdf(&id).await.unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(std::fs::read(&first).unwrap(), b"%PDF-1.5 test");
+}
+
+#[tokio::test]
+async fn test_html_error_page_is_not_cached() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/pdf/2403.00001v1"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "text/html; charset=utf-8")
+                .set_body_string("<html><body>PDF being generated</body></html>"),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let cache = tempfile::tempdir().unwrap();
+    let fetcher = FullTextFetcher::with_config(FullTextConfig {
+        cache_dir: cache.path().to_path_buf(),
+        pdf_base_url: format!("{}/pdf", server.uri()),
+        request_delay: Duration::from_secs(1),
+        ..Default::default()
+    });
+    let id = ArxivId::parse("2403.00001v1").unwrap();
+
+    let started = std::time::Instant::now();
+    for _ in 0..2 {
+        let error = fetcher.download_pdf(&id).await.unwrap_err();
+        assert!(matches!(error, SearchError::ProviderError(_)), "{error:?}");
+    }
+
+    assert!(started.elapsed() >= Duration::from_millis(900));
+    assert_eq!(std::fs::read_dir(cache.path()).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_missing_pdftotext_is_a_config_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.5 test".to_vec()))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let cache = tempfile::tempdir().unwrap();
+    let fetcher = FullTextFetcher::with_config(FullTextConfig {
+        cache_dir: cache.path().to_path_buf(),
+        pdf_base_url: format!("{}/pdf", server.uri()),
+        pdftotext: "/nonexistent/pdftotext".into(),
+        ..Default::default()
+    });
+    let id = ArxivId::parse("2403.00001v1").unwrap();
+
+    // Nothing is downloaded when the PDF couldn't be converted anyway
+    match fetcher.extract_text(&id).await {
+        Err(SearchError::ConfigError(message)) => {
+            assert!(message.contains("pdftotext"), "{message}");
+            assert!(message.contains("not found"), "{message}");
+        }
+        other => panic!("expected a config error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_unversioned_ids_are_cached_by_latest_version() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/query"))
+        .and(query_param("id_list", "2403.00001"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(atom_feed(
+            &["2403.00001v3"],
+            1,
+            0,
+        )))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/pdf/2403.00001v3"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"%PDF-1.5 v3".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let cache = tempfile::tempdir().unwrap();
+    let fetcher = FullTextFetcher::with_config(FullTextConfig {
+        cache_dir: cache.path().to_path_buf(),
+        pdf_base_url: format!("{}/pdf", server.uri()),
+        arxiv: ArxivConfig {
+            base_url: format!("{}/api/query", server.uri()),
+            request_delay: Duration::ZERO,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let id = ArxivId::parse("2403.00001").unwrap();
+
+    let first = fetcher.download_pdf(&id).await.unwrap();
+    let second = fetcher.download_pdf(&id).await.unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(first, cache.path().join("2403.00001v3.pdf"));
+    assert_eq!(std::fs::read(&first).unwrap(), b"%PDF-1.5 v3");
+}
+
+fn atom_feed(ids: &[&str], total: u64, start: u64) -> String {
+    let entries: String = ids
+        .iter()
+        .map(|id| {
+            format!(
+                r#"<entry>
+    <id>http://arxiv.org/abs/{id}</id>
+    <updated>2024-03-02T00:00:00Z</updated>
+    <published>2024-03-01T00:00:00Z</published>
+    <title>Paper {id}</title>
+    <summary>Abstract.</summary>
+    <author><name>Jane Doe</name></author>
+    <link href="http://arxiv.org/abs/{id}" rel="alternate" type="text/html"/>
+    <link title="pdf" href="http://arxiv.org/pdf/{id}" rel="related" type="application/pdf"/>
+    <arxiv:primary_category term="cs.CL" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="cs.CL" scheme="http://arxiv.org/schemas/atom"/>
+  </entry>"#
+            )
+        })
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:opensearch="http://a9.com/-/spec/opensearch/1.1/" xmlns:arxiv="http://arxiv.org/schemas/atom">
+  <title>ArXiv Query</title>
+  <opensearch:totalResults>{total}</opensearch:totalResults>
+  <opensearch:startIndex>{start}</opensearch:startIndex>
+  <opensearch:itemsPerPage>{}</opensearch:itemsPerPage>
+  {entries}
+</feed>"#,
+        ids.len()
+    )
+}
+
+fn arxiv_provider(server: &MockServer) -> ArxivProvider {
+    ArxivProvider::with_config(ArxivConfig {
+        base_url: format!("{}/api/query", server.uri()),
+        contact_email: Some("lab@example.org".to_string()),
+        max_page_size: 2,
+        request_delay: Duration::ZERO,
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn test_arxiv_search_uses_configured_endpoint_and_user_agent() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/query"))
+        .and(query_param("search_query", "all:transformers"))
+        .and(query_param("max_results", "2"))
+        .and(header_regex(
+            "user-agent",
+            r"^websearch/.+ \(mailto:lab@example\.org\)$",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_string(atom_feed(
+            &["2403.00001v1"],
+            1,
+            0,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "transformers".to_string(),
+        max_results: Some(50),
+        ..Default::default()
+    };
+    let response = arxiv_provider(&server)
+        .search_response(&options)
+        .await
+        .unwrap();
+
+    assert_eq!(response.total_results, Some(1));
+    assert_eq!(response.results[0].title, "Paper 2403.00001v1");
+}
+
+#[tokio::test]
+async fn test_arxiv_search_all_papers_pages_through_results() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("start", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(atom_feed(
+            &["2403.00001v1", "2403.00002v1"],
+            3,
+            0,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("start", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(atom_feed(
+            &["2403.00003v1"],
+            3,
+            2,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "transformers".to_string(),
+        ..Default::default()
+    };
+    let papers = arxiv_provider(&server)
+        .search_all_papers(&options, None)
+        .await
+        .unwrap();
+
+    let ids: Vec<String> = papers.iter().map(|paper| paper.id.to_string()).collect();
+    assert_eq!(ids, vec!["2403.00001v1", "2403.00002v1", "2403.00003v1"]);
+}
+
+#[tokio::test]
+async fn test_arxiv_http_errors_keep_status() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "transformers".to_string(),
+        ..Default::default()
+    };
+    let error = arxiv_provider(&server).search(&options).await.unwrap_err();
+    assert!(matches!(
+        error,
+        SearchError::HttpError {
+            status_code: Some(503),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_arxiv_config_validation() {
+    let config = ArxivConfig {
+        max_page_size: 5000,
+        ..Default::default()
+    };
+    assert!(matches!(
+        config.validate(),
+        Err(SearchError::ConfigError(_))
+    ));
+    assert!(ArxivConfig::default().validate().is_ok());
+    assert!(ArxivConfig::default().base_url.starts_with("https://"));
+}
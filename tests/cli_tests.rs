@@ -9,7 +9,7 @@ const CLI_BINARY: &str = "websearch";
 /// Helper function to run CLI commands and capture output
 fn run_cli_command(args: &[&str]) -> (String, String, bool) {
     let output = Command::new("cargo")
-        .args(&["run", "--bin", CLI_BINARY, "--"])
+        .args(["run", "--bin", CLI_BINARY, "--"])
         .args(args)
         .output()
         .expect("Failed to execute CLI command");
@@ -25,7 +25,7 @@ fn run_cli_command(args: &[&str]) -> (String, String, bool) {
 fn cli_binary_exists() -> bool {
     // Try to build the binary first
     let build_output = Command::new("cargo")
-        .args(&["build", "--bin", CLI_BINARY])
+        .args(["build", "--bin", CLI_BINARY])
         .output()
         .expect("Failed to build CLI binary");
 
@@ -90,6 +90,14 @@ fn test_invalid_provider() {
     assert!(stderr.contains("invalid") || stdout.contains("invalid"));
 }
 
+#[test]
+fn test_local_provider_requires_index_dir() {
+    let (_stdout, stderr, success) = run_cli_command(&["deploy", "--provider", "local"]);
+
+    assert!(!success, "Local search without --index-dir should fail");
+    assert!(stderr.contains("--index-dir"), "{stderr}");
+}
+
 #[test]
 fn test_duckduckgo_search_dry_run() {
     // Test DuckDuckGo search which doesn't require API keys
@@ -105,7 +113,7 @@ fn test_duckduckgo_search_dry_run() {
     ]);
 
     if success {
-        assert!(stdout.len() > 0, "Should return some results");
+        assert!(!stdout.is_empty(), "Should return some results");
         assert!(stdout.contains("1."), "Should have numbered results");
     } else {
         // If it fails, it should be due to network/parsing, not configuration
@@ -124,6 +132,44 @@ fn test_output_formats() {
     }
 }
 
+#[test]
+fn test_json_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("deploy.md"),
+        "# Deploying\n\nRun make deploy.\n",
+    )
+    .unwrap();
+    let index_dir = dir.path().to_str().unwrap();
+
+    // `json` stays a plain array of results
+    let (stdout, stderr, success) = run_cli_command(&[
+        "deploy",
+        "--provider",
+        "local",
+        "--index-dir",
+        index_dir,
+        "--format",
+        "json",
+    ]);
+    assert!(success, "{stderr}");
+    let results: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(results[0]["title"], "Deploying");
+
+    let (stdout, stderr, success) = run_cli_command(&[
+        "deploy",
+        "--provider",
+        "local",
+        "--index-dir",
+        index_dir,
+        "--format",
+        "json-response",
+    ]);
+    assert!(success, "{stderr}");
+    let response: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(response["results"][0]["title"], "Deploying");
+}
+
 #[test]
 fn test_arxiv_paper_search() {
     // Test ArXiv search with actual paper IDs
@@ -0,0 +1,94 @@
+//! Crossref provider tests against a local mock server
+
+use serde_json::json;
+use websearch::providers::crossref::{CrossrefConfig, CrossrefProvider};
+use websearch::types::{SearchOptions, SearchProvider, SortBy, SortOrder};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_works_search_sends_filters_and_mailto() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("query", "deep learning"))
+        .and(query_param("rows", "3"))
+        .and(query_param(
+            "filter",
+            "type:journal-article,from-pub-date:2015",
+        ))
+        .and(query_param("sort", "published"))
+        .and(query_param("order", "desc"))
+        .and(query_param("mailto", "lab@example.org"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "status": "ok",
+            "message-type": "work-list",
+            "message": {
+                "total-results": 81234,
+                "items": [{
+                    "DOI": "10.1038/nature14539",
+                    "title": ["Deep learning"],
+                    "author": [{"given": "Yann", "family": "LeCun"}],
+                    "container-title": ["Nature"],
+                    "issued": {"date-parts": [[2015, 5, 27]]}
+                }]
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = CrossrefProvider::with_config(CrossrefConfig {
+        base_url: server.uri(),
+        mailto: Some("lab@example.org".to_string()),
+        filters: vec![
+            ("type".to_string(), "journal-article".to_string()),
+            ("from-pub-date".to_string(), "2015".to_string()),
+        ],
+    });
+    let options = SearchOptions {
+        query: "deep learning".to_string(),
+        max_results: Some(3),
+        sort_by: Some(SortBy::SubmittedDate),
+        sort_order: Some(SortOrder::Descending),
+        ..Default::default()
+    };
+    let response = provider.search_response(&options).await.unwrap();
+
+    assert_eq!(response.total_results, Some(81234));
+    let result = &response.results[0];
+    assert_eq!(result.url, "https://doi.org/10.1038/nature14539");
+    assert_eq!(result.published_date.as_deref(), Some("2015-05-27"));
+    assert_eq!(result.raw.as_ref().unwrap()["authors"][0], "LeCun, Yann");
+}
+
+#[tokio::test]
+async fn test_page_sets_the_offset() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("rows", "20"))
+        .and(query_param("offset", "40"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "status": "ok",
+            "message-type": "work-list",
+            "message": {"total-results": 81234, "items": []}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = CrossrefProvider::with_config(CrossrefConfig {
+        base_url: server.uri(),
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        query: "deep learning".to_string(),
+        max_results: Some(20),
+        page: Some(3),
+        ..Default::default()
+    };
+    let response = provider.search_response(&options).await.unwrap();
+
+    assert_eq!(response.total_results, Some(81234));
+}
@@ -0,0 +1,155 @@
+//! GitHub provider tests against a local mock server
+
+use serde_json::json;
+use websearch::error::SearchError;
+use websearch::providers::github::{GitHubConfig, GitHubProvider, GitHubSearchKind};
+use websearch::types::{SearchOptions, SearchProvider, SortBy, SortOrder};
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn provider(server: &MockServer, kind: GitHubSearchKind, token: Option<&str>) -> GitHubProvider {
+    GitHubProvider::with_config(GitHubConfig {
+        base_url: server.uri(),
+        token: token.map(str::to_string),
+        kind,
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn test_repository_search_passes_qualifiers_and_sort() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search/repositories"))
+        .and(query_param("q", "tokio language:rust stars:>100"))
+        .and(query_param("sort", "updated"))
+        .and(query_param("order", "desc"))
+        .and(query_param("per_page", "5"))
+        .and(header("authorization", "Bearer gh-token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total_count": 1234,
+            "incomplete_results": false,
+            "items": [{
+                "full_name": "tokio-rs/tokio",
+                "html_url": "https://github.com/tokio-rs/tokio",
+                "description": "A runtime for writing reliable asynchronous applications",
+                "language": "Rust",
+                "stargazers_count": 25000,
+                "forks_count": 2300,
+                "topics": ["async", "rust"],
+                "pushed_at": "2024-05-01T10:00:00Z"
+            }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "tokio language:rust stars:>100".to_string(),
+        max_results: Some(5),
+        sort_by: Some(SortBy::LastUpdatedDate),
+        sort_order: Some(SortOrder::Descending),
+        ..Default::default()
+    };
+    let response = provider(&server, GitHubSearchKind::Repositories, Some("gh-token"))
+        .search_response(&options)
+        .await
+        .unwrap();
+
+    assert_eq!(response.total_results, Some(1234));
+    let result = &response.results[0];
+    assert_eq!(result.title, "tokio-rs/tokio");
+    assert_eq!(result.provider.as_deref(), Some("github"));
+    let raw = result.raw.as_ref().unwrap();
+    assert_eq!(raw["stargazers_count"], 25000);
+    assert_eq!(raw["kind"], "repositories");
+}
+
+#[tokio::test]
+async fn test_code_search_uses_text_match_fragments() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search/code"))
+        .and(header("accept", "application/vnd.github.text-match+json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total_count": 1,
+            "items": [{
+                "name": "lib.rs",
+                "path": "src/lib.rs",
+                "sha": "abc123",
+                "html_url": "https://github.com/owner/name/blob/main/src/lib.rs",
+                "repository": {"full_name": "owner/name", "html_url": "https://github.com/owner/name"},
+                "text_matches": [{"fragment": "pub fn spawn_blocking"}]
+            }]
+        })))
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "spawn_blocking repo:owner/name".to_string(),
+        ..Default::default()
+    };
+    let results = provider(&server, GitHubSearchKind::Code, Some("gh-token"))
+        .search(&options)
+        .await
+        .unwrap();
+    assert_eq!(results[0].title, "owner/name:src/lib.rs");
+    assert_eq!(results[0].snippet.as_deref(), Some("pub fn spawn_blocking"));
+
+    let error = provider(&server, GitHubSearchKind::Code, None)
+        .search(&options)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, SearchError::AuthenticationError(_)));
+}
+
+#[tokio::test]
+async fn test_exhausted_rate_limit_reports_reset_time() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search/issues"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", "1700000000")
+                .set_body_json(json!({"message": "API rate limit exceeded"})),
+        )
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "is:issue is:open label:bug".to_string(),
+        ..Default::default()
+    };
+    let error = provider(&server, GitHubSearchKind::Issues, None)
+        .search(&options)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&error, SearchError::RateLimit(message) if message.contains("2023-11-14T22:13:20")),
+        "{error}"
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_qualifier_is_invalid_input() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search/issues"))
+        .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+            "message": "Validation Failed",
+            "errors": [{"message": "The listed users cannot be searched"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "author:nobody-at-all".to_string(),
+        ..Default::default()
+    };
+    let error = provider(&server, GitHubSearchKind::Issues, None)
+        .search(&options)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, SearchError::InvalidInput(_)));
+}
@@ -0,0 +1,96 @@
+//! Hacker News provider tests against a local mock server
+
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use websearch::providers::hackernews::{HackerNewsConfig, HackerNewsProvider, HackerNewsTag};
+use websearch::types::{SearchOptions, SearchProvider, SortBy};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_search_by_date_with_tags_and_numeric_filters() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search_by_date"))
+        .and(query_param("query", "rust"))
+        .and(query_param("tags", "(story,show_hn)"))
+        .and(query_param(
+            "numericFilters",
+            "points>=50,created_at_i>=1704067200,created_at_i<1706745600",
+        ))
+        .and(query_param("hitsPerPage", "5"))
+        .and(query_param("page", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "hits": [{
+                "objectID": "38800000",
+                "title": "Rust in production",
+                "url": "https://example.com/rust",
+                "author": "alice",
+                "points": 420,
+                "num_comments": 180,
+                "created_at_i": 1704500000,
+                "_tags": ["story", "author_alice", "story_38800000"]
+            }],
+            "nbHits": 57,
+            "page": 1,
+            "nbPages": 12
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = HackerNewsProvider::with_config(HackerNewsConfig {
+        base_url: server.uri(),
+        tags: vec![HackerNewsTag::Story, HackerNewsTag::ShowHn],
+        min_points: Some(50),
+        created_after: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+        created_before: Some(Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap()),
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        query: "rust".to_string(),
+        max_results: Some(5),
+        page: Some(2),
+        sort_by: Some(SortBy::SubmittedDate),
+        ..Default::default()
+    };
+    let response = provider.search_response(&options).await.unwrap();
+
+    assert_eq!(response.total_results, Some(57));
+    let result = &response.results[0];
+    assert_eq!(result.url, "https://example.com/rust");
+    assert_eq!(result.provider.as_deref(), Some("hackernews"));
+    let raw = result.raw.as_ref().unwrap();
+    assert_eq!(raw["points"], 420);
+    assert_eq!(raw["num_comments"], 180);
+    assert_eq!(
+        raw["discussion_url"],
+        "https://news.ycombinator.com/item?id=38800000"
+    );
+}
+
+#[tokio::test]
+async fn test_relevance_search_uses_search_endpoint() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search"))
+        .and(query_param("tags", "comment,author_pg"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "hits": [], "nbHits": 0, "page": 0, "nbPages": 0
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = HackerNewsProvider::with_config(HackerNewsConfig {
+        base_url: server.uri(),
+        tags: vec![HackerNewsTag::Comment],
+        author: Some("pg".to_string()),
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        query: "lisp".to_string(),
+        ..Default::default()
+    };
+    assert!(provider.search(&options).await.unwrap().is_empty());
+}
@@ -0,0 +1,154 @@
+//! Local index provider tests against a temporary directory
+
+use std::fs;
+use websearch::providers::local_index::{
+    LocalIndex, LocalIndexConfig, LocalIndexProvider, INDEX_FILE_NAME,
+};
+use websearch::types::{SearchOptions, SearchProvider};
+use websearch::web_search;
+
+fn write_docs(root: &std::path::Path) {
+    fs::create_dir_all(root.join("runbooks")).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::write(
+        root.join("runbooks/deploy.md"),
+        "# Deploying\n\nRun `make deploy`. To roll back a bad deploy, run the \
+         [rollback script](scripts/rollback.sh) and deploy the previous tag.\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("oncall.html"),
+        "<html><head><title>On-call guide</title><style>.deploy{}</style></head>\
+         <body><p>Page the secondary if a deploy fails twice.</p></body></html>",
+    )
+    .unwrap();
+    fs::write(
+        root.join("notes.txt"),
+        "Lunch menu\nNothing about releases.\n",
+    )
+    .unwrap();
+    fs::write(root.join("image.png"), "deploy").unwrap();
+    fs::write(root.join(".git/HEAD"), "deploy deploy deploy").unwrap();
+}
+
+#[tokio::test]
+async fn test_search_ranks_and_highlights() {
+    let dir = tempfile::tempdir().unwrap();
+    write_docs(dir.path());
+
+    let provider = LocalIndexProvider::open(dir.path()).unwrap();
+    assert_eq!(provider.index().documents().len(), 3);
+    assert!(dir.path().join(INDEX_FILE_NAME).is_file());
+
+    let results = web_search(SearchOptions {
+        query: "deploy rollback".to_string(),
+        provider: Box::new(provider),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].title, "Deploying");
+    assert!(results[0].url.starts_with("file://"));
+    assert!(results[0].url.ends_with("/runbooks/deploy.md"));
+    let snippet = results[0].snippet.as_deref().unwrap();
+    assert!(snippet.contains("**rollback**"), "{snippet}");
+    assert!(!snippet.contains("rollback.sh"), "{snippet}");
+    let raw = results[0].raw.as_ref().unwrap();
+    assert_eq!(raw["path"], "runbooks/deploy.md");
+
+    // Style contents aren't indexed, so only the paragraph matches
+    assert_eq!(results[1].title, "On-call guide");
+    assert_eq!(
+        results[1].snippet.as_deref(),
+        Some("On-call guide Page the secondary if a **deploy** fails twice.")
+    );
+}
+
+#[tokio::test]
+async fn test_pagination_and_total() {
+    let dir = tempfile::tempdir().unwrap();
+    write_docs(dir.path());
+    let provider = LocalIndexProvider::open(dir.path()).unwrap();
+
+    let options = SearchOptions {
+        query: "deploy".to_string(),
+        max_results: Some(1),
+        page: Some(2),
+        ..Default::default()
+    };
+    let response = provider.search_response(&options).await.unwrap();
+
+    assert_eq!(response.total_results, Some(2));
+    assert_eq!(response.results.len(), 1);
+    assert_eq!(response.results[0].title, "On-call guide");
+}
+
+#[test]
+fn test_index_is_reused_until_files_change() {
+    let dir = tempfile::tempdir().unwrap();
+    write_docs(dir.path());
+    let config = LocalIndexConfig {
+        root: dir.path().to_path_buf(),
+        ..Default::default()
+    };
+
+    LocalIndexProvider::with_config(config.clone()).unwrap();
+    let stored = LocalIndex::load(&config.index_path()).unwrap();
+    assert!(!stored.is_stale(&config).unwrap());
+
+    fs::write(
+        dir.path().join("release.md"),
+        "# Release\n\nTag and deploy.\n",
+    )
+    .unwrap();
+    assert!(stored.is_stale(&config).unwrap());
+
+    let provider = LocalIndexProvider::with_config(config.clone()).unwrap();
+    assert_eq!(provider.index().documents().len(), 4);
+    let stored = LocalIndex::load(&config.index_path()).unwrap();
+    assert_eq!(stored.documents().len(), 4);
+}
+
+#[test]
+fn test_skipped_files_do_not_make_the_index_stale() {
+    let dir = tempfile::tempdir().unwrap();
+    write_docs(dir.path());
+    fs::write(dir.path().join("latin1.txt"), b"caf\xe9 deploy\n").unwrap();
+    let config = LocalIndexConfig {
+        root: dir.path().to_path_buf(),
+        ..Default::default()
+    };
+
+    let provider = LocalIndexProvider::with_config(config.clone()).unwrap();
+    assert_eq!(provider.index().documents().len(), 3);
+    let stored = LocalIndex::load(&config.index_path()).unwrap();
+    assert!(!stored.is_stale(&config).unwrap());
+
+    fs::write(dir.path().join("latin1.txt"), "café deploy\n").unwrap();
+    assert!(stored.is_stale(&config).unwrap());
+}
+
+#[test]
+fn test_unsaveable_index_is_kept_in_memory() {
+    let dir = tempfile::tempdir().unwrap();
+    write_docs(dir.path());
+    // A directory where the index file belongs makes saving fail
+    fs::create_dir(dir.path().join(INDEX_FILE_NAME)).unwrap();
+
+    let provider = LocalIndexProvider::open(dir.path()).unwrap();
+
+    assert_eq!(provider.index().documents().len(), 3);
+    assert!(dir.path().join(INDEX_FILE_NAME).is_dir());
+}
+
+#[test]
+fn test_missing_root_is_a_config_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let error = LocalIndexProvider::open(dir.path().join("missing")).unwrap_err();
+    assert!(matches!(
+        error,
+        websearch::error::SearchError::ConfigError(_)
+    ));
+}
@@ -0,0 +1,126 @@
+//! OpenAlex provider tests against a local mock server
+
+use serde_json::json;
+use websearch::providers::openalex::{OpenAlexConfig, OpenAlexProvider};
+use websearch::types::{SearchOptions, SearchProvider, SuggestProvider};
+use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn page(ids: &[&str], next_cursor: Option<&str>) -> serde_json::Value {
+    json!({
+        "meta": {"count": 3, "per_page": 2, "next_cursor": next_cursor},
+        "results": ids.iter().map(|id| json!({
+            "id": format!("https://openalex.org/{id}"),
+            "display_name": format!("Work {id}"),
+            "publication_year": 2020,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+#[tokio::test]
+async fn test_search_all_works_follows_cursors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("search", "open access"))
+        .and(query_param("filter", "is_oa:true"))
+        .and(query_param("per-page", "2"))
+        .and(query_param("cursor", "*"))
+        .and(query_param("mailto", "lab@example.org"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(&["W1", "W2"], Some("c2"))))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "c2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(&["W3"], Some("c3"))))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(query_param("cursor", "c3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(&[], Some("c4"))))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = OpenAlexProvider::with_config(OpenAlexConfig {
+        base_url: server.uri(),
+        mailto: Some("lab@example.org".to_string()),
+        filter: Some("is_oa:true".to_string()),
+    });
+    let options = SearchOptions {
+        query: "open access".to_string(),
+        max_results: Some(2),
+        ..Default::default()
+    };
+    let works = provider.search_all_works(&options, None).await.unwrap();
+
+    let titles: Vec<_> = works
+        .iter()
+        .filter_map(|work| work.display_name.as_deref())
+        .collect();
+    assert_eq!(titles, vec!["Work W1", "Work W2", "Work W3"]);
+}
+
+#[tokio::test]
+async fn test_search_response_requests_the_numbered_page() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/works"))
+        .and(query_param("page", "3"))
+        .and(query_param("per-page", "2"))
+        .and(query_param_is_missing("cursor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(&["W5"], None)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = OpenAlexProvider::with_config(OpenAlexConfig {
+        base_url: server.uri(),
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        query: "open access".to_string(),
+        max_results: Some(2),
+        page: Some(3),
+        ..Default::default()
+    };
+    let response = provider.search_response(&options).await.unwrap();
+
+    assert_eq!(response.results.len(), 1);
+    assert_eq!(response.results[0].title, "Work W5");
+}
+
+#[tokio::test]
+async fn test_suggest_completes_work_titles() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/autocomplete/works"))
+        .and(query_param("q", "attention is"))
+        .and(query_param("mailto", "lab@example.org"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "meta": {"count": 3},
+            "results": [
+                {"id": "https://openalex.org/W1", "display_name": "Attention Is All You Need"},
+                {"id": "https://openalex.org/W2", "display_name": "Attention is  not Explanation"},
+                {"id": "https://openalex.org/W3", "display_name": "Attention Is All You Need"},
+                {"id": "https://openalex.org/W4", "display_name": null},
+            ],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = OpenAlexProvider::with_config(OpenAlexConfig {
+        base_url: server.uri(),
+        mailto: Some("lab@example.org".to_string()),
+        ..Default::default()
+    });
+
+    assert_eq!(
+        provider.suggest(" attention is ").await.unwrap(),
+        vec!["Attention Is All You Need", "Attention is not Explanation"]
+    );
+    assert!(provider.suggest("  ").await.unwrap().is_empty());
+}
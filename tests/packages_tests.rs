@@ -0,0 +1,191 @@
+//! Package registry provider tests against a local mock server
+
+use serde_json::json;
+use std::time::{Duration, Instant};
+use websearch::error::SearchError;
+use websearch::providers::packages::{
+    CratesIoConfig, CratesIoProvider, NpmConfig, NpmProvider, PyPiConfig, PyPiProvider,
+};
+use websearch::types::{SearchOptions, SearchProvider, SortBy};
+use wiremock::matchers::{header_regex, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_crates_io_search() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/crates"))
+        .and(query_param("q", "http client"))
+        .and(query_param("sort", "recent-updates"))
+        .and(query_param("per_page", "3"))
+        .and(header_regex("user-agent", r"^websearch/.+ \(https://"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "crates": [{
+                "name": "reqwest",
+                "description": "higher level HTTP client library",
+                "max_version": "0.13.0-rc.1",
+                "max_stable_version": "0.12.9",
+                "downloads": 250000000,
+                "repository": "https://github.com/seanmonstar/reqwest",
+                "homepage": null,
+                "updated_at": "2024-10-28T14:00:00.000000+00:00",
+                "keywords": ["http", "request", "client"]
+            }],
+            "meta": {"total": 1500}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = CratesIoProvider::with_config(CratesIoConfig {
+        base_url: format!("{}/api/v1", server.uri()),
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        query: "http client".to_string(),
+        max_results: Some(3),
+        sort_by: Some(SortBy::LastUpdatedDate),
+        ..Default::default()
+    };
+    let response = provider.search_response(&options).await.unwrap();
+
+    assert_eq!(response.total_results, Some(1500));
+    let result = &response.results[0];
+    assert_eq!(result.title, "reqwest 0.12.9");
+    assert_eq!(result.url, "https://crates.io/crates/reqwest");
+    let raw = result.raw.as_ref().unwrap();
+    assert_eq!(raw["downloads"], 250000000u64);
+    assert_eq!(raw["repository"], "https://github.com/seanmonstar/reqwest");
+}
+
+#[tokio::test]
+async fn test_crates_io_spaces_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/crates"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"crates": []})))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let provider = CratesIoProvider::with_config(CratesIoConfig {
+        base_url: format!("{}/api/v1", server.uri()),
+        request_delay: Duration::from_millis(500),
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        query: "serde".to_string(),
+        ..Default::default()
+    };
+
+    let started = Instant::now();
+    provider.search(&options).await.unwrap();
+    provider.search(&options).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn test_npm_search_pages_with_from() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/-/v1/search"))
+        .and(query_param("text", "keywords:cli"))
+        .and(query_param("size", "20"))
+        .and(query_param("from", "20"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "objects": [{
+                "package": {
+                    "name": "commander",
+                    "version": "12.1.0",
+                    "description": "the complete solution for node.js command-line programs",
+                    "keywords": ["commander", "cli"],
+                    "date": "2024-05-18T07:00:00.000Z",
+                    "links": {
+                        "npm": "https://www.npmjs.com/package/commander",
+                        "repository": "https://github.com/tj/commander.js"
+                    }
+                },
+                "downloads": {"monthly": 480000000, "weekly": 120000000}
+            }],
+            "total": 9000
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = NpmProvider::with_config(NpmConfig {
+        base_url: server.uri(),
+    });
+    let options = SearchOptions {
+        query: "keywords:cli".to_string(),
+        max_results: Some(20),
+        page: Some(2),
+        ..Default::default()
+    };
+    let results = provider.search(&options).await.unwrap();
+
+    assert_eq!(results[0].title, "commander 12.1.0");
+    let raw = results[0].raw.as_ref().unwrap();
+    assert_eq!(raw["registry"], "npm");
+    assert_eq!(raw["downloads"], 480000000u64);
+    assert_eq!(raw["repository"], "https://github.com/tj/commander.js");
+}
+
+#[tokio::test]
+async fn test_npm_page_overflow_is_invalid_input() {
+    let provider = NpmProvider::with_config(NpmConfig {
+        base_url: "http://127.0.0.1:9".to_string(),
+    });
+    let options = SearchOptions {
+        query: "keywords:cli".to_string(),
+        max_results: Some(20),
+        page: Some(u32::MAX),
+        ..Default::default()
+    };
+
+    let error = provider.search(&options).await.unwrap_err();
+    assert!(matches!(error, SearchError::InvalidInput(_)), "{error:?}");
+}
+
+#[tokio::test]
+async fn test_pypi_looks_up_each_named_project() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/pypi/flask-sqlalchemy/json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "info": {
+                "name": "Flask-SQLAlchemy",
+                "version": "3.1.1",
+                "summary": "Add SQLAlchemy support to your Flask application.",
+                "home_page": null,
+                "project_urls": {"Source": "https://github.com/pallets-eco/flask-sqlalchemy/"}
+            },
+            "urls": [{"upload_time_iso_8601": "2023-09-11T21:42:34.514Z"}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/pypi/not-a-real-project/json"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = PyPiProvider::with_config(PyPiConfig {
+        base_url: server.uri(),
+    });
+    let options = SearchOptions {
+        query: "Flask_SQLAlchemy, not-a-real-project flask-sqlalchemy".to_string(),
+        ..Default::default()
+    };
+    let results = provider.search(&options).await.unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].title, "Flask-SQLAlchemy 3.1.1");
+    assert_eq!(results[0].url, "https://pypi.org/project/Flask-SQLAlchemy/");
+    assert_eq!(
+        results[0].raw.as_ref().unwrap()["repository"],
+        "https://github.com/pallets-eco/flask-sqlalchemy/"
+    );
+}
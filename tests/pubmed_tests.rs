@@ -0,0 +1,139 @@
+//! PubMed provider tests against a local mock server
+
+use serde_json::json;
+use websearch::error::SearchError;
+use websearch::providers::pubmed::{PubMedConfig, PubMedProvider};
+use websearch::types::{SearchOptions, SearchProvider};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn summary(pmids: &[&str]) -> serde_json::Value {
+    let mut result = json!({ "uids": pmids });
+    for pmid in pmids {
+        result[*pmid] = json!({
+            "uid": pmid,
+            "title": format!("Article {pmid}."),
+            "authors": [{"name": "Smith J", "authtype": "Author"}],
+            "fulljournalname": "Journal of Tests",
+            "sortpubdate": "2021/05/04 00:00",
+            "articleids": [{"idtype": "doi", "value": format!("10.1000/{pmid}")}],
+        });
+    }
+    json!({ "result": result })
+}
+
+fn provider(server: &MockServer) -> PubMedProvider {
+    PubMedProvider::with_config(PubMedConfig {
+        base_url: server.uri(),
+        api_key: Some("ncbi-key".to_string()),
+        email: Some("lab@example.org".to_string()),
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn test_search_runs_esearch_esummary_and_efetch() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/esearch.fcgi"))
+        .and(query_param("db", "pubmed"))
+        .and(query_param("term", "asthma[mh]"))
+        .and(query_param("retmax", "2"))
+        .and(query_param("api_key", "ncbi-key"))
+        .and(query_param("email", "lab@example.org"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "esearchresult": {"count": "57", "retmax": "2", "retstart": "0", "idlist": ["11", "22"]}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/esummary.fcgi"))
+        .and(query_param("id", "11,22"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(summary(&["11", "22"])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/efetch.fcgi"))
+        .and(query_param("id", "11,22"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"<PubmedArticleSet><PubmedArticle><MedlineCitation><PMID>22</PMID>
+            <Article><Abstract><AbstractText>Second abstract.</AbstractText></Abstract></Article>
+            </MedlineCitation></PubmedArticle></PubmedArticleSet>"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "asthma[mh]".to_string(),
+        max_results: Some(2),
+        ..Default::default()
+    };
+    let response = provider(&server).search_response(&options).await.unwrap();
+
+    assert_eq!(response.total_results, Some(57));
+    assert_eq!(response.results.len(), 2);
+    assert_eq!(response.results[0].title, "Article 11");
+    assert_eq!(
+        response.results[0].snippet.as_deref(),
+        Some("Journal of Tests")
+    );
+    assert_eq!(
+        response.results[1].snippet.as_deref(),
+        Some("Second abstract.")
+    );
+    assert_eq!(
+        response.results[1].raw.as_ref().unwrap()["doi"],
+        "10.1000/22"
+    );
+}
+
+#[tokio::test]
+async fn test_id_list_skips_esearch() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/esearch.fcgi"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/esummary.fcgi"))
+        .and(query_param("id", "33"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(summary(&["33"])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = PubMedProvider::with_config(PubMedConfig {
+        base_url: server.uri(),
+        fetch_abstracts: false,
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        id_list: Some("PMID:33".to_string()),
+        ..Default::default()
+    };
+    let results = provider.search(&options).await.unwrap();
+    assert_eq!(results[0].url, "https://pubmed.ncbi.nlm.nih.gov/33/");
+}
+
+#[tokio::test]
+async fn test_too_many_requests_is_a_rate_limit_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(429))
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "asthma".to_string(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        provider(&server).search(&options).await,
+        Err(SearchError::RateLimit(_))
+    ));
+}
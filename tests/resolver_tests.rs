@@ -0,0 +1,177 @@
+//! Identifier resolver tests against a local mock server
+
+use serde_json::json;
+use std::time::Duration;
+use websearch::error::SearchError;
+use websearch::providers::arxiv::ArxivConfig;
+use websearch::providers::pubmed::PubMedConfig;
+use websearch::providers::semantic_scholar::SemanticScholarConfig;
+use websearch::resolver::{Resolver, ResolverConfig};
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const ATOM_FEED: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:opensearch="http://a9.com/-/spec/opensearch/1.1/" xmlns:arxiv="http://arxiv.org/schemas/atom">
+  <opensearch:totalResults>2</opensearch:totalResults>
+  <opensearch:startIndex>0</opensearch:startIndex>
+  <opensearch:itemsPerPage>2</opensearch:itemsPerPage>
+  <entry>
+    <id>http://arxiv.org/abs/2403.00002v1</id>
+    <published>2024-03-01T00:00:00Z</published>
+    <title>Second paper</title>
+    <summary>Abstract.</summary>
+    <author><name>Jane Doe</name></author>
+  </entry>
+  <entry>
+    <id>http://arxiv.org/abs/2403.00001v3</id>
+    <published>2024-03-01T00:00:00Z</published>
+    <title>First paper</title>
+    <summary>Abstract.</summary>
+    <author><name>Jane Doe</name></author>
+  </entry>
+</feed>"#;
+
+fn resolver(server: &MockServer) -> Resolver {
+    Resolver::with_config(ResolverConfig {
+        doi_base_url: format!("{}/doi", server.uri()),
+        arxiv: ArxivConfig {
+            base_url: format!("{}/api/query", server.uri()),
+            request_delay: Duration::ZERO,
+            ..Default::default()
+        },
+        pubmed: PubMedConfig {
+            base_url: format!("{}/eutils", server.uri()),
+            fetch_abstracts: false,
+            ..Default::default()
+        },
+        semantic_scholar: SemanticScholarConfig {
+            base_url: format!("{}/graph/v1", server.uri()),
+            ..Default::default()
+        },
+    })
+}
+
+#[tokio::test]
+async fn test_resolve_all_keeps_order_and_batches_arxiv_ids() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/doi/10.1000/xyz"))
+        .and(header("accept", "application/vnd.citationstyles.csl+json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "type": "article-journal",
+            "title": "Negotiated content",
+            "author": [{"family": "Smith", "given": "Ann"}],
+            "container-title": ["Journal of Tests"],
+            "issued": {"date-parts": [[2020, 2]]},
+            "DOI": "10.1000/xyz",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/query"))
+        .and(query_param("id_list", "2403.00001,2403.00002v1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(ATOM_FEED))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/eutils/esummary.fcgi"))
+        .and(query_param("id", "31452104"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "result": {
+                "uids": ["31452104"],
+                "31452104": {
+                    "uid": "31452104",
+                    "title": "A PubMed article.",
+                    "authors": [{"name": "Lee K", "authtype": "Author"}],
+                    "fulljournalname": "Journal of Medicine",
+                    "sortpubdate": "2019/08/27 00:00",
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(
+            "/graph/v1/paper/204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "paperId": "204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+            "title": "Attention is All you Need",
+            "year": 2017,
+            "authors": [{"name": "Ashish Vaswani"}],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let results = resolver(&server)
+        .resolve_all(&[
+            "arXiv:2403.00001",
+            "https://doi.org/10.1000/xyz",
+            "not an id",
+            "PMID:31452104",
+            "2403.00002v1",
+            "204e3073870fae3d05bcbc2f6a8e263d9b72e776",
+        ])
+        .await;
+
+    assert_eq!(results.len(), 6);
+    let first = results[0].as_ref().unwrap();
+    assert_eq!(first.title, "First paper");
+
+    let doi = results[1].as_ref().unwrap();
+    assert_eq!(doi.title, "Negotiated content");
+    assert_eq!(doi.published_date.as_deref(), Some("2020-02"));
+    let raw = doi.raw.as_ref().unwrap();
+    assert_eq!(raw["venue"], "Journal of Tests");
+    assert_eq!(raw["authors"][0], "Smith, Ann");
+
+    assert!(matches!(results[2], Err(SearchError::InvalidInput(_))));
+    assert_eq!(results[3].as_ref().unwrap().title, "A PubMed article");
+    assert_eq!(results[4].as_ref().unwrap().title, "Second paper");
+    assert_eq!(
+        results[5].as_ref().unwrap().title,
+        "Attention is All you Need"
+    );
+}
+
+#[tokio::test]
+async fn test_unknown_doi_is_invalid_input() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/doi/10.1000/missing"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let error = resolver(&server)
+        .resolve("doi:10.1000/missing")
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, SearchError::InvalidInput(message) if message.contains("10.1000/missing"))
+    );
+}
+
+#[tokio::test]
+async fn test_doi_reserved_characters_are_encoded() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/doi/10.1000/a%23b%3Fc%25d"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "DOI": "10.1000/a#b?c%d",
+            "title": "Awkward identifier",
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let result = resolver(&server)
+        .resolve("doi:10.1000/a#b?c%d")
+        .await
+        .unwrap();
+    assert_eq!(result.title, "Awkward identifier");
+}
@@ -0,0 +1,215 @@
+//! Semantic Scholar provider tests against a local mock server
+
+use serde_json::json;
+use websearch::error::SearchError;
+use websearch::providers::arxiv::ArxivId;
+use websearch::providers::semantic_scholar::{SemanticScholarConfig, SemanticScholarProvider};
+use websearch::types::{SearchOptions, SearchProvider, SuggestProvider};
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn paper(id: &str, citations: u64) -> serde_json::Value {
+    json!({
+        "paperId": id,
+        "externalIds": {"ArXiv": "1706.03762"},
+        "title": format!("Paper {id}"),
+        "year": 2017,
+        "authors": [{"authorId": "1", "name": "Ashish Vaswani"}],
+        "citationCount": citations,
+    })
+}
+
+fn provider(server: &MockServer) -> SemanticScholarProvider {
+    SemanticScholarProvider::with_config(SemanticScholarConfig {
+        base_url: format!("{}/graph/v1", server.uri()),
+        api_key: Some("secret".to_string()),
+    })
+}
+
+#[tokio::test]
+async fn test_search_sends_api_key_and_reports_total() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/search"))
+        .and(query_param("query", "attention"))
+        .and(query_param("limit", "5"))
+        .and(header("x-api-key", "secret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total": 1234,
+            "offset": 0,
+            "next": 5,
+            "data": [paper("a", 10), paper("b", 20)],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let options = SearchOptions {
+        query: "attention".to_string(),
+        max_results: Some(5),
+        ..Default::default()
+    };
+    let response = provider(&server).search_response(&options).await.unwrap();
+
+    assert_eq!(response.total_results, Some(1234));
+    assert_eq!(response.results.len(), 2);
+    assert_eq!(
+        response.results[1].raw.as_ref().unwrap()["citation_count"],
+        20
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_arxiv_id_and_doi() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/arXiv:1706.03762"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(paper("a", 1)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/DOI:10.18653/v1/N18-3011"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(paper("b", 2)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = provider(&server);
+    let id = ArxivId::parse("1706.03762v7").unwrap();
+    assert_eq!(provider.paper_by_arxiv_id(&id).await.unwrap().paper_id, "a");
+    let by_doi = provider
+        .paper_by_doi("https://doi.org/10.18653/v1/N18-3011")
+        .await
+        .unwrap();
+    assert_eq!(by_doi.paper_id, "b");
+}
+
+#[tokio::test]
+async fn test_citations_follow_next_offset() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/abc/citations"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "offset": 0,
+            "next": 2,
+            "data": [{"citingPaper": paper("c1", 1)}, {"citingPaper": paper("c2", 2)}],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/abc/citations"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "offset": 2,
+            "data": [{"citingPaper": paper("c3", 3)}],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/abc/references"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "offset": 0,
+            "data": [{"citedPaper": paper("r1", 1)}, {"citedPaper": null}],
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = provider(&server);
+    let citing = provider.citations("abc", 10).await.unwrap();
+    let ids: Vec<&str> = citing.iter().map(|paper| paper.paper_id.as_str()).collect();
+    assert_eq!(ids, vec!["c1", "c2", "c3"]);
+
+    let references = provider.references("abc", 10).await.unwrap();
+    assert_eq!(references.len(), 1);
+}
+
+#[tokio::test]
+async fn test_unresolved_references_are_dropped() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/abc/references"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "offset": 0,
+            "data": [
+                {"citedPaper": {
+                    "paperId": null,
+                    "externalIds": null,
+                    "title": "Some unmatched reference",
+                    "authors": [],
+                }},
+                {"citedPaper": {
+                    "paperId": "r1",
+                    "externalIds": null,
+                    "title": null,
+                }},
+            ],
+        })))
+        .mount(&server)
+        .await;
+
+    let references = provider(&server).references("abc", 10).await.unwrap();
+
+    assert_eq!(references.len(), 1);
+    assert_eq!(references[0].paper_id, "r1");
+    assert_eq!(references[0].title, "");
+    assert!(references[0].external_ids.doi.is_none());
+}
+
+#[tokio::test]
+async fn test_rate_limit_and_missing_paper_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/search"))
+        .respond_with(ResponseTemplate::new(429))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/missing"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({"error": "Paper not found"})))
+        .mount(&server)
+        .await;
+
+    let provider = provider(&server);
+    let options = SearchOptions {
+        query: "attention".to_string(),
+        ..Default::default()
+    };
+    assert!(matches!(
+        provider.search(&options).await,
+        Err(SearchError::RateLimit(_))
+    ));
+    assert!(matches!(
+        provider.paper("missing").await,
+        Err(SearchError::InvalidInput(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_suggest_completes_paper_titles() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/graph/v1/paper/autocomplete"))
+        .and(query_param("query", "attention is"))
+        .and(header("x-api-key", "secret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "matches": [
+                {"id": "204e3073", "title": "Attention is All you Need", "authorsYear": "Vaswani et al., 2017"},
+                {"id": "1a2b", "title": "Attention Is Not Explanation"},
+                {"id": "3c4d", "title": null},
+            ],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let suggestions = provider(&server).suggest("attention is").await.unwrap();
+
+    assert_eq!(
+        suggestions,
+        vec!["Attention is All you Need", "Attention Is Not Explanation"]
+    );
+}
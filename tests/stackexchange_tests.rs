@@ -0,0 +1,146 @@
+//! Stack Exchange provider tests against a local mock server
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::json;
+use std::io::Write;
+use std::time::{Duration, Instant};
+use websearch::error::SearchError;
+use websearch::providers::stackexchange::{
+    StackExchangeConfig, StackExchangeProvider, StackExchangeQuota,
+};
+use websearch::types::{SearchOptions, SearchProvider, SortBy};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// The API always gzips its responses
+fn gzipped(body: serde_json::Value) -> ResponseTemplate {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body.to_string().as_bytes()).unwrap();
+    ResponseTemplate::new(200)
+        .insert_header("content-encoding", "gzip")
+        .insert_header("content-type", "application/json; charset=utf-8")
+        .set_body_bytes(encoder.finish().unwrap())
+}
+
+fn question(id: u64) -> serde_json::Value {
+    json!({
+        "question_id": id,
+        "title": format!("How do I use tokio::select! ({id})?"),
+        "link": format!("https://stackoverflow.com/questions/{id}"),
+        "tags": ["rust", "tokio"],
+        "score": 12,
+        "answer_count": 2,
+        "is_answered": true,
+        "accepted_answer_id": 99,
+        "creation_date": 1700000000,
+        "body": "<p>Question body</p>"
+    })
+}
+
+#[tokio::test]
+async fn test_search_decodes_gzip_and_reports_quota() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search/advanced"))
+        .and(query_param("site", "superuser"))
+        .and(query_param("q", "select"))
+        .and(query_param("tagged", "rust;tokio"))
+        .and(query_param("accepted", "True"))
+        .and(query_param("sort", "creation"))
+        .and(query_param("key", "app-key"))
+        .respond_with(gzipped(json!({
+            "items": [question(1)],
+            "has_more": true,
+            "quota_max": 10000,
+            "quota_remaining": 9876
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = StackExchangeProvider::with_config(StackExchangeConfig {
+        base_url: server.uri(),
+        site: "superuser".to_string(),
+        key: Some("app-key".to_string()),
+        tagged: vec!["rust".to_string(), "tokio".to_string()],
+        accepted: Some(true),
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        query: "select".to_string(),
+        sort_by: Some(SortBy::SubmittedDate),
+        ..Default::default()
+    };
+    let results = provider.search(&options).await.unwrap();
+
+    assert_eq!(results[0].title, "How do I use tokio::select! (1)?");
+    let raw = results[0].raw.as_ref().unwrap();
+    assert_eq!(raw["answer_count"], 2);
+    assert_eq!(raw["is_answered"], true);
+    assert_eq!(raw["site"], "superuser");
+    assert_eq!(
+        provider.quota(),
+        Some(StackExchangeQuota {
+            max: 10000,
+            remaining: 9876
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_backoff_delays_next_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search/advanced"))
+        .respond_with(gzipped(json!({
+            "items": [question(2)],
+            "quota_max": 300,
+            "quota_remaining": 250,
+            "backoff": 1
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let provider = StackExchangeProvider::with_config(StackExchangeConfig {
+        base_url: server.uri(),
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        query: "lifetimes".to_string(),
+        ..Default::default()
+    };
+
+    let page = provider.search_questions(&options).await.unwrap();
+    assert_eq!(page.backoff, Some(1));
+
+    let started = Instant::now();
+    provider.search_questions(&options).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(900));
+}
+
+#[tokio::test]
+async fn test_throttle_violation_is_rate_limit() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search/advanced"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "error_id": 502,
+            "error_name": "throttle_violation",
+            "error_message": "too many requests from this IP, more requests available in 3600 seconds"
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = StackExchangeProvider::with_config(StackExchangeConfig {
+        base_url: server.uri(),
+        ..Default::default()
+    });
+    let options = SearchOptions {
+        query: "borrow checker".to_string(),
+        ..Default::default()
+    };
+    let error = provider.search(&options).await.unwrap_err();
+    assert!(matches!(error, SearchError::RateLimit(message) if message.contains("3600 seconds")));
+}